@@ -6,37 +6,37 @@ wt() {
     local cmd="${1:-}"
 
     case "$cmd" in
-        add)
+        add|review|scratch)
             shift
 
             # Preserve native help/version output.
             for arg in "$@"; do
                 case "$arg" in
                     -h|--help|-V|--version)
-                        wt-core add "$@"
+                        wt-core "$cmd" "$@"
                         return $?
                         ;;
                 esac
             done
 
             local target
-            target=$(wt-core add "$@" --print-cd-path 2>/dev/null)
+            target=$(wt-core "$cmd" "$@" --print-cd-path 2>/dev/null)
             if [ $? -eq 0 ] && [ -n "$target" ]; then
                 cd "$target" || return 1
             else
                 # Re-run without --print-cd-path to show the error message
-                wt-core add "$@"
+                wt-core "$cmd" "$@"
                 return $?
             fi
             ;;
-        go)
+        go|ui)
             shift
 
             # Preserve native help/version output.
             for arg in "$@"; do
                 case "$arg" in
                     -h|--help|-V|--version)
-                        wt-core go "$@"
+                        wt-core "$cmd" "$@"
                         return $?
                         ;;
                 esac
@@ -49,14 +49,14 @@ wt() {
             done
 
             if [ "$want_json" = true ]; then
-                wt-core go "$@"
+                wt-core "$cmd" "$@"
                 return $?
             fi
 
             local target rc
-            # --print-cd-path works with the interactive picker:
+            # --print-cd-path works with the interactive picker and `wt ui`:
             # the picker UI renders on stderr/tty, the path goes to stdout.
-            target=$(wt-core go "$@" --print-cd-path)
+            target=$(wt-core "$cmd" "$@" --print-cd-path)
             rc=$?
             if [ $rc -eq 0 ] && [ -n "$target" ]; then
                 cd "$target" || return 1
@@ -211,3 +211,43 @@ wt() {
             ;;
     esac
 }
+
+# ── Completion ───────────────────────────────────────────────────────
+# `wt-core init bash` emits clap-generated completions (`_wt`) ahead of this
+# binding. Branch and revision arguments are completed live via
+# `wt-core complete <context>`; everything else falls through to `_wt`.
+
+# Print the candidate context for subcommand $1 after previous word $2.
+_wt_complete_context() {
+    case "$2" in
+        --base|--against|--detach|--mainline) echo revisions; return ;;
+        --into|--to) echo worktrees; return ;;
+        --repo|--tool|--pr|--sparse|--color) return ;;
+    esac
+    case "$1" in
+        go|remove|merge|diff|link|sync) echo worktrees ;;
+        add) echo branches ;;
+        scratch) echo revisions ;;
+    esac
+}
+
+_wt_complete() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local context=""
+    if [ "$COMP_CWORD" -ge 2 ] && [[ "$cur" != -* ]]; then
+        context=$(_wt_complete_context "${COMP_WORDS[1]}" "${COMP_WORDS[COMP_CWORD-1]}")
+    fi
+
+    if [ -n "$context" ]; then
+        local IFS=$'\n'
+        # shellcheck disable=SC2207
+        COMPREPLY=($(compgen -W "$(wt-core complete "$context" 2>/dev/null)" -- "$cur"))
+        return 0
+    fi
+
+    if declare -F _wt >/dev/null; then
+        _wt "$@"
+    fi
+}
+
+complete -F _wt_complete -o bashdefault -o default wt
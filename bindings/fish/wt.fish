@@ -5,32 +5,32 @@ function wt --description "Git worktree manager"
     set -l cmd $argv[1]
 
     switch "$cmd"
-        case add
+        case add review scratch
             set -e argv[1]
 
             # Preserve native help/version output.
             for arg in $argv
                 if test "$arg" = "-h" -o "$arg" = "--help" -o "$arg" = "-V" -o "$arg" = "--version"
-                    wt-core add $argv
+                    wt-core $cmd $argv
                     return $status
                 end
             end
 
-            set -l target (wt-core add $argv --print-cd-path 2>/dev/null)
+            set -l target (wt-core $cmd $argv --print-cd-path 2>/dev/null)
             if test $status -eq 0 -a -n "$target"
                 cd "$target"
             else
-                wt-core add $argv
+                wt-core $cmd $argv
                 return $status
             end
 
-        case go
+        case go ui
             set -e argv[1]
 
             # Preserve native help/version output.
             for arg in $argv
                 if test "$arg" = "-h" -o "$arg" = "--help" -o "$arg" = "-V" -o "$arg" = "--version"
-                    wt-core go $argv
+                    wt-core $cmd $argv
                     return $status
                 end
             end
@@ -44,13 +44,13 @@ function wt --description "Git worktree manager"
             end
 
             if test "$want_json" = true
-                wt-core go $argv
+                wt-core $cmd $argv
                 return $status
             end
 
-            # --print-cd-path works with the interactive picker:
+            # --print-cd-path works with the interactive picker and `wt ui`:
             # the picker UI renders on stderr/tty, the path goes to stdout.
-            set -l target (wt-core go $argv --print-cd-path)
+            set -l target (wt-core $cmd $argv --print-cd-path)
             set -l rc $status
             if test $rc -eq 0 -a -n "$target"
                 cd "$target"
@@ -184,3 +184,16 @@ function wt --description "Git worktree manager"
             wt-core $argv  # $argv still includes the subcommand
     end
 end
+
+# ── Completion ───────────────────────────────────────────────────────
+# `wt-core init fish` emits clap-generated completions for subcommands and
+# flags ahead of this binding; branch and revision arguments are completed
+# live via `wt-core complete <context>`.
+complete -c wt -n "__fish_seen_subcommand_from go remove merge diff link sync" -f -a "(wt-core complete worktrees 2>/dev/null)"
+complete -c wt -n "__fish_seen_subcommand_from add" -f -a "(wt-core complete branches 2>/dev/null)"
+complete -c wt -n "__fish_seen_subcommand_from scratch" -f -a "(wt-core complete revisions 2>/dev/null)"
+complete -c wt -n "__fish_seen_subcommand_from add" -l base -l detach -x -a "(wt-core complete revisions 2>/dev/null)"
+complete -c wt -n "__fish_seen_subcommand_from list diff" -l against -x -a "(wt-core complete revisions 2>/dev/null)"
+complete -c wt -n "__fish_seen_subcommand_from prune sync" -l mainline -x -a "(wt-core complete revisions 2>/dev/null)"
+complete -c wt -n "__fish_seen_subcommand_from merge" -l into -x -a "(wt-core complete worktrees 2>/dev/null)"
+complete -c wt -n "__fish_seen_subcommand_from move-changes" -l to -x -a "(wt-core complete worktrees 2>/dev/null)"
@@ -0,0 +1,95 @@
+# wt — Git worktree manager (PowerShell binding)
+# Add this line to your $PROFILE:
+#   Invoke-Expression (& wt-core init powershell | Out-String)
+
+# Whether the arguments ask for native help/version output.
+function Test-WtHelpArg([string[]]$Arguments) {
+    foreach ($arg in $Arguments) {
+        if ($arg -in '-h', '--help', '-V', '--version') { return $true }
+    }
+    return $false
+}
+
+# Whether $Path lies inside the worktree at $Root.
+function Test-WtInside([string]$Path, [string]$Root) {
+    if (-not $Root) { return $false }
+    # git reports forward slashes on Windows; GetFullPath normalizes them.
+    $Root = [System.IO.Path]::GetFullPath($Root)
+    $comparison = if ([System.IO.Path]::DirectorySeparatorChar -eq '\') {
+        [System.StringComparison]::OrdinalIgnoreCase
+    } else {
+        [System.StringComparison]::Ordinal
+    }
+    return $Path.StartsWith($Root, $comparison)
+}
+
+# Follow the `event` of a JSON response: "switch" enters cd_path, "reset"
+# leaves a removed worktree for the repository root.
+function Invoke-WtEvent([string]$Json, [string]$CwdBefore) {
+    $response = $Json | ConvertFrom-Json -ErrorAction SilentlyContinue
+    if (-not $response) { return }
+    switch ($response.event) {
+        'switch' {
+            if ($response.cd_path) { Set-Location -LiteralPath $response.cd_path }
+        }
+        'reset' {
+            if (Test-WtInside $CwdBefore $response.removed_path) {
+                Set-Location -LiteralPath $response.repo_root
+            }
+        }
+    }
+}
+
+function wt {
+    if ($args.Count -eq 0) {
+        wt-core --help
+        return
+    }
+
+    $cmd = [string]$args[0]
+    $rest = @($args | Select-Object -Skip 1)
+
+    # Unwrapped subcommands and native help/version output pass straight through.
+    if ($cmd -notin 'add', 'review', 'scratch', 'go', 'ui', 'remove', 'merge' -or (Test-WtHelpArg $rest)) {
+        wt-core @args
+        return
+    }
+
+    $cwdBefore = (Get-Location).ProviderPath
+
+    if ($rest -contains '--json') {
+        $output = (wt-core @args) -join "`n"
+        if ($LASTEXITCODE -eq 0) { Invoke-WtEvent $output $cwdBefore }
+        $output
+        return
+    }
+
+    switch ($cmd) {
+        { $_ -in 'add', 'review', 'scratch', 'go', 'ui' } {
+            # --print-cd-path works with the interactive picker:
+            # the picker UI renders on stderr/tty, the path goes to stdout.
+            $target = wt-core @args --print-cd-path
+            if ($LASTEXITCODE -eq 0 -and $target) { Set-Location -LiteralPath $target }
+        }
+        'remove' {
+            # --print-paths outputs three lines: removed_path, repo_root, branch.
+            $lines = @(wt-core @args --print-paths)
+            if ($LASTEXITCODE -ne 0) { return }
+            $removedPath, $repoRoot, $branch = $lines
+            if (Test-WtInside $cwdBefore $removedPath) { Set-Location -LiteralPath $repoRoot }
+            "Removed worktree and branch '$branch'"
+        }
+        'merge' {
+            # --print-paths outputs: repo_root, branch, mainline, cleaned_up, removed_path, pushed
+            $lines = @(wt-core @args --print-paths)
+            if ($LASTEXITCODE -ne 0) { return }
+            $repoRoot, $branch, $mainline, $cleanedUp, $removedPath, $pushed = $lines
+            if ($cleanedUp -eq 'true' -and (Test-WtInside $cwdBefore $removedPath)) {
+                Set-Location -LiteralPath $repoRoot
+            }
+            "Merged '$branch' into $mainline"
+            if ($cleanedUp -eq 'true') { "Removed worktree and branch '$branch'" }
+            if ($pushed -eq 'true') { "Pushed $mainline to origin" }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::path::Path;
+
+use crate::domain::{BranchName, RepoRoot, Worktree, WorktreeStats};
+use crate::error::Result;
+use crate::git;
+
+#[cfg(test)]
+pub(crate) mod fake;
+#[cfg(feature = "gix")]
+mod native;
+
+#[cfg(feature = "gix")]
+pub use native::GixBackend;
+
+/// The Git operations worktree orchestration needs.
+///
+/// Methods mirror the free functions of the same name in the private `git`
+/// module; see [`ProcessGit`] for their exact behavior.
+pub trait GitBackend {
+    /// All worktrees, main first, after pruning stale registrations.
+    fn list_worktrees(&self, repo: &RepoRoot) -> Result<Vec<Worktree>>;
+
+    /// Create a worktree at `dir` on a new `branch` from `base` (default
+    /// `HEAD`).
+    fn add_worktree(
+        &self,
+        repo: &RepoRoot,
+        dir: &Path,
+        branch: &BranchName,
+        base: Option<&str>,
+    ) -> Result<()>;
+
+    /// Remove the worktree at `dir`; `force` discards local changes.
+    fn remove_worktree(&self, repo: &RepoRoot, dir: &Path, force: bool) -> Result<()>;
+
+    /// Lock the linked worktree at `dir`, with an optional `reason`.
+    fn lock_worktree(&self, repo: &RepoRoot, dir: &Path, reason: Option<&str>) -> Result<()>;
+
+    /// Unlock the linked worktree at `dir`.
+    fn unlock_worktree(&self, repo: &RepoRoot, dir: &Path) -> Result<()>;
+
+    /// Whether the worktree at `path` is locked.
+    fn is_locked(&self, path: &Path) -> bool;
+
+    /// Whether the worktree at `path` has uncommitted changes to tracked
+    /// files.
+    fn is_dirty(&self, path: &Path) -> Result<bool>;
+
+    /// Whether a local branch exists.
+    fn branch_exists(&self, repo: &RepoRoot, branch: &BranchName) -> bool;
+
+    /// Delete a local branch; without `force` only if it is merged.
+    fn delete_branch(&self, repo: &RepoRoot, branch: &BranchName, force: bool) -> Result<()>;
+
+    /// Whether `rev` resolves to a commit.
+    fn rev_exists(&self, repo: &RepoRoot, rev: &str) -> bool;
+
+    /// Detect the mainline branch.
+    fn resolve_mainline(&self, repo: &RepoRoot) -> Result<String>;
+
+    /// Whether `branch` is an ancestor of `mainline`.
+    fn is_ancestor(&self, repo: &RepoRoot, branch: &str, mainline: &str) -> bool;
+
+    /// Whether every patch of `branch` is already in `mainline`.
+    fn cherry(&self, repo: &RepoRoot, mainline: &str, branch: &str) -> bool;
+
+    /// `git merge --no-ff <branch>` in the main worktree.
+    fn merge_no_ff(&self, repo: &RepoRoot, branch: &str) -> Result<()>;
+
+    /// Abort an in-progress merge in the worktree at `path`.
+    fn merge_abort(&self, path: &Path);
+
+    /// Push `branch` to `origin`.
+    fn push(&self, repo: &RepoRoot, branch: &str) -> Result<()>;
+
+    /// Commit and diff stats for `branch` against `base`.
+    fn worktree_stats(&self, repo: &RepoRoot, base: &str, branch: &str) -> Result<WorktreeStats>;
+
+    /// Read a config value as seen from `path`.
+    fn config_get(&self, path: &Path, key: &str) -> Result<Option<String>>;
+
+    /// Whether `reference` exists on `remote` (requires network access).
+    fn remote_ref_exists(&self, repo: &RepoRoot, remote: &str, reference: &str) -> Result<bool>;
+}
+
+/// The backend for read-heavy commands such as `list` and `go`.
+///
+/// With the `gix` feature this is `GixBackend`, unless the repository
+/// cannot be opened natively; otherwise it is [`ProcessGit`].
+pub fn detect(repo: &RepoRoot) -> Box<dyn GitBackend> {
+    #[cfg(feature = "gix")]
+    if let Ok(native) = GixBackend::open(repo) {
+        return Box::new(native);
+    }
+    #[cfg(not(feature = "gix"))]
+    let _ = repo;
+    Box::new(ProcessGit)
+}
+
+/// The main repository root above `start`, found in-process when the `gix`
+/// feature is enabled.
+#[cfg(feature = "gix")]
+pub(crate) fn discover_root(start: &Path) -> Option<RepoRoot> {
+    native::discover_root(start)
+}
+
+#[cfg(not(feature = "gix"))]
+pub(crate) fn discover_root(_start: &Path) -> Option<RepoRoot> {
+    None
+}
+
+/// The default backend: runs the `git` executable.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessGit;
+
+impl GitBackend for ProcessGit {
+    fn list_worktrees(&self, repo: &RepoRoot) -> Result<Vec<Worktree>> {
+        git::list_worktrees(repo)
+    }
+
+    fn add_worktree(
+        &self,
+        repo: &RepoRoot,
+        dir: &Path,
+        branch: &BranchName,
+        base: Option<&str>,
+    ) -> Result<()> {
+        git::add_worktree(repo, dir, branch, base)
+    }
+
+    fn remove_worktree(&self, repo: &RepoRoot, dir: &Path, force: bool) -> Result<()> {
+        git::remove_worktree(repo, dir, force)
+    }
+
+    fn lock_worktree(&self, repo: &RepoRoot, dir: &Path, reason: Option<&str>) -> Result<()> {
+        git::lock_worktree(repo, dir, reason)
+    }
+
+    fn unlock_worktree(&self, repo: &RepoRoot, dir: &Path) -> Result<()> {
+        git::unlock_worktree(repo, dir)
+    }
+
+    fn is_locked(&self, path: &Path) -> bool {
+        git::is_locked(path)
+    }
+
+    fn is_dirty(&self, path: &Path) -> Result<bool> {
+        git::is_dirty(path)
+    }
+
+    fn branch_exists(&self, repo: &RepoRoot, branch: &BranchName) -> bool {
+        git::branch_exists(repo, branch)
+    }
+
+    fn delete_branch(&self, repo: &RepoRoot, branch: &BranchName, force: bool) -> Result<()> {
+        git::delete_branch(repo, branch, force)
+    }
+
+    fn rev_exists(&self, repo: &RepoRoot, rev: &str) -> bool {
+        git::rev_exists(repo, rev)
+    }
+
+    fn resolve_mainline(&self, repo: &RepoRoot) -> Result<String> {
+        git::resolve_mainline(repo)
+    }
+
+    fn is_ancestor(&self, repo: &RepoRoot, branch: &str, mainline: &str) -> bool {
+        git::is_ancestor(repo, branch, mainline)
+    }
+
+    fn cherry(&self, repo: &RepoRoot, mainline: &str, branch: &str) -> bool {
+        git::cherry(repo, mainline, branch)
+    }
+
+    fn merge_no_ff(&self, repo: &RepoRoot, branch: &str) -> Result<()> {
+        git::merge_no_ff(repo, branch)
+    }
+
+    fn merge_abort(&self, path: &Path) {
+        git::merge_abort(path);
+    }
+
+    fn push(&self, repo: &RepoRoot, branch: &str) -> Result<()> {
+        git::push(repo, branch)
+    }
+
+    fn worktree_stats(&self, repo: &RepoRoot, base: &str, branch: &str) -> Result<WorktreeStats> {
+        git::worktree_stats(repo, base, branch)
+    }
+
+    fn config_get(&self, path: &Path, key: &str) -> Result<Option<String>> {
+        git::config_get(path, key)
+    }
+
+    fn remote_ref_exists(&self, repo: &RepoRoot, remote: &str, reference: &str) -> Result<bool> {
+        git::remote_ref_exists(repo, remote, reference)
+    }
+}
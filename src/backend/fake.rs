@@ -0,0 +1,315 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use super::GitBackend;
+use crate::domain::{BranchName, RepoRoot, Worktree, WorktreeStats};
+use crate::error::{AppError, Result};
+
+/// In-memory [`GitBackend`]: a repository at `/repo` whose main worktree is on `main`.
+///
+/// Integration and failures are declared up front; every mutating call is
+/// appended to [`FakeGit::calls`] so tests can assert on the exact sequence.
+pub(crate) struct FakeGit {
+    state: RefCell<State>,
+}
+
+#[derive(Default)]
+struct State {
+    worktrees: Vec<Worktree>,
+    branches: BTreeSet<String>,
+    mainline: String,
+    /// Branches reachable from the mainline (merged or fast-forwarded).
+    merged: BTreeSet<String>,
+    /// Branches whose patches are all in the mainline (rebase merged).
+    rebased: BTreeSet<String>,
+    conflicting: BTreeSet<String>,
+    failing_remove: BTreeSet<PathBuf>,
+    failing_delete: BTreeSet<String>,
+    failing_push: bool,
+    dirty: BTreeSet<PathBuf>,
+    locked: BTreeSet<PathBuf>,
+    config: BTreeMap<String, String>,
+    calls: Vec<String>,
+}
+
+impl FakeGit {
+    pub(crate) fn new() -> Self {
+        let state = State {
+            worktrees: vec![Worktree {
+                path: PathBuf::from("/repo"),
+                branch: Some("main".to_string()),
+                commit: "0000000".to_string(),
+                is_main: true,
+            }],
+            branches: BTreeSet::from(["main".to_string()]),
+            mainline: "main".to_string(),
+            ..State::default()
+        };
+        Self {
+            state: RefCell::new(state),
+        }
+    }
+
+    pub(crate) fn repo() -> RepoRoot {
+        RepoRoot(PathBuf::from("/repo"))
+    }
+
+    /// Managed path of `branch`'s worktree.
+    pub(crate) fn path_of(branch: &str) -> PathBuf {
+        Self::repo()
+            .worktrees_dir()
+            .join(BranchName::new(branch).to_dir_name())
+    }
+
+    /// Add a linked worktree on a new `branch`.
+    pub(crate) fn with_worktree(self, branch: &str) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.branches.insert(branch.to_string());
+            state.worktrees.push(Worktree {
+                path: Self::path_of(branch),
+                branch: Some(branch.to_string()),
+                commit: "1111111".to_string(),
+                is_main: false,
+            });
+        }
+        self
+    }
+
+    /// Add a linked worktree with a detached `HEAD` at `path`.
+    pub(crate) fn with_detached(self, path: &str) -> Self {
+        self.state.borrow_mut().worktrees.push(Worktree {
+            path: PathBuf::from(path),
+            branch: None,
+            commit: "2222222".to_string(),
+            is_main: false,
+        });
+        self
+    }
+
+    /// Check out `branch` in the main worktree.
+    pub(crate) fn main_on(self, branch: &str) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.branches.insert(branch.to_string());
+            state.worktrees[0].branch = Some(branch.to_string());
+        }
+        self
+    }
+
+    pub(crate) fn merged(self, branch: &str) -> Self {
+        self.state.borrow_mut().merged.insert(branch.to_string());
+        self
+    }
+
+    pub(crate) fn rebased(self, branch: &str) -> Self {
+        self.state.borrow_mut().rebased.insert(branch.to_string());
+        self
+    }
+
+    pub(crate) fn conflicting(self, branch: &str) -> Self {
+        self.state
+            .borrow_mut()
+            .conflicting
+            .insert(branch.to_string());
+        self
+    }
+
+    pub(crate) fn failing_remove(self, branch: &str) -> Self {
+        self.state
+            .borrow_mut()
+            .failing_remove
+            .insert(Self::path_of(branch));
+        self
+    }
+
+    pub(crate) fn failing_delete(self, branch: &str) -> Self {
+        self.state
+            .borrow_mut()
+            .failing_delete
+            .insert(branch.to_string());
+        self
+    }
+
+    pub(crate) fn failing_push(self) -> Self {
+        self.state.borrow_mut().failing_push = true;
+        self
+    }
+
+    /// Give `branch`'s worktree uncommitted changes.
+    pub(crate) fn dirty(self, branch: &str) -> Self {
+        self.state.borrow_mut().dirty.insert(Self::path_of(branch));
+        self
+    }
+
+    pub(crate) fn locked(self, branch: &str) -> Self {
+        self.state.borrow_mut().locked.insert(Self::path_of(branch));
+        self
+    }
+
+    pub(crate) fn with_config(self, key: &str, value: &str) -> Self {
+        self.state
+            .borrow_mut()
+            .config
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Mutating calls made so far, e.g. `merge feat` or `branch -D feat`.
+    pub(crate) fn calls(&self) -> Vec<String> {
+        self.state.borrow().calls.clone()
+    }
+
+    /// Branches still present.
+    pub(crate) fn branches(&self) -> Vec<String> {
+        self.state.borrow().branches.iter().cloned().collect()
+    }
+
+    fn record(&self, call: String) {
+        self.state.borrow_mut().calls.push(call);
+    }
+}
+
+impl GitBackend for FakeGit {
+    fn list_worktrees(&self, _repo: &RepoRoot) -> Result<Vec<Worktree>> {
+        Ok(self.state.borrow().worktrees.clone())
+    }
+
+    fn add_worktree(
+        &self,
+        _repo: &RepoRoot,
+        dir: &Path,
+        branch: &BranchName,
+        _base: Option<&str>,
+    ) -> Result<()> {
+        self.record(format!("worktree add {}", branch));
+        let mut state = self.state.borrow_mut();
+        state.branches.insert(branch.to_string());
+        state.worktrees.push(Worktree {
+            path: dir.to_path_buf(),
+            branch: Some(branch.to_string()),
+            commit: "3333333".to_string(),
+            is_main: false,
+        });
+        Ok(())
+    }
+
+    fn remove_worktree(&self, _repo: &RepoRoot, dir: &Path, force: bool) -> Result<()> {
+        let flag = if force { " --force" } else { "" };
+        self.record(format!("worktree remove{flag} {}", dir.display()));
+        let mut state = self.state.borrow_mut();
+        if state.failing_remove.contains(dir) {
+            return Err(AppError::conflict(format!(
+                "'{}' contains modified or untracked files",
+                dir.display()
+            )));
+        }
+        state.worktrees.retain(|wt| wt.path != dir);
+        Ok(())
+    }
+
+    fn lock_worktree(&self, _repo: &RepoRoot, dir: &Path, _reason: Option<&str>) -> Result<()> {
+        self.record(format!("worktree lock {}", dir.display()));
+        if !self.state.borrow_mut().locked.insert(dir.to_path_buf()) {
+            return Err(AppError::git(format!(
+                "'{}' is already locked",
+                dir.display()
+            )));
+        }
+        Ok(())
+    }
+
+    fn unlock_worktree(&self, _repo: &RepoRoot, dir: &Path) -> Result<()> {
+        self.record(format!("worktree unlock {}", dir.display()));
+        if !self.state.borrow_mut().locked.remove(dir) {
+            return Err(AppError::git(format!("'{}' is not locked", dir.display())));
+        }
+        Ok(())
+    }
+
+    fn is_locked(&self, path: &Path) -> bool {
+        self.state.borrow().locked.contains(path)
+    }
+
+    fn is_dirty(&self, path: &Path) -> Result<bool> {
+        Ok(self.state.borrow().dirty.contains(path))
+    }
+
+    fn branch_exists(&self, _repo: &RepoRoot, branch: &BranchName) -> bool {
+        self.state.borrow().branches.contains(branch.as_str())
+    }
+
+    fn delete_branch(&self, _repo: &RepoRoot, branch: &BranchName, force: bool) -> Result<()> {
+        let flag = if force { "-D" } else { "-d" };
+        self.record(format!("branch {flag} {branch}"));
+        let mut state = self.state.borrow_mut();
+        if state.failing_delete.contains(branch.as_str()) {
+            return Err(AppError::git(format!("cannot delete branch '{branch}'")));
+        }
+        if !force && !state.merged.contains(branch.as_str()) {
+            return Err(AppError::conflict(format!(
+                "the branch '{branch}' is not fully merged"
+            )));
+        }
+        state.branches.remove(branch.as_str());
+        Ok(())
+    }
+
+    fn rev_exists(&self, _repo: &RepoRoot, rev: &str) -> bool {
+        self.state.borrow().branches.contains(rev)
+    }
+
+    fn resolve_mainline(&self, _repo: &RepoRoot) -> Result<String> {
+        Ok(self.state.borrow().mainline.clone())
+    }
+
+    fn is_ancestor(&self, _repo: &RepoRoot, branch: &str, _mainline: &str) -> bool {
+        self.state.borrow().merged.contains(branch)
+    }
+
+    fn cherry(&self, _repo: &RepoRoot, _mainline: &str, branch: &str) -> bool {
+        self.state.borrow().rebased.contains(branch)
+    }
+
+    fn merge_no_ff(&self, _repo: &RepoRoot, branch: &str) -> Result<()> {
+        self.record(format!("merge {branch}"));
+        let mut state = self.state.borrow_mut();
+        if state.conflicting.contains(branch) {
+            return Err(AppError::conflict("Automatic merge failed".to_string()));
+        }
+        state.merged.insert(branch.to_string());
+        Ok(())
+    }
+
+    fn merge_abort(&self, _path: &Path) {
+        self.record("merge --abort".to_string());
+    }
+
+    fn push(&self, _repo: &RepoRoot, branch: &str) -> Result<()> {
+        self.record(format!("push {branch}"));
+        if self.state.borrow().failing_push {
+            return Err(AppError::git("remote rejected".to_string()));
+        }
+        Ok(())
+    }
+
+    fn worktree_stats(&self, _repo: &RepoRoot, base: &str, _branch: &str) -> Result<WorktreeStats> {
+        Ok(WorktreeStats {
+            base: base.to_string(),
+            commits_ahead: 0,
+            commits_behind: 0,
+            files_changed: 0,
+            insertions: 0,
+            deletions: 0,
+        })
+    }
+
+    fn config_get(&self, _path: &Path, key: &str) -> Result<Option<String>> {
+        Ok(self.state.borrow().config.get(key).cloned())
+    }
+
+    fn remote_ref_exists(&self, _repo: &RepoRoot, _remote: &str, _reference: &str) -> Result<bool> {
+        Ok(true)
+    }
+}
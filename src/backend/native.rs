@@ -0,0 +1,414 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use gix::bstr::{BStr, ByteSlice};
+use gix::refs::TargetRef;
+use gix::ObjectId;
+
+use super::{GitBackend, ProcessGit};
+use crate::domain::{BranchName, RepoRoot, Worktree, WorktreeStats};
+use crate::error::{AppError, Result};
+use crate::git;
+
+/// Reads the repository in-process with gitoxide; writes go to [`ProcessGit`].
+///
+/// Worktrees are enumerated from `$GIT_COMMON_DIR/worktrees/*` without
+/// running `git worktree prune`: registrations whose directory is gone are
+/// skipped (unless locked) instead of being deleted.
+pub struct GixBackend {
+    repo: gix::Repository,
+}
+
+impl GixBackend {
+    /// Open the repository rooted at `repo`.
+    pub fn open(repo: &RepoRoot) -> Result<Self> {
+        gix::open(repo.as_ref())
+            .map(|repo| Self { repo })
+            .map_err(|e| AppError::git(format!("cannot open {repo}: {e}")))
+    }
+
+    fn resolve(&self, rev: &str) -> Option<ObjectId> {
+        self.repo.rev_parse_single(rev).ok().map(|id| id.detach())
+    }
+
+    /// Commit a `HEAD` file points at, and its branch when symbolic.
+    fn read_head(&self, head_file: &Path) -> (Option<String>, String) {
+        let content = fs::read_to_string(head_file).unwrap_or_default();
+        let content = content.trim();
+        let Some(name) = content.strip_prefix("ref: ") else {
+            return (None, abbrev(content));
+        };
+        let commit = self
+            .repo
+            .try_find_reference(name)
+            .ok()
+            .flatten()
+            .and_then(|mut r| r.peel_to_id_in_place().ok())
+            .map_or_else(|| abbrev(""), |id| abbrev(&id.to_string()));
+        let branch = name.strip_prefix("refs/heads/").unwrap_or(name);
+        (Some(branch.to_string()), commit)
+    }
+
+    fn linked_worktree(&self, proxy: gix::worktree::Proxy<'_>) -> Option<Worktree> {
+        let base = proxy.base().ok()?;
+        let path = if base.is_relative() {
+            proxy.git_dir().join(base)
+        } else {
+            base
+        };
+        if !path.exists() && !proxy.is_locked() {
+            return None;
+        }
+        let (branch, commit) = self.read_head(&proxy.git_dir().join("HEAD"));
+        Some(Worktree {
+            path,
+            branch,
+            commit,
+            is_main: false,
+        })
+    }
+
+    /// Commit time and parents of `id`.
+    fn commit_info(&self, id: ObjectId) -> Result<(i64, Vec<ObjectId>)> {
+        let commit = self
+            .repo
+            .find_commit(id)
+            .map_err(|e| AppError::git(e.to_string()))?;
+        let time = commit.time().map_err(|e| AppError::git(e.to_string()))?;
+        let parents = commit.parent_ids().map(|p| p.detach()).collect();
+        Ok((time.seconds, parents))
+    }
+
+    /// Number of commits reachable from `tip` but not from `hidden`, like
+    /// `git rev-list --count hidden..tip`.
+    ///
+    /// Both histories are walked newest first, marks from `hidden` flowing
+    /// down to its ancestors, and the walk stops a few commits after only
+    /// older, hidden ones are left queued. Its cost is therefore bounded by the
+    /// history since the merge base rather than the whole history; like Git,
+    /// it relies on commit times being roughly ordered.
+    fn count_exclusive(&self, tip: ObjectId, hidden: ObjectId) -> Result<usize> {
+        // Hidden commits walked after nothing visible is queued, as slack
+        // for clock skew (Git's `SLOP`).
+        const SLACK: usize = 5;
+
+        let mut is_hidden = HashSet::from([hidden]);
+        let mut walked: HashMap<ObjectId, Vec<ObjectId>> = HashMap::new();
+        let mut queue = CommitQueue::default();
+        queue.push(self, tip)?;
+        queue.push(self, hidden)?;
+
+        let mut slack = SLACK;
+        while let Some((time, id, parents)) = queue.pop() {
+            let hide = is_hidden.contains(&id);
+            for &parent in &parents {
+                if hide {
+                    mark_hidden(parent, &walked, &mut is_hidden);
+                }
+                if !walked.contains_key(&parent) {
+                    queue.push(self, parent)?;
+                }
+            }
+            walked.insert(id, parents);
+
+            if hide && should_stop(&mut slack, SLACK, time, &queue, &is_hidden) {
+                break;
+            }
+        }
+
+        Ok(walked.keys().filter(|id| !is_hidden.contains(*id)).count())
+    }
+}
+
+/// Commits waiting to be walked, newest first; ties go to the commit queued
+/// first, as in Git.
+#[derive(Default)]
+struct CommitQueue {
+    heap: BinaryHeap<(i64, Reverse<usize>, ObjectId)>,
+    parents: HashMap<ObjectId, Vec<ObjectId>>,
+    pushed: usize,
+}
+
+impl CommitQueue {
+    /// Queue `id` unless it already is.
+    fn push(&mut self, backend: &GixBackend, id: ObjectId) -> Result<()> {
+        if let Entry::Vacant(slot) = self.parents.entry(id) {
+            let (time, parents) = backend.commit_info(id)?;
+            slot.insert(parents);
+            self.heap.push((time, Reverse(self.pushed), id));
+            self.pushed += 1;
+        }
+        Ok(())
+    }
+
+    /// The newest queued commit with its time and parents.
+    fn pop(&mut self) -> Option<(i64, ObjectId, Vec<ObjectId>)> {
+        let (time, _, id) = self.heap.pop()?;
+        let parents = self.parents.remove(&id).unwrap_or_default();
+        Some((time, id, parents))
+    }
+
+    fn newest_time(&self) -> Option<i64> {
+        self.heap.peek().map(|(time, _, _)| *time)
+    }
+
+    fn ids(&self) -> impl Iterator<Item = &ObjectId> {
+        self.heap.iter().map(|(_, _, id)| id)
+    }
+}
+
+/// Count down `slack` after walking a hidden commit from `time`, and say
+/// when it runs out.
+///
+/// Like Git, the countdown only runs once everything queued is hidden and
+/// older than that commit; otherwise it starts over from `reset`.
+fn should_stop(
+    slack: &mut usize,
+    reset: usize,
+    time: i64,
+    queue: &CommitQueue,
+    is_hidden: &HashSet<ObjectId>,
+) -> bool {
+    let undecided = queue.newest_time().is_some_and(|newest| newest >= time)
+        || queue.ids().any(|id| !is_hidden.contains(id));
+    *slack = if undecided { reset } else { *slack - 1 };
+    *slack == 0
+}
+
+/// Mark `id` and its already walked ancestors as hidden.
+fn mark_hidden(
+    id: ObjectId,
+    parents: &HashMap<ObjectId, Vec<ObjectId>>,
+    is_hidden: &mut HashSet<ObjectId>,
+) {
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        if is_hidden.insert(id) {
+            stack.extend(parents.get(&id).into_iter().flatten());
+        }
+    }
+}
+
+/// The main repository root above `start`, found the way `git rev-parse
+/// --git-common-dir` finds it; `None` for bare repositories or when gitoxide
+/// cannot open the repository.
+pub(crate) fn discover_root(start: &Path) -> Option<RepoRoot> {
+    let repo = gix::discover(start).ok()?;
+    repo.workdir()?;
+    let common = repo.common_dir().canonicalize().ok()?;
+    Some(RepoRoot(common.parent()?.to_path_buf()))
+}
+
+/// Git dir of the linked worktree at `path`, from its `.git` file.
+fn linked_git_dir(path: &Path) -> Option<PathBuf> {
+    let gitfile = fs::read_to_string(path.join(".git")).ok()?;
+    let git_dir = gitfile.trim().strip_prefix("gitdir: ")?;
+    Some(path.join(git_dir))
+}
+
+/// Abbreviate a hex object id the way `wt list` shows it.
+fn abbrev(hex: &str) -> String {
+    let hex = if hex.is_empty() { "0000000" } else { hex };
+    hex[..7.min(hex.len())].to_string()
+}
+
+impl GitBackend for GixBackend {
+    fn list_worktrees(&self, repo: &RepoRoot) -> Result<Vec<Worktree>> {
+        let mut worktrees = Vec::new();
+        if !self.repo.is_bare() {
+            let (branch, commit) = self.read_head(&self.repo.common_dir().join("HEAD"));
+            worktrees.push(Worktree {
+                path: PathBuf::from(repo.as_ref()),
+                branch,
+                commit,
+                is_main: false,
+            });
+        }
+        let linked = self
+            .repo
+            .worktrees()
+            .map_err(|e| AppError::git(format!("cannot read worktrees: {e}")))?;
+        worktrees.extend(linked.into_iter().filter_map(|p| self.linked_worktree(p)));
+        if let Some(first) = worktrees.first_mut() {
+            first.is_main = true;
+        }
+        Ok(worktrees)
+    }
+
+    fn add_worktree(
+        &self,
+        repo: &RepoRoot,
+        dir: &Path,
+        branch: &BranchName,
+        base: Option<&str>,
+    ) -> Result<()> {
+        ProcessGit.add_worktree(repo, dir, branch, base)
+    }
+
+    fn remove_worktree(&self, repo: &RepoRoot, dir: &Path, force: bool) -> Result<()> {
+        ProcessGit.remove_worktree(repo, dir, force)
+    }
+
+    fn lock_worktree(&self, repo: &RepoRoot, dir: &Path, reason: Option<&str>) -> Result<()> {
+        ProcessGit.lock_worktree(repo, dir, reason)
+    }
+
+    fn unlock_worktree(&self, repo: &RepoRoot, dir: &Path) -> Result<()> {
+        ProcessGit.unlock_worktree(repo, dir)
+    }
+
+    fn is_locked(&self, path: &Path) -> bool {
+        ProcessGit.is_locked(path)
+    }
+
+    fn is_dirty(&self, path: &Path) -> Result<bool> {
+        ProcessGit.is_dirty(path)
+    }
+
+    fn branch_exists(&self, _repo: &RepoRoot, branch: &BranchName) -> bool {
+        self.resolve(&format!("refs/heads/{branch}")).is_some()
+    }
+
+    fn delete_branch(&self, repo: &RepoRoot, branch: &BranchName, force: bool) -> Result<()> {
+        ProcessGit.delete_branch(repo, branch, force)
+    }
+
+    fn rev_exists(&self, _repo: &RepoRoot, rev: &str) -> bool {
+        self.resolve(rev).is_some()
+    }
+
+    fn resolve_mainline(&self, repo: &RepoRoot) -> Result<String> {
+        let origin_head = self
+            .repo
+            .try_find_reference("refs/remotes/origin/HEAD")
+            .ok()
+            .flatten();
+        let symref = origin_head.as_ref().and_then(|r| match r.target() {
+            TargetRef::Symbolic(name) => Some(name.shorten().to_str_lossy().into_owned()),
+            TargetRef::Object(_) => None,
+        });
+        // Prefer the local branch `origin/HEAD` tracks.
+        let from_origin = symref.map(|symref| {
+            let local = symref.strip_prefix("origin/").unwrap_or(&symref);
+            if self.branch_exists(repo, &BranchName::new(local)) {
+                local.to_string()
+            } else {
+                symref
+            }
+        });
+        if let Some(mainline) = from_origin {
+            return Ok(mainline);
+        }
+
+        for name in ["main", "master"] {
+            if self.branch_exists(repo, &BranchName::new(name)) {
+                return Ok(name.to_string());
+            }
+        }
+
+        self.list_worktrees(repo)?
+            .into_iter()
+            .find(|wt| wt.is_main)
+            .and_then(|wt| wt.branch)
+            .ok_or_else(|| {
+                AppError::git(
+                    "could not determine mainline branch; use --mainline to specify".to_string(),
+                )
+            })
+    }
+
+    fn is_ancestor(&self, _repo: &RepoRoot, branch: &str, mainline: &str) -> bool {
+        let (Some(branch), Some(mainline)) = (self.resolve(branch), self.resolve(mainline)) else {
+            return false;
+        };
+        branch == mainline
+            || self
+                .repo
+                .merge_base(branch, mainline)
+                .is_ok_and(|base| base.detach() == branch)
+    }
+
+    fn cherry(&self, repo: &RepoRoot, mainline: &str, branch: &str) -> bool {
+        ProcessGit.cherry(repo, mainline, branch)
+    }
+
+    fn merge_no_ff(&self, repo: &RepoRoot, branch: &str) -> Result<()> {
+        ProcessGit.merge_no_ff(repo, branch)
+    }
+
+    fn merge_abort(&self, path: &Path) {
+        ProcessGit.merge_abort(path);
+    }
+
+    fn push(&self, repo: &RepoRoot, branch: &str) -> Result<()> {
+        ProcessGit.push(repo, branch)
+    }
+
+    /// Commit counts are computed natively with a walk bounded by the merge
+    /// base. The diff stat still spawns `git diff --numstat`: gitoxide has no
+    /// line-level tree diff in the features enabled here.
+    fn worktree_stats(&self, repo: &RepoRoot, base: &str, branch: &str) -> Result<WorktreeStats> {
+        let unknown = |rev: &str| AppError::git(format!("unknown revision '{rev}'"));
+        let branch_ref = format!("refs/heads/{branch}");
+        let base_id = self.resolve(base).ok_or_else(|| unknown(base))?;
+        let branch_id = self
+            .resolve(&branch_ref)
+            .ok_or_else(|| unknown(&branch_ref))?;
+        let count = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        let (files_changed, insertions, deletions) =
+            git::diff_numstat(repo, &format!("{base}...{branch_ref}"))?;
+
+        Ok(WorktreeStats {
+            base: base.to_string(),
+            commits_ahead: count(self.count_exclusive(branch_id, base_id)?),
+            commits_behind: count(self.count_exclusive(base_id, branch_id)?),
+            files_changed,
+            insertions,
+            deletions,
+        })
+    }
+
+    /// Read natively. For linked worktrees this is the shared config plus
+    /// their `config.worktree`, without opening each worktree; when `path` is
+    /// not a worktree of this repository, `git config` is asked instead.
+    fn config_get(&self, path: &Path, key: &str) -> Result<Option<String>> {
+        let config = self.repo.config_snapshot();
+        let to_string = |v: Cow<'_, BStr>| v.to_str_lossy().into_owned();
+        if self.repo.workdir() == Some(path) {
+            return Ok(config.string(key).map(to_string));
+        }
+        let Some(git_dir) = linked_git_dir(path) else {
+            return ProcessGit.config_get(path, key);
+        };
+        let own = config
+            .boolean("extensions.worktreeConfig")
+            .unwrap_or(false)
+            .then(|| {
+                gix::config::File::from_path_no_includes(
+                    git_dir.join("config.worktree"),
+                    gix::config::Source::Worktree,
+                )
+            })
+            .and_then(std::result::Result::ok);
+        let own_value = own
+            .as_ref()
+            .and_then(|file| file.string(key))
+            .map(to_string);
+        // The main worktree's own `config.worktree` does not apply here.
+        let shared = || {
+            config
+                .plumbing()
+                .string_filter(key, |meta| meta.source != gix::config::Source::Worktree)
+                .map(to_string)
+        };
+        Ok(own_value.or_else(shared))
+    }
+
+    fn remote_ref_exists(&self, repo: &RepoRoot, remote: &str, reference: &str) -> Result<bool> {
+        ProcessGit.remote_ref_exists(repo, remote, reference)
+    }
+}
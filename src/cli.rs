@@ -41,12 +41,93 @@ pub enum Command {
     /// Create a new worktree and branch
     Add {
         /// Branch name to create
-        branch: String,
+        #[arg(required_unless_present_any = ["pr", "detach"])]
+        branch: Option<String>,
+
+        /// Create a review worktree for this pull request instead (branch `pr/<N>`)
+        #[arg(long, value_name = "N", conflicts_with_all = ["branch", "base", "carry"])]
+        pr: Option<u64>,
+
+        /// Check out this revision with a detached HEAD instead of creating a branch
+        #[arg(long, value_name = "REV", conflicts_with_all = ["branch", "pr", "base", "carry"])]
+        detach: Option<String>,
+
+        /// Only check out these directories (cone-mode sparse checkout)
+        #[arg(long, num_args = 1.., value_name = "PATTERN", conflicts_with_all = ["pr", "detach"])]
+        sparse: Vec<String>,
 
         /// Base revision to branch from (defaults to HEAD)
         #[arg(long)]
         base: Option<String>,
 
+        /// Move uncommitted changes from the current worktree into the new one
+        #[arg(long)]
+        carry: bool,
+
+        /// Also carry untracked files
+        #[arg(long, requires = "carry")]
+        include_untracked: bool,
+
+        /// Do not initialize submodules in the new worktree
+        #[arg(long)]
+        no_submodules: bool,
+
+        /// Open the new worktree in an editor (see `wt open`)
+        #[arg(long)]
+        open: bool,
+
+        /// Command used by --open (defaults to `git config wt.openCommand`, $VISUAL, $EDITOR)
+        #[arg(long, value_name = "CMD", requires = "open")]
+        editor: Option<String>,
+
+        /// Create or switch to the worktree's tmux session (default: `git config wt.tmux`)
+        #[arg(long, overrides_with = "no_tmux")]
+        tmux: bool,
+
+        /// Do not touch tmux sessions
+        #[arg(long, overrides_with = "tmux")]
+        no_tmux: bool,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Print only the worktree path (for shell wrappers)
+        #[arg(long, conflicts_with = "json")]
+        print_cd_path: bool,
+    },
+
+    /// Create a review worktree for a pull request (same as `add --pr`)
+    Review {
+        /// Pull request number
+        number: u64,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Print only the worktree path (for shell wrappers)
+        #[arg(long, conflicts_with = "json")]
+        print_cd_path: bool,
+    },
+
+    /// Create a throwaway detached worktree
+    Scratch {
+        /// Revision to check out (defaults to HEAD)
+        rev: Option<String>,
+
+        /// Do not initialize submodules in the new worktree
+        #[arg(long)]
+        no_submodules: bool,
+
         /// Repository path (defaults to current directory)
         #[arg(long)]
         repo: Option<PathBuf>,
@@ -62,13 +143,32 @@ pub enum Command {
 
     /// Switch to an existing worktree
     Go {
-        /// Branch name of the worktree to switch to
+        /// Worktree to switch to: branch, directory name, path, or a partial
+        /// match of one; `-` for the previously visited worktree, `@main` or
+        /// `^` for the main worktree
         branch: Option<String>,
 
         /// Force the interactive picker (skip auto-select)
         #[arg(short, long, conflicts_with_all = ["branch", "json"])]
         interactive: bool,
 
+        /// Land in the current subdirectory of the target worktree when it exists there
+        /// (default: `git config wt.keepSubdir`)
+        #[arg(long, overrides_with = "no_keep_subdir")]
+        keep_subdir: bool,
+
+        /// Always land at the target worktree's root
+        #[arg(long, overrides_with = "keep_subdir")]
+        no_keep_subdir: bool,
+
+        /// Create or switch to the worktree's tmux session (default: `git config wt.tmux`)
+        #[arg(long, overrides_with = "no_tmux")]
+        tmux: bool,
+
+        /// Do not touch tmux sessions
+        #[arg(long, overrides_with = "tmux")]
+        no_tmux: bool,
+
         /// Repository path (defaults to current directory)
         #[arg(long)]
         repo: Option<PathBuf>,
@@ -82,6 +182,52 @@ pub enum Command {
         print_cd_path: bool,
     },
 
+    /// Open a worktree in an editor or another tool
+    Open {
+        /// Worktree to open, matched like `wt go` (defaults to the current worktree)
+        branch: Option<String>,
+
+        /// Command template, `{path}` being the worktree path (defaults to
+        /// `git config wt.openCommand`, then $VISUAL, then $EDITOR)
+        #[arg(long, value_name = "CMD")]
+        editor: Option<String>,
+
+        /// Print the resolved command without running it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print the resolved command without running it
+        #[arg(long)]
+        print_command: bool,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+
+    /// List recently visited worktrees, most recent first
+    Recent {
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Interactive dashboard: browse worktrees and go, diff, merge, remove,
+    /// lock or prune them
+    Ui {
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Print only the path of the worktree chosen with `go` (for shell wrappers)
+        #[arg(long)]
+        print_cd_path: bool,
+    },
+
     /// Remove a worktree and its local branch
     Remove {
         /// Branch name (defaults to current worktree's branch)
@@ -182,6 +328,10 @@ pub enum Command {
         #[arg(long, requires = "execute")]
         force: bool,
 
+        /// Also remove all scratch worktrees
+        #[arg(long)]
+        scratch: bool,
+
         /// Override mainline branch (default: auto-detect)
         #[arg(long)]
         mainline: Option<String>,
@@ -195,6 +345,109 @@ pub enum Command {
         json: bool,
     },
 
+    /// Rebase or merge worktree branches onto the latest mainline
+    Sync {
+        /// Branches to sync (defaults to all non-main worktrees)
+        branches: Vec<String>,
+
+        /// Rebase each branch onto mainline (default)
+        #[arg(long, conflicts_with = "merge")]
+        rebase: bool,
+
+        /// Merge mainline into each branch instead of rebasing
+        #[arg(long)]
+        merge: bool,
+
+        /// Override mainline branch (default: auto-detect)
+        #[arg(long)]
+        mainline: Option<String>,
+
+        /// Skip fetching from origin before syncing
+        #[arg(long)]
+        no_fetch: bool,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Re-apply .wt/symlinks to existing worktrees
+    Link {
+        /// Branch whose worktree to refresh (defaults to the current worktree)
+        branch: Option<String>,
+
+        /// Refresh every non-main worktree
+        #[arg(long, conflicts_with = "branch")]
+        all: bool,
+
+        /// Show what would change without touching the filesystem
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also remove shared links that are no longer configured
+        #[arg(long)]
+        prune: bool,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Move worktrees created outside wt into .worktrees/
+    Adopt {
+        /// Path of the worktree to adopt (defaults to the current worktree)
+        path: Option<PathBuf>,
+
+        /// Adopt every worktree that is not at its managed path
+        #[arg(long, conflicts_with = "path")]
+        all: bool,
+
+        /// Show what would be moved without touching anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Move uncommitted changes from the current worktree into another
+    MoveChanges {
+        /// Branch of the worktree to move changes into
+        #[arg(long, value_name = "BRANCH")]
+        to: String,
+
+        /// Also move untracked files
+        #[arg(long)]
+        include_untracked: bool,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Inspect or adjust a worktree's sparse checkout
+    Sparse {
+        #[command(subcommand)]
+        action: SparseCommand,
+    },
+
     /// Generate .wt/symlinks config from detected project ecosystems
     Setup {
         /// Repository path (defaults to current directory)
@@ -206,14 +459,97 @@ pub enum Command {
         json: bool,
     },
 
+    /// Print a status segment for the worktree containing the current directory
+    Prompt {
+        /// Template with {branch}, {commit}, {ahead}, {behind} and {dirty} placeholders
+        #[arg(long, default_value = wt_core::worktree::PROMPT_FORMAT)]
+        format: String,
+
+        /// Recompute stats instead of reusing the short-lived cache
+        #[arg(long)]
+        no_cache: bool,
+    },
+
     /// Print shell bindings to stdout
     Init {
         /// Shell to generate bindings for
         shell: Shell,
     },
 
+    /// Print completion candidates for shell bindings
+    #[command(hide = true)]
+    Complete {
+        /// What to list
+        context: CompleteContext,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+
     /// Diagnose worktree and repository health
     Doctor {
+        /// Repair the findings that can be fixed safely
+        #[arg(long)]
+        fix: bool,
+
+        /// With --fix, show what would be repaired without changing anything
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SparseCommand {
+    /// Add directories to a sparse worktree
+    Add {
+        /// Branch of the worktree to change
+        branch: String,
+
+        /// Directories to add (cone mode)
+        #[arg(required = true)]
+        patterns: Vec<String>,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Replace the checked-out directories, enabling sparse checkout if needed
+    Set {
+        /// Branch of the worktree to change
+        branch: String,
+
+        /// Directories to check out (cone mode)
+        #[arg(required = true)]
+        patterns: Vec<String>,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List the checked-out directories of a worktree
+    List {
+        /// Branch of the worktree to inspect
+        branch: String,
+
         /// Repository path (defaults to current directory)
         #[arg(long)]
         repo: Option<PathBuf>,
@@ -225,11 +561,26 @@ pub enum Command {
 }
 
 #[derive(ValueEnum, Clone, Copy, Debug)]
+#[allow(clippy::enum_variant_names)] // `PowerShell` is the shell's name.
 pub enum Shell {
     Bash,
     Zsh,
     Fish,
     Nu,
+    #[value(name = "powershell", alias = "pwsh")]
+    PowerShell,
+    Elvish,
+    Xonsh,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CompleteContext {
+    /// Branches checked out in a worktree
+    Worktrees,
+    /// Local and origin branches without a worktree
+    Branches,
+    /// Branches, remote-tracking branches and tags
+    Revisions,
 }
 
 #[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
@@ -0,0 +1,217 @@
+use std::path::{Path, PathBuf};
+
+use crate::backend::{self, GitBackend, ProcessGit};
+use crate::domain::{BranchName, RepoRoot, Worktree};
+use crate::error::Result;
+use crate::git;
+use crate::metadata;
+use crate::tmux;
+use crate::worktree::{self, DiffResult, DirtyDiffMode, DirtyDiffResult};
+
+/// Like [`worktree::diff`], with the difftool's stdout sent to stderr so that it
+/// cannot mix with a path printed on stdout (`--print-cd-path`).
+pub fn diff_to_stderr(
+    repo: &RepoRoot,
+    branch: &BranchName,
+    against: Option<&str>,
+    tool: Option<&str>,
+) -> Result<DiffResult> {
+    worktree::diff_impl(repo, branch, against, tool, false, true)
+}
+
+/// Like [`worktree::diff_dirty`], with the difftool's stdout sent to stderr.
+pub fn diff_dirty_to_stderr(
+    worktree: &Worktree,
+    mode: DirtyDiffMode,
+    tool: Option<&str>,
+) -> Result<DirtyDiffResult> {
+    worktree::diff_dirty_impl(worktree, mode, tool, false, true)
+}
+
+/// Which candidates [`completion_candidates`] lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    /// Branches checked out in a worktree (`go`, `remove`, `merge`, `diff`).
+    Worktrees,
+    /// Local and `origin` branches that have no worktree yet (`add`).
+    Branches,
+    /// Local branches, remote-tracking branches and tags (`--base`,
+    /// `--against`).
+    Revisions,
+}
+
+/// Live candidates for shell completion, sorted and without duplicates.
+pub fn completion_candidates(repo: &RepoRoot, kind: CompletionKind) -> Result<Vec<String>> {
+    let worktree_branches = || -> Result<Vec<String>> {
+        let worktrees = backend::detect(repo).list_worktrees(repo)?;
+        Ok(worktrees.into_iter().filter_map(|wt| wt.branch).collect())
+    };
+    let mut candidates: Vec<String> = match kind {
+        CompletionKind::Worktrees => worktree_branches()?,
+        CompletionKind::Branches => {
+            let taken = worktree_branches()?;
+            git::ref_names(repo, &["refs/heads/", "refs/remotes/origin/"])?
+                .iter()
+                .filter_map(|name| {
+                    name.strip_prefix("refs/heads/")
+                        .or_else(|| name.strip_prefix("refs/remotes/origin/"))
+                })
+                .filter(|name| *name != "HEAD" && !taken.iter().any(|t| t == name))
+                .map(str::to_string)
+                .collect()
+        }
+        CompletionKind::Revisions => {
+            git::ref_names(repo, &["refs/heads/", "refs/remotes/", "refs/tags/"])?
+                .iter()
+                .filter(|name| !name.ends_with("/HEAD"))
+                .filter_map(|name| {
+                    name.strip_prefix("refs/heads/")
+                        .or_else(|| name.strip_prefix("refs/remotes/"))
+                        .or_else(|| name.strip_prefix("refs/tags/"))
+                })
+                .map(str::to_string)
+                .collect()
+        }
+    };
+    candidates.sort();
+    candidates.dedup();
+    Ok(candidates)
+}
+
+/// Where to `cd` when switching from `cwd`, inside the worktree at
+/// `current`, to the worktree at `target`: the same subdirectory of
+/// `target` when it exists there, else `target` itself.
+pub fn matching_subdir(current: &Path, cwd: &Path, target: &Path) -> PathBuf {
+    let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    canonical(cwd)
+        .strip_prefix(canonical(current))
+        .ok()
+        .map(|rel| target.join(rel))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| target.to_path_buf())
+}
+
+/// Whether `go` keeps the current subdirectory by default
+/// (`git config wt.keepSubdir true`).
+pub fn keep_subdir_configured(repo: &RepoRoot) -> bool {
+    keep_subdir_configured_with(&ProcessGit, repo)
+}
+
+/// [`keep_subdir_configured`] against an arbitrary [`GitBackend`].
+pub fn keep_subdir_configured_with(backend: &dyn GitBackend, repo: &RepoRoot) -> bool {
+    config_flag(backend, repo.as_ref(), "wt.keepSubdir")
+}
+
+fn config_flag(backend: &dyn GitBackend, path: &Path, key: &str) -> bool {
+    matches!(
+        backend.config_get(path, key),
+        Ok(Some(v)) if matches!(v.to_ascii_lowercase().as_str(), "true" | "yes" | "on" | "1")
+    )
+}
+
+/// Result of [`tmux_session`].
+pub struct TmuxResult {
+    /// Name of the worktree's tmux session.
+    pub session: String,
+    /// Whether the session was created rather than found.
+    pub created: bool,
+    /// Whether the terminal was switched to (or attached to) the session.
+    pub switched: bool,
+}
+
+/// Whether `add` and `go` manage a tmux session per worktree by default
+/// (`git config wt.tmux true`) in the repository containing `path`.
+pub fn tmux_configured(path: &Path) -> bool {
+    tmux_configured_with(&ProcessGit, path)
+}
+
+/// [`tmux_configured`] against an arbitrary [`GitBackend`].
+pub fn tmux_configured_with(backend: &dyn GitBackend, path: &Path) -> bool {
+    config_flag(backend, path, "wt.tmux")
+}
+
+/// Find or create the tmux session of the worktree at `worktree_path`, and
+/// switch to it when `switch` is set.
+///
+/// New sessions are named after the worktree's branch slug (its directory
+/// name without the hash suffix), falling back to the full directory name
+/// when that name is taken, and start in the worktree with the windows of
+/// its `.wt/tmux` layout (`git config wt.tmuxLayout` to use another file).
+pub fn tmux_session(worktree_path: &Path, switch: bool) -> Result<TmuxResult> {
+    let dir_name = worktree_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "wt".to_string());
+    let layout_file = git::config_get(worktree_path, "wt.tmuxLayout")?;
+    let layout = tmux::load_layout(worktree_path, layout_file.as_deref());
+
+    let (session, created) =
+        tmux::ensure_session(worktree_path, dir_slug(&dir_name), &dir_name, &layout)?;
+    let switched = switch && tmux::switch_to(&session)?;
+
+    Ok(TmuxResult {
+        session: session.name,
+        created,
+        switched,
+    })
+}
+
+/// `feature-auth--a1b2c3d4` → `feature-auth`; other names are kept as is.
+fn dir_slug(dir_name: &str) -> &str {
+    match dir_name.rsplit_once("--") {
+        Some((slug, hash))
+            if !slug.is_empty()
+                && hash.len() == 8
+                && hash.chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            slug
+        }
+        _ => dir_name,
+    }
+}
+
+/// Name of the tmux session of each worktree, parallel to `worktrees`.
+///
+/// All `None` when tmux is not installed or no server is running.
+pub fn tmux_sessions(worktrees: &[Worktree]) -> Vec<Option<String>> {
+    let sessions = tmux::sessions();
+    worktrees
+        .iter()
+        .map(|wt| tmux::session_for(&sessions, &wt.path).map(|s| s.name))
+        .collect()
+}
+
+/// Record navigation from the worktree at `from` (if any) to the one at
+/// `to` in the history shared by all worktrees of `repo`.
+///
+/// Best-effort: navigation never fails because history could not be saved.
+pub fn record_visit(repo: &RepoRoot, from: Option<&Path>, to: &Path) {
+    let paths: Vec<&Path> = from.into_iter().chain([to]).collect();
+    let _ = metadata::record_visits(repo.as_ref(), &paths);
+}
+
+/// Order `worktrees` by most recent visit; unvisited ones follow in their
+/// original order.
+pub fn sort_by_recent(repo: &RepoRoot, worktrees: &mut [Worktree]) {
+    let visits = metadata::read_visits(repo.as_ref());
+    worktrees.sort_by_key(|wt| {
+        visits
+            .iter()
+            .position(|v| v.path == wt.path)
+            .unwrap_or(usize::MAX)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dir_slug_strips_the_hash_suffix_only() {
+        assert_eq!(dir_slug("feature-auth--a1b2c3d4"), "feature-auth");
+        assert_eq!(dir_slug("a--b--0123abcd"), "a--b");
+        assert_eq!(dir_slug("my-repo"), "my-repo");
+        assert_eq!(dir_slug("release--v2"), "release--v2");
+        assert_eq!(dir_slug("--a1b2c3d4"), "--a1b2c3d4");
+    }
+}
@@ -1,18 +1,24 @@
 use std::io::IsTerminal;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::cli::{Cli, ColorChoice, Command, Shell};
-use crate::domain::{self, BranchName, WorktreeStatsStatus};
-use crate::error::{AppError, Result};
-use crate::git;
+use unicode_width::UnicodeWidthStr;
+use wt_core::backend::{self, GitBackend, ProcessGit};
+use wt_core::cli_support;
+use wt_core::domain::{self, BranchName, WorktreeStatsStatus};
+use wt_core::error::{AppError, Result};
+use wt_core::symlinks;
+use wt_core::worktree;
+
+use crate::cli::{Cli, ColorChoice, Command, CompleteContext, Shell, SparseCommand};
+use crate::completions;
 use crate::output::{
-    find_current_worktree, print_json, JsonDoctorResponse, JsonListResponse, JsonMergeResponse,
+    find_current_worktree, print_json, JsonAdoptResponse, JsonAdoptWorktree, JsonDoctorResponse,
+    JsonLinkOutcome, JsonLinkResponse, JsonLinkWorktree, JsonListResponse, JsonMergeResponse,
     JsonPruneDryRunEntry, JsonPruneDryRunResponse, JsonPruneExecuteResponse, JsonPrunedEntry,
-    JsonResponse, JsonSkippedEntry, MergeFormat, NavigationFormat, PruneFormat, RemoveFormat,
+    JsonRecentEntry, JsonRecentResponse, JsonResponse, JsonSkippedEntry, JsonSparseResponse,
+    JsonSyncResponse, JsonSyncedEntry, MergeFormat, NavigationFormat, PruneFormat, RemoveFormat,
     StatusFormat,
 };
-use crate::worktree;
-use unicode_width::UnicodeWidthStr;
 
 pub fn run(cli: Cli) -> Result<()> {
     match cli.command {
@@ -25,28 +31,102 @@ pub fn run(cli: Cli) -> Result<()> {
         } => cmd_list(repo, status_fmt(json), stats, against.as_deref(), color),
         Command::Add {
             branch,
+            pr,
+            detach,
+            sparse,
             base,
+            carry,
+            include_untracked,
+            no_submodules,
+            open,
+            editor,
+            tmux,
+            no_tmux,
+            repo,
+            json,
+            print_cd_path,
+        } => {
+            let fmt = nav_fmt(json, print_cd_path);
+            let path = match (pr, detach, branch) {
+                (Some(number), _, _) => cmd_review(number, !no_submodules, repo, fmt)?,
+                (None, Some(rev), _) => cmd_add_detached(&rev, !no_submodules, repo, fmt)?,
+                (None, None, Some(branch)) => cmd_add(
+                    &BranchName::new(&branch),
+                    &worktree::AddOptions {
+                        base: base.as_deref(),
+                        carry,
+                        carry_untracked: include_untracked,
+                        sparse: &sparse,
+                        no_submodules,
+                    },
+                    repo,
+                    fmt,
+                )?,
+                (None, None, None) => {
+                    return Err(AppError::usage(
+                        "a branch name, --pr or --detach is required",
+                    ))
+                }
+            };
+            tmux_session(&ProcessGit, &path, flag_pair(tmux, no_tmux), fmt);
+            if open {
+                open_after_add(&path, editor.as_deref());
+            }
+            Ok(())
+        }
+        Command::Review {
+            number,
             repo,
             json,
             print_cd_path,
-        } => cmd_add(
-            &BranchName::new(&branch),
-            base.as_deref(),
+        } => cmd_review(number, true, repo, nav_fmt(json, print_cd_path)).map(drop),
+        Command::Scratch {
+            rev,
+            no_submodules,
+            repo,
+            json,
+            print_cd_path,
+        } => cmd_scratch(
+            rev.as_deref(),
+            !no_submodules,
             repo,
             nav_fmt(json, print_cd_path),
         ),
         Command::Go {
             branch,
             interactive,
+            keep_subdir,
+            no_keep_subdir,
+            tmux,
+            no_tmux,
             repo,
             json,
             print_cd_path,
         } => cmd_go(
             branch.as_deref(),
             interactive,
+            flag_pair(keep_subdir, no_keep_subdir),
+            flag_pair(tmux, no_tmux),
             repo,
             nav_fmt(json, print_cd_path),
         ),
+        Command::Open {
+            branch,
+            editor,
+            dry_run,
+            print_command,
+            repo,
+        } => cmd_open(
+            branch.as_deref(),
+            editor.as_deref(),
+            dry_run || print_command,
+            repo,
+        ),
+        Command::Recent { repo, json } => cmd_recent(repo, status_fmt(json)),
+        Command::Ui {
+            repo,
+            print_cd_path,
+        } => cmd_ui(repo, print_cd_path),
         Command::Remove {
             branch,
             force,
@@ -96,13 +176,78 @@ pub fn run(cli: Cli) -> Result<()> {
         Command::Prune {
             execute,
             force,
+            scratch,
             mainline,
             repo,
             json,
-        } => cmd_prune(execute, force, mainline.as_deref(), repo, prune_fmt(json)),
+        } => cmd_prune(
+            execute,
+            force,
+            scratch,
+            mainline.as_deref(),
+            repo,
+            prune_fmt(json),
+        ),
+        Command::Sync {
+            branches,
+            rebase: _,
+            merge,
+            mainline,
+            no_fetch,
+            repo,
+            json,
+        } => cmd_sync(
+            &branches,
+            sync_strategy(merge),
+            mainline.as_deref(),
+            !no_fetch,
+            repo,
+            status_fmt(json),
+        ),
+        Command::Adopt {
+            path,
+            all,
+            dry_run,
+            repo,
+            json,
+        } => cmd_adopt(path.as_deref(), all, dry_run, repo, status_fmt(json)),
+        Command::Link {
+            branch,
+            all,
+            dry_run,
+            prune,
+            repo,
+            json,
+        } => cmd_link(
+            branch.as_deref(),
+            all,
+            dry_run,
+            prune,
+            repo,
+            status_fmt(json),
+        ),
+        Command::MoveChanges {
+            to,
+            include_untracked,
+            repo,
+            json,
+        } => cmd_move_changes(
+            &BranchName::new(&to),
+            include_untracked,
+            repo,
+            status_fmt(json),
+        ),
+        Command::Sparse { action } => cmd_sparse(action),
         Command::Setup { repo, json } => cmd_setup(repo, status_fmt(json)),
+        Command::Prompt { format, no_cache } => cmd_prompt(&format, !no_cache),
         Command::Init { shell } => cmd_init(shell),
-        Command::Doctor { repo, json } => cmd_doctor(repo, status_fmt(json)),
+        Command::Complete { context, repo } => cmd_complete(context, repo),
+        Command::Doctor {
+            fix,
+            dry_run,
+            repo,
+            json,
+        } => cmd_doctor(repo, fix, dry_run, status_fmt(json)),
     }
 }
 
@@ -116,6 +261,15 @@ fn nav_fmt(json: bool, cd_path: bool) -> NavigationFormat {
     }
 }
 
+/// `Some(true)` for `--flag`, `Some(false)` for `--no-flag`, `None` for neither.
+fn flag_pair(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
 fn status_fmt(json: bool) -> StatusFormat {
     if json {
         StatusFormat::Json
@@ -152,13 +306,21 @@ fn prune_fmt(json: bool) -> PruneFormat {
     }
 }
 
+fn sync_strategy(merge: bool) -> worktree::SyncStrategy {
+    if merge {
+        worktree::SyncStrategy::Merge
+    } else {
+        worktree::SyncStrategy::Rebase
+    }
+}
+
 fn resolve_repo(repo: Option<PathBuf>) -> Result<domain::RepoRoot> {
     let start = match repo {
         Some(p) => p,
         None => std::env::current_dir()
             .map_err(|e| AppError::not_a_repo(format!("cannot determine cwd: {e}")))?,
     };
-    git::repo_root(&start)
+    worktree::repo_root(&start)
 }
 
 // ── Commands ────────────────────────────────────────────────────────
@@ -171,27 +333,39 @@ fn cmd_list(
     color: ColorChoice,
 ) -> Result<()> {
     let repo = resolve_repo(repo)?;
-    let worktrees = git::list_worktrees(&repo)?;
+    let backend = backend::detect(&repo);
+    let worktrees = backend.list_worktrees(&repo)?;
     let cwd = std::env::current_dir()
         .ok()
         .and_then(|p| p.canonicalize().ok());
     let stats = if stats {
-        Some(list_stats(&repo, &worktrees, against)?)
+        Some(list_stats(backend.as_ref(), &repo, &worktrees, against)?)
     } else {
         None
     };
+    let sparse: Vec<bool> = worktrees
+        .iter()
+        .map(|wt| worktree::is_sparse_with(backend.as_ref(), &wt.path))
+        .collect();
+    // Only ask tmux when sessions are managed, so plain listings never spawn it.
+    let tmux = if cli_support::tmux_configured_with(backend.as_ref(), repo.as_ref()) {
+        cli_support::tmux_sessions(&worktrees)
+    } else {
+        vec![None; worktrees.len()]
+    };
 
     match fmt {
         StatusFormat::Json => match &stats {
-            Some(stats) => print_json(&JsonListResponse::from_worktrees_with_stats(
-                &worktrees,
-                cwd.as_deref(),
-                stats,
-            ))?,
-            None => print_json(&JsonListResponse::from_worktrees(
-                &worktrees,
-                cwd.as_deref(),
-            ))?,
+            Some(stats) => print_json(
+                &JsonListResponse::from_worktrees_with_stats(&worktrees, cwd.as_deref(), stats)
+                    .with_sparse(&sparse)
+                    .with_tmux_sessions(&tmux),
+            )?,
+            None => print_json(
+                &JsonListResponse::from_worktrees(&worktrees, cwd.as_deref())
+                    .with_sparse(&sparse)
+                    .with_tmux_sessions(&tmux),
+            )?,
         },
         StatusFormat::Human => {
             if worktrees.is_empty() {
@@ -200,9 +374,9 @@ fn cmd_list(
             }
             if let Some(stats) = &stats {
                 let color = ColorPolicy::from_env(color);
-                print_list_with_stats(&worktrees, stats, color);
+                print_list_with_stats(&worktrees, stats, &sparse, &tmux, color);
             } else {
-                print_list_default(&worktrees, cwd.as_deref());
+                print_list_default(&worktrees, &sparse, &tmux, cwd.as_deref());
             }
         }
     }
@@ -210,56 +384,57 @@ fn cmd_list(
 }
 
 fn list_stats(
+    backend: &dyn GitBackend,
     repo: &domain::RepoRoot,
     worktrees: &[domain::Worktree],
     against: Option<&str>,
 ) -> Result<Vec<WorktreeStatsStatus>> {
     let base = match against {
         Some(rev) => {
-            if !git::rev_exists(repo, rev) {
+            if !backend.rev_exists(repo, rev) {
                 return Err(AppError::usage(format!(
                     "base revision '{rev}' does not exist"
                 )));
             }
             rev.to_string()
         }
-        None => git::resolve_mainline(repo)?,
+        None => backend.resolve_mainline(repo)?,
     };
 
     Ok(worktrees
         .iter()
-        .map(|wt| match &wt.branch {
-            Some(branch) => git::worktree_stats(repo, &base, branch).map_or_else(
-                |_| WorktreeStatsStatus::Unavailable {
-                    base: base.clone(),
-                    reason: "git_error".to_string(),
-                },
-                WorktreeStatsStatus::Available,
-            ),
-            None => WorktreeStatsStatus::Unavailable {
-                base: base.clone(),
-                reason: "no_branch".to_string(),
-            },
-        })
+        .map(|wt| worktree::stats_status_with(backend, repo, &base, wt))
         .collect())
 }
 
-fn print_list_default(worktrees: &[domain::Worktree], cwd: Option<&std::path::Path>) {
+fn print_list_default(
+    worktrees: &[domain::Worktree],
+    sparse: &[bool],
+    tmux: &[Option<String>],
+    cwd: Option<&std::path::Path>,
+) {
     let current_idx = cwd.and_then(|cwd| find_current_worktree(worktrees, cwd));
-    for (i, wt) in worktrees.iter().enumerate() {
+    for (i, ((wt, &is_sparse), session)) in worktrees.iter().zip(sparse).zip(tmux).enumerate() {
         let branch_str = wt.branch.as_deref().unwrap_or("(detached)");
         let main_tag = if wt.is_main { " [main]" } else { "" };
+        let sparse_tag = if is_sparse { " [sparse]" } else { "" };
+        let tmux_tag = session
+            .as_ref()
+            .map(|name| format!(" [tmux: {name}]"))
+            .unwrap_or_default();
         let here_tag = if current_idx == Some(i) {
             " ← here"
         } else {
             ""
         };
         println!(
-            "{:<50} {:<20} {}{}{}",
+            "{:<50} {:<20} {}{}{}{}{}",
             wt.path.display(),
             branch_str,
             wt.commit,
             main_tag,
+            sparse_tag,
+            tmux_tag,
             here_tag
         );
     }
@@ -268,15 +443,24 @@ fn print_list_default(worktrees: &[domain::Worktree], cwd: Option<&std::path::Pa
 fn print_list_with_stats(
     worktrees: &[domain::Worktree],
     stats: &[WorktreeStatsStatus],
+    sparse: &[bool],
+    tmux: &[Option<String>],
     color: ColorPolicy,
 ) {
     let rows = worktrees
         .iter()
         .zip(stats)
-        .map(|(wt, stat)| StatsRow {
+        .zip(sparse)
+        .zip(tmux)
+        .map(|(((wt, stat), &is_sparse), session)| StatsRow {
             branch: plain_cell(wt.branch.as_deref().unwrap_or("(detached)")),
             columns: format_stats_columns(stat, color),
-            path: wt.path.display().to_string(),
+            path: format!(
+                "{}{}{}",
+                wt.path.display(),
+                if is_sparse { " [sparse]" } else { "" },
+                if session.is_some() { " [tmux]" } else { "" }
+            ),
         })
         .collect::<Vec<_>>();
     let widths = StatsColumnWidths::from_rows(&rows);
@@ -494,36 +678,203 @@ impl StatSign {
     }
 }
 
+/// Create a worktree and return its path.
 fn cmd_add(
     branch: &BranchName,
-    base: Option<&str>,
+    opts: &worktree::AddOptions,
+    repo: Option<PathBuf>,
+    fmt: NavigationFormat,
+) -> Result<PathBuf> {
+    let repo = resolve_repo(repo)?;
+    let result = worktree::add(&repo, branch, opts)?;
+    record_visit(&repo, &result.worktree_path);
+    print_add_result(&result, opts.carry, None, fmt)?;
+    Ok(result.worktree_path)
+}
+
+/// Create a review worktree and return its path.
+fn cmd_review(
+    number: u64,
+    submodules: bool,
+    repo: Option<PathBuf>,
+    fmt: NavigationFormat,
+) -> Result<PathBuf> {
+    let repo = resolve_repo(repo)?;
+    let result = worktree::add_pr(&repo, number, submodules)?;
+    record_visit(&repo, &result.worktree_path);
+    print_add_result(&result, false, Some(number), fmt)?;
+    Ok(result.worktree_path)
+}
+
+/// Create a detached worktree and return its path.
+fn cmd_add_detached(
+    rev: &str,
+    submodules: bool,
+    repo: Option<PathBuf>,
+    fmt: NavigationFormat,
+) -> Result<PathBuf> {
+    let repo = resolve_repo(repo)?;
+    let result = worktree::add_detached(&repo, rev, submodules)?;
+    print_detached_result(&result, &format!("detached worktree at '{rev}'"), fmt)?;
+    Ok(result.worktree_path)
+}
+
+/// Open a freshly created worktree. It exists either way, so failing to
+/// launch the editor is only a warning and `add` still succeeds.
+fn open_after_add(path: &Path, editor: Option<&str>) {
+    if let Err(err) = worktree::open(path, editor, false) {
+        eprintln!("warning: {err}");
+    }
+}
+
+fn cmd_open(
+    branch: Option<&str>,
+    editor: Option<&str>,
+    dry_run: bool,
+    repo: Option<PathBuf>,
+) -> Result<()> {
+    if matches!(editor, Some(cmd) if cmd.trim().is_empty()) {
+        return Err(AppError::usage("--editor must not be empty".to_string()));
+    }
+
+    let repo = resolve_repo(repo)?;
+    let backend = backend::detect(&repo);
+    let path = match branch {
+        Some(query) => {
+            let resolved =
+                resolve_go_query(backend.as_ref(), &repo, query, NavigationFormat::Human)?;
+            worktree::go_with(backend.as_ref(), &repo, &resolved)?.worktree_path
+        }
+        None => current_worktree_path(backend.as_ref(), &repo)
+            .unwrap_or_else(|| repo.as_ref().to_path_buf()),
+    };
+
+    let result = worktree::open(&path, editor, dry_run)?;
+    if dry_run {
+        println!("{}", result.display_command());
+    } else {
+        println!(
+            "Opened {} with {}",
+            result.worktree_path.display(),
+            result.command[0]
+        );
+    }
+    Ok(())
+}
+
+fn cmd_scratch(
+    rev: Option<&str>,
+    submodules: bool,
     repo: Option<PathBuf>,
     fmt: NavigationFormat,
 ) -> Result<()> {
     let repo = resolve_repo(repo)?;
-    let result = worktree::add(&repo, branch, base)?;
+    let result = worktree::scratch(&repo, rev, submodules)?;
+    let short = &result.commit[..result.commit.len().min(8)];
+    print_detached_result(&result, &format!("scratch worktree at {short}"), fmt)
+}
 
+fn linked_entries(report: Option<&symlinks::SymlinkReport>) -> &[symlinks::LinkedEntry] {
+    report.map(|r| r.created.as_slice()).unwrap_or_default()
+}
+
+fn linked_paths(report: Option<&symlinks::SymlinkReport>) -> Vec<String> {
+    linked_entries(report)
+        .iter()
+        .map(|l| l.path.display().to_string())
+        .collect()
+}
+
+fn print_linked(report: Option<&symlinks::SymlinkReport>) {
+    for linked in linked_entries(report) {
+        println!("  {} {}", linked.mode.verb(), linked.path.display());
+    }
+}
+
+/// Print successful setup steps; skipped and failed ones surface as warnings.
+fn print_setup_steps(steps: &[worktree::SetupStep]) {
+    for step in steps
+        .iter()
+        .filter(|s| s.status == worktree::SetupStepStatus::Ok)
+    {
+        println!("  {}", step.detail);
+    }
+}
+
+fn print_detached_result(
+    result: &worktree::DetachedAddResult,
+    what: &str,
+    fmt: NavigationFormat,
+) -> Result<()> {
+    let path_str = result.worktree_path.display().to_string();
+
+    match fmt {
+        NavigationFormat::CdPath => {
+            println!("{path_str}");
+        }
+        NavigationFormat::Json => {
+            let resp = JsonResponse::success(format!("created {what}"))
+                .with_event("switch")
+                .with_repo_root(result.repo_root.display().to_string())
+                .with_worktree_path(&path_str)
+                .with_cd_path(&path_str)
+                .with_commit(&result.commit)
+                .with_symlinks(linked_paths(result.symlinks.as_ref()))
+                .with_links(linked_entries(result.symlinks.as_ref()))
+                .with_steps(&result.steps)
+                .with_warnings(result.warnings.clone());
+            print_json(&resp)?;
+        }
+        NavigationFormat::Human => {
+            println!("Created {what} at {path_str}");
+            print_setup_steps(&result.steps);
+            print_linked(result.symlinks.as_ref());
+        }
+    }
+    if !matches!(fmt, NavigationFormat::Json) {
+        for w in &result.warnings {
+            eprintln!("warning: {w}");
+        }
+    }
+    if let Some(report) = &result.symlinks {
+        for (path, reason) in &report.skipped {
+            eprintln!("warning: symlink {}: {reason}", path.display());
+        }
+    }
+    if let Some(recommendation) = &result.setup_recommendation {
+        eprintln!("{recommendation}");
+    }
+
+    Ok(())
+}
+
+fn print_add_result(
+    result: &worktree::AddResult,
+    carry: bool,
+    pr: Option<u64>,
+    fmt: NavigationFormat,
+) -> Result<()> {
     let path_str = result.worktree_path.display().to_string();
     let root_str = result.repo_root.display().to_string();
     let branch_name = &result.branch;
     let tracking = result.tracking;
 
-    let symlinked: Vec<String> = result
-        .symlinks
-        .as_ref()
-        .map(|r| r.created.iter().map(|p| p.display().to_string()).collect())
-        .unwrap_or_default();
+    let carried: Vec<String> = result
+        .carried
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect();
     match fmt {
         NavigationFormat::CdPath => {
             println!("{path_str}");
         }
         NavigationFormat::Json => {
-            let message = if tracking {
-                format!(
+            let message = match pr {
+                Some(n) => format!("created review worktree for PR #{n} on branch '{branch_name}'"),
+                None if tracking => format!(
                     "created worktree for branch '{branch_name}' tracking 'origin/{branch_name}'"
-                )
-            } else {
-                format!("created worktree for branch '{branch_name}'")
+                ),
+                None => format!("created worktree for branch '{branch_name}'"),
             };
             let resp = JsonResponse::success(message)
                 .with_event("switch")
@@ -532,22 +883,43 @@ fn cmd_add(
                 .with_cd_path(&path_str)
                 .with_branch(branch_name.as_str())
                 .with_tracking(tracking)
-                .with_symlinks(symlinked);
+                .with_symlinks(linked_paths(result.symlinks.as_ref()))
+                .with_links(linked_entries(result.symlinks.as_ref()))
+                .with_sparse(result.sparse.clone())
+                .with_steps(&result.steps)
+                .with_warnings(result.warnings.clone());
+            let resp = if carry {
+                resp.with_files(carried)
+            } else {
+                resp
+            };
             print_json(&resp)?;
         }
         NavigationFormat::Human => {
-            if tracking {
+            if let Some(n) = pr {
+                println!(
+                    "Created review worktree for PR #{n} on branch '{branch_name}' at {path_str}"
+                );
+            } else if tracking {
                 println!("Created worktree for branch '{branch_name}' tracking 'origin/{branch_name}' at {path_str}");
             } else {
                 println!("Created worktree for branch '{branch_name}' at {path_str}");
             }
-            if let Some(report) = &result.symlinks {
-                for path in &report.created {
-                    println!("  Symlinked {}", path.display());
-                }
+            print_setup_steps(&result.steps);
+            print_linked(result.symlinks.as_ref());
+            if !result.sparse.is_empty() {
+                println!("  Sparse checkout: {}", result.sparse.join(", "));
+            }
+            for path in &carried {
+                println!("  Carried {path}");
             }
         }
     }
+    if !matches!(fmt, NavigationFormat::Json) {
+        for w in &result.warnings {
+            eprintln!("warning: {w}");
+        }
+    }
     if let Some(report) = &result.symlinks {
         for (path, reason) in &report.skipped {
             eprintln!("warning: symlink {}: {reason}", path.display());
@@ -563,25 +935,44 @@ fn cmd_add(
 fn cmd_go(
     branch: Option<&str>,
     interactive: bool,
+    keep_subdir: Option<bool>,
+    tmux: Option<bool>,
     repo: Option<PathBuf>,
     fmt: NavigationFormat,
 ) -> Result<()> {
     let repo = resolve_repo(repo)?;
+    let backend = backend::detect(&repo);
+    let current = current_worktree_path(backend.as_ref(), &repo);
 
-    let resolved_branch = match branch {
-        Some(b) => BranchName::new(b),
-        None => resolve_interactive_branch(&repo, interactive, fmt)?,
+    let result = if branch == Some("-") {
+        worktree::go_back(&repo, current.as_deref())?
+    } else {
+        let resolved_branch = match branch {
+            Some(b) => resolve_go_query(backend.as_ref(), &repo, b, fmt)?,
+            None => resolve_interactive_branch(&repo, interactive, fmt)?,
+        };
+        worktree::go_with(backend.as_ref(), &repo, &resolved_branch)?
+    };
+    cli_support::record_visit(&repo, current.as_deref(), &result.worktree_path);
+
+    let keep_subdir = keep_subdir
+        .unwrap_or_else(|| cli_support::keep_subdir_configured_with(backend.as_ref(), &repo));
+    let cwd = std::env::current_dir().ok();
+    let cd_path = match (keep_subdir, &current, &cwd) {
+        (true, Some(current), Some(cwd)) => {
+            cli_support::matching_subdir(current, cwd, &result.worktree_path)
+        }
+        _ => result.worktree_path.clone(),
     };
-
-    let result = worktree::go(&repo, &resolved_branch)?;
 
     let path_str = result.worktree_path.display().to_string();
+    let cd_str = cd_path.display().to_string();
     let root_str = result.repo_root.display().to_string();
     let branch_name = &result.branch;
 
     match fmt {
         NavigationFormat::CdPath => {
-            println!("{path_str}");
+            println!("{cd_str}");
         }
         NavigationFormat::Json => {
             let resp =
@@ -589,7 +980,7 @@ fn cmd_go(
                     .with_event("switch")
                     .with_repo_root(&root_str)
                     .with_worktree_path(&path_str)
-                    .with_cd_path(&path_str)
+                    .with_cd_path(&cd_str)
                     .with_branch(branch_name.as_str());
             print_json(&resp)?;
         }
@@ -597,9 +988,56 @@ fn cmd_go(
             println!("Worktree for branch '{branch_name}' is at {path_str}");
         }
     }
+    tmux_session(backend.as_ref(), &result.worktree_path, tmux, fmt);
     Ok(())
 }
 
+/// Find or create the tmux session of the worktree at `path` when enabled
+/// by `--tmux` or `git config wt.tmux`, switching to it unless the output
+/// is JSON. The worktree is in place either way, so tmux failures are only
+/// warnings; notes go to stderr to keep stdout for the shell wrappers.
+fn tmux_session(backend: &dyn GitBackend, path: &Path, tmux: Option<bool>, fmt: NavigationFormat) {
+    if !tmux.unwrap_or_else(|| cli_support::tmux_configured_with(backend, path)) {
+        return;
+    }
+    match cli_support::tmux_session(path, fmt != NavigationFormat::Json) {
+        Ok(result) if fmt != NavigationFormat::Json => {
+            let verb = if result.created { "Created" } else { "Found" };
+            eprintln!("{verb} tmux session '{}'", result.session);
+            if !result.switched {
+                eprintln!("  attach with: tmux attach -t '={}'", result.session);
+            }
+        }
+        Ok(_) => {}
+        Err(err) => eprintln!("warning: {err}"),
+    }
+}
+
+/// Resolve a `go` identifier, opening the picker pre-filtered with `query`
+/// when it matches several worktrees and a terminal is available.
+///
+/// Otherwise the query is passed through, and [`worktree::go_with`] either
+/// resolves it or fails with the list of candidates.
+fn resolve_go_query(
+    backend: &dyn GitBackend,
+    repo: &domain::RepoRoot,
+    query: &str,
+    fmt: NavigationFormat,
+) -> Result<BranchName> {
+    let can_pick = cfg!(feature = "interactive")
+        && fmt != NavigationFormat::Json
+        && std::io::stdin().is_terminal();
+    if !can_pick {
+        return Ok(BranchName::new(query));
+    }
+    let mut worktrees = backend.list_worktrees(repo)?;
+    if worktree::match_worktrees(&worktrees, query).len() <= 1 {
+        return Ok(BranchName::new(query));
+    }
+    cli_support::sort_by_recent(repo, &mut worktrees);
+    pick_worktree(&worktrees, Some(query))
+}
+
 /// Resolve a branch via interactive picker or error if not possible.
 fn resolve_interactive_branch(
     repo: &domain::RepoRoot,
@@ -615,7 +1053,7 @@ fn resolve_interactive_branch(
         ));
     }
 
-    let worktrees = git::list_worktrees(repo)?;
+    let mut worktrees = worktree::list_worktrees(repo)?;
     let candidates: Vec<_> = worktrees.iter().filter(|wt| !wt.is_main).collect();
 
     if candidates.is_empty() {
@@ -640,15 +1078,39 @@ fn resolve_interactive_branch(
         ));
     }
 
-    pick_worktree(&worktrees)
+    cli_support::sort_by_recent(repo, &mut worktrees);
+    pick_worktree(&worktrees, None)
+}
+
+/// Path of the worktree containing the cwd, if it belongs to `repo`.
+fn current_worktree_path(backend: &dyn GitBackend, repo: &domain::RepoRoot) -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?.canonicalize().ok()?;
+    let worktrees = backend.list_worktrees(repo).ok()?;
+    let idx = find_current_worktree(&worktrees, &cwd)?;
+    Some(worktrees[idx].path.clone())
+}
+
+/// Record navigation from the worktree containing the cwd to `to`.
+fn record_visit(repo: &domain::RepoRoot, to: &Path) {
+    let current = current_worktree_path(backend::detect(repo).as_ref(), repo);
+    cli_support::record_visit(repo, current.as_deref(), to);
 }
 
 /// Present an interactive fuzzy picker and return the selected branch.
+///
+/// Entries are shown in the given order (most recently visited first), with
+/// the first one other than the current worktree selected. `filter`
+/// pre-fills the picker's search text.
 #[cfg(feature = "interactive")]
-fn pick_worktree(worktrees: &[domain::Worktree]) -> Result<BranchName> {
+fn pick_worktree(worktrees: &[domain::Worktree], filter: Option<&str>) -> Result<BranchName> {
     use dialoguer::theme::ColorfulTheme;
     use dialoguer::FuzzySelect;
 
+    let current = std::env::current_dir()
+        .ok()
+        .and_then(|p| p.canonicalize().ok())
+        .and_then(|cwd| find_current_worktree(worktrees, &cwd));
+
     let items: Vec<String> = worktrees
         .iter()
         .map(|wt| {
@@ -661,7 +1123,8 @@ fn pick_worktree(worktrees: &[domain::Worktree]) -> Result<BranchName> {
     let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
         .with_prompt("Select worktree")
         .items(&items)
-        .default(1) // skip main worktree (always index 0)
+        .with_initial_text(filter.unwrap_or_default())
+        .default(usize::from(current == Some(0)))
         .interact_opt()
         .map_err(|e| AppError::usage(format!("picker failed: {e}")))?;
 
@@ -680,7 +1143,7 @@ fn pick_worktree(worktrees: &[domain::Worktree]) -> Result<BranchName> {
 }
 
 #[cfg(not(feature = "interactive"))]
-fn pick_worktree(_worktrees: &[domain::Worktree]) -> Result<BranchName> {
+fn pick_worktree(_worktrees: &[domain::Worktree], _filter: Option<&str>) -> Result<BranchName> {
     Err(AppError::usage(
         "interactive mode not available (compiled without 'interactive' feature)".to_string(),
     ))
@@ -709,7 +1172,7 @@ fn resolve_action_branch(
         return Ok(None);
     }
 
-    let worktrees = git::list_worktrees(repo)?;
+    let worktrees = worktree::list_worktrees(repo)?;
     let candidates: Vec<_> = worktrees.iter().filter(|wt| !wt.is_main).collect();
 
     if candidates.is_empty() {
@@ -916,7 +1379,7 @@ fn resolve_diff_worktree(
     repo: &domain::RepoRoot,
     branch: Option<BranchName>,
 ) -> Result<domain::Worktree> {
-    let worktrees = git::list_worktrees(repo)?;
+    let worktrees = worktree::list_worktrees(repo)?;
 
     if let Some(branch) = branch {
         return worktrees
@@ -957,7 +1420,7 @@ fn resolve_diff_worktree(
 }
 
 fn resolve_diff_branch(repo: &domain::RepoRoot) -> Result<BranchName> {
-    let worktrees = git::list_worktrees(repo)?;
+    let worktrees = worktree::list_worktrees(repo)?;
     let candidates: Vec<_> = worktrees.iter().filter(|wt| !wt.is_main).collect();
 
     if candidates.is_empty() {
@@ -1062,6 +1525,61 @@ fn cmd_merge(
     Ok(())
 }
 
+fn cmd_recent(repo: Option<PathBuf>, fmt: StatusFormat) -> Result<()> {
+    let repo = resolve_repo(repo)?;
+    let recent = worktree::recent(&repo)?;
+    let current = current_worktree_path(backend::detect(&repo).as_ref(), &repo);
+    let is_current = |wt: &worktree::RecentWorktree| current.as_deref() == Some(wt.path.as_path());
+
+    match fmt {
+        StatusFormat::Json => print_json(&JsonRecentResponse {
+            ok: true,
+            worktrees: recent
+                .iter()
+                .map(|wt| JsonRecentEntry {
+                    path: wt.path.display().to_string(),
+                    branch: wt.branch.clone(),
+                    visited_at: wt.visited_at,
+                    is_current: is_current(wt),
+                })
+                .collect(),
+        })?,
+        StatusFormat::Human => {
+            if recent.is_empty() {
+                println!("No recently visited worktrees");
+            }
+            for wt in &recent {
+                let branch_str = wt.branch.as_deref().unwrap_or("(detached)");
+                let here_tag = if is_current(wt) { " ← here" } else { "" };
+                println!("{branch_str:<30} {}{here_tag}", wt.path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "interactive")]
+fn cmd_ui(repo: Option<PathBuf>, print_cd_path: bool) -> Result<()> {
+    let repo = resolve_repo(repo)?;
+    let Some(path) = crate::ui::run(&repo)? else {
+        return Ok(());
+    };
+    record_visit(&repo, &path);
+    if print_cd_path {
+        println!("{}", path.display());
+    } else {
+        println!("Worktree is at {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "interactive"))]
+fn cmd_ui(_repo: Option<PathBuf>, _print_cd_path: bool) -> Result<()> {
+    Err(AppError::usage(
+        "interactive mode not available (compiled without 'interactive' feature)".to_string(),
+    ))
+}
+
 fn cmd_remove(
     branch: Option<BranchName>,
     force: bool,
@@ -1109,6 +1627,7 @@ fn cmd_remove(
 fn cmd_prune(
     execute: bool,
     force: bool,
+    scratch: bool,
     mainline: Option<&str>,
     repo: Option<PathBuf>,
     fmt: PruneFormat,
@@ -1116,9 +1635,9 @@ fn cmd_prune(
     let repo = resolve_repo(repo)?;
 
     if execute {
-        cmd_prune_execute(&repo, mainline, force, fmt)
+        cmd_prune_execute(&repo, mainline, force, scratch, fmt)
     } else {
-        cmd_prune_dry_run(&repo, mainline, fmt)
+        cmd_prune_dry_run(&repo, mainline, scratch, fmt)
     }
 }
 
@@ -1133,6 +1652,22 @@ fn format_prune_entry(entry: &worktree::WorktreePruneEntry) -> (String, Option<S
         }
         worktree::IntegrationStatus::NotIntegrated => ("not_integrated".to_string(), None),
         worktree::IntegrationStatus::NoBranch => ("no_branch".to_string(), None),
+        worktree::IntegrationStatus::Expired(_) => ("expired".to_string(), None),
+    }
+}
+
+fn expiry_reason(entry: &worktree::WorktreePruneEntry) -> Option<String> {
+    match entry.status {
+        worktree::IntegrationStatus::Expired(worktree::ExpiryReason::RefGone) => {
+            Some("ref_gone".to_string())
+        }
+        worktree::IntegrationStatus::Expired(worktree::ExpiryReason::TtlElapsed) => {
+            Some("ttl_elapsed".to_string())
+        }
+        worktree::IntegrationStatus::Expired(worktree::ExpiryReason::Scratch) => {
+            Some("scratch".to_string())
+        }
+        _ => None,
     }
 }
 
@@ -1153,20 +1688,36 @@ fn print_prune_entry_human(entry: &worktree::WorktreePruneEntry) {
         worktree::IntegrationStatus::NoBranch => {
             println!("  ⚠ {:<20} no branch (detached HEAD)", "(detached)");
         }
+        worktree::IntegrationStatus::Expired(reason) => {
+            let reason_str = match reason {
+                worktree::ExpiryReason::RefGone => "review ref gone",
+                worktree::ExpiryReason::TtlElapsed => "TTL elapsed",
+                worktree::ExpiryReason::Scratch => "scratch",
+            };
+            let branch = entry.branch.as_deref().unwrap_or("(detached)");
+            println!("  ✓ {branch:<20} expired ({reason_str})");
+        }
     }
 }
 
 fn cmd_prune_dry_run(
     repo: &domain::RepoRoot,
     mainline: Option<&str>,
+    scratch: bool,
     fmt: PruneFormat,
 ) -> Result<()> {
-    let result = worktree::prune_dry_run(repo, mainline)?;
+    let result = worktree::prune_dry_run(repo, mainline, scratch)?;
 
     let prunable = result
         .entries
         .iter()
-        .filter(|e| matches!(e.status, worktree::IntegrationStatus::Integrated(_)))
+        .filter(|e| {
+            matches!(
+                e.status,
+                worktree::IntegrationStatus::Integrated(_)
+                    | worktree::IntegrationStatus::Expired(_)
+            )
+        })
         .count();
 
     match fmt {
@@ -1180,6 +1731,7 @@ fn cmd_prune_dry_run(
                         branch: e.branch.clone(),
                         status,
                         method,
+                        reason: expiry_reason(e),
                         path: e.path.display().to_string(),
                     }
                 })
@@ -1203,7 +1755,7 @@ fn cmd_prune_dry_run(
                 println!("\nNo integrated worktrees found.");
             } else {
                 println!(
-                    "\n{prunable} integrated worktree{} can be pruned. Run with --execute to remove.",
+                    "\n{prunable} worktree{} can be pruned. Run with --execute to remove.",
                     if prunable == 1 { "" } else { "s" }
                 );
             }
@@ -1216,9 +1768,10 @@ fn cmd_prune_execute(
     repo: &domain::RepoRoot,
     mainline: Option<&str>,
     force: bool,
+    scratch: bool,
     fmt: PruneFormat,
 ) -> Result<()> {
-    let result = worktree::prune_execute(repo, mainline, force)?;
+    let result = worktree::prune_execute(repo, mainline, force, scratch)?;
 
     match fmt {
         PruneFormat::Json => {
@@ -1252,16 +1805,14 @@ fn cmd_prune_execute(
         PruneFormat::Human => {
             println!("Mainline: {}", result.mainline);
             for entry in &result.pruned {
-                println!("  Removed {}", entry.branch);
+                match &entry.branch {
+                    Some(branch) => println!("  Removed {branch}"),
+                    None => println!("  Removed {} (detached)", entry.path.display()),
+                }
             }
             for entry in &result.skipped {
                 let label = entry.branch.as_deref().unwrap_or("(detached)");
-                let reason = match entry.reason.as_str() {
-                    "not_integrated" => "not integrated",
-                    "no_branch" => "no branch",
-                    "removal_failed" => "removal failed",
-                    other => other,
-                };
+                let reason = human_skip_reason(&entry.reason);
                 println!("  Skipped {label} ({reason})");
             }
             for w in &result.warnings {
@@ -1281,9 +1832,359 @@ fn cmd_prune_execute(
     Ok(())
 }
 
+/// Human-readable label for a machine-readable skip reason.
+fn human_skip_reason(reason: &str) -> &str {
+    match reason {
+        "not_integrated" => "not integrated",
+        "no_branch" => "no branch",
+        "removal_failed" => "removal failed",
+        "up_to_date" => "up to date",
+        "status_failed" => "status failed",
+        "dirty" => "uncommitted changes",
+        "conflict" => "conflict, aborted",
+        "sync_failed" => "sync failed",
+        "mainline" => "is mainline",
+        other => other,
+    }
+}
+
+fn cmd_sync(
+    branches: &[String],
+    strategy: worktree::SyncStrategy,
+    mainline: Option<&str>,
+    fetch: bool,
+    repo: Option<PathBuf>,
+    fmt: StatusFormat,
+) -> Result<()> {
+    let repo = resolve_repo(repo)?;
+    let branches: Vec<BranchName> = branches.iter().map(BranchName::new).collect();
+    let result = worktree::sync(&repo, &branches, strategy, mainline, fetch)?;
+
+    match fmt {
+        StatusFormat::Json => {
+            let synced = result
+                .synced
+                .iter()
+                .map(|e| JsonSyncedEntry {
+                    branch: e.branch.clone(),
+                    path: e.path.display().to_string(),
+                })
+                .collect();
+
+            let skipped = result
+                .skipped
+                .iter()
+                .map(|e| JsonSkippedEntry {
+                    branch: e.branch.clone(),
+                    reason: e.reason.clone(),
+                    path: e.path.display().to_string(),
+                })
+                .collect();
+
+            print_json(&JsonSyncResponse {
+                ok: true,
+                mainline: result.mainline,
+                strategy: result.strategy,
+                synced,
+                skipped,
+                warnings: result.warnings,
+            })?;
+        }
+        StatusFormat::Human => {
+            let verb = match result.strategy {
+                worktree::SyncStrategy::Rebase => "Rebased",
+                worktree::SyncStrategy::Merge => "Merged",
+            };
+            println!("Mainline: {}", result.mainline);
+            for entry in &result.synced {
+                println!("  {verb} {}", entry.branch);
+            }
+            for entry in &result.skipped {
+                let label = entry.branch.as_deref().unwrap_or("(detached)");
+                let reason = human_skip_reason(&entry.reason);
+                println!("  Skipped {label} ({reason})");
+            }
+            for w in &result.warnings {
+                eprintln!("warning: {w}");
+            }
+            let count = result.synced.len();
+            if count == 0 {
+                println!("\nNo worktrees synced.");
+            } else {
+                println!(
+                    "\nSynced {count} worktree{}.",
+                    if count == 1 { "" } else { "s" }
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_link(
+    branch: Option<&str>,
+    all: bool,
+    dry_run: bool,
+    prune: bool,
+    repo: Option<PathBuf>,
+    fmt: StatusFormat,
+) -> Result<()> {
+    let repo = resolve_repo(repo)?;
+    let branch = branch.map(BranchName::new);
+    let target = match (&branch, all) {
+        (_, true) => worktree::LinkTarget::All,
+        (Some(branch), false) => worktree::LinkTarget::Branch(branch),
+        (None, false) => worktree::LinkTarget::Current,
+    };
+    let reports = worktree::link(&repo, target, dry_run, prune)?;
+
+    match fmt {
+        StatusFormat::Json => {
+            let worktrees = reports
+                .iter()
+                .map(|r| JsonLinkWorktree {
+                    branch: r.branch.clone(),
+                    path: r.worktree_path.display().to_string(),
+                    links: r.outcomes.iter().map(JsonLinkOutcome::from).collect(),
+                })
+                .collect();
+            print_json(&JsonLinkResponse {
+                ok: true,
+                dry_run,
+                worktrees,
+            })?;
+        }
+        StatusFormat::Human => {
+            for report in &reports {
+                let label = report.branch.as_deref().unwrap_or("(detached)");
+                println!("{label} ({})", report.worktree_path.display());
+                if report.outcomes.is_empty() {
+                    println!("  Up to date");
+                }
+                for outcome in &report.outcomes {
+                    print_link_outcome(outcome, dry_run);
+                }
+            }
+            let changes = reports
+                .iter()
+                .flat_map(|r| &r.outcomes)
+                .filter(|o| !matches!(o, symlinks::SymlinkOutcome::Skipped(..)))
+                .count();
+            let plural = if changes == 1 { "" } else { "s" };
+            if dry_run {
+                println!(
+                    "\n{changes} change{plural} would be made. Run without --dry-run to apply."
+                );
+            } else {
+                println!("\n{changes} change{plural} made.");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_adopt(
+    path: Option<&Path>,
+    all: bool,
+    dry_run: bool,
+    repo: Option<PathBuf>,
+    fmt: StatusFormat,
+) -> Result<()> {
+    let repo = resolve_repo(repo)?;
+    let target = match (path, all) {
+        (_, true) => worktree::AdoptTarget::All,
+        (Some(path), false) => worktree::AdoptTarget::Path(path),
+        (None, false) => worktree::AdoptTarget::Current,
+    };
+    let reports = worktree::adopt(&repo, target, dry_run)?;
+    let failed = reports
+        .iter()
+        .any(|r| matches!(r.status, worktree::AdoptStatus::Failed(_)));
+
+    match fmt {
+        StatusFormat::Json => {
+            print_json(&JsonAdoptResponse {
+                ok: !failed,
+                dry_run,
+                worktrees: reports.iter().map(JsonAdoptWorktree::from).collect(),
+            })?;
+        }
+        StatusFormat::Human => {
+            let verb = if dry_run { "Would move" } else { "Moved" };
+            for report in &reports {
+                let label = report.branch.as_deref().unwrap_or("(detached)");
+                println!("{label}");
+                match &report.status {
+                    worktree::AdoptStatus::Unchanged => {
+                        println!("  Already at {}", report.to.display());
+                    }
+                    worktree::AdoptStatus::Failed(reason) => {
+                        println!("  Skipped {} ({reason})", report.from.display());
+                    }
+                    worktree::AdoptStatus::Moved => {
+                        println!(
+                            "  {verb} {} -> {}",
+                            report.from.display(),
+                            report.to.display()
+                        );
+                    }
+                }
+                for outcome in &report.links {
+                    print_link_outcome(outcome, dry_run);
+                }
+            }
+            let moved = reports
+                .iter()
+                .filter(|r| r.status == worktree::AdoptStatus::Moved)
+                .count();
+            let plural = if moved == 1 { "" } else { "s" };
+            if dry_run {
+                println!(
+                    "\n{moved} worktree{plural} would be adopted. Run without --dry-run to apply."
+                );
+            } else {
+                println!("\n{moved} worktree{plural} adopted.");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_link_outcome(outcome: &symlinks::SymlinkOutcome, dry_run: bool) {
+    use symlinks::{LinkMode, SymlinkOutcome};
+
+    let (planned, done, path, mode) = match outcome {
+        SymlinkOutcome::Created(path, mode) => ("Would create", "Created", path, Some(*mode)),
+        SymlinkOutcome::Repaired(path, mode) => ("Would repair", "Repaired", path, Some(*mode)),
+        SymlinkOutcome::Removed(path) => ("Would remove", "Removed", path, None),
+        SymlinkOutcome::Skipped(path, reason) => {
+            println!("  Skipped {} ({reason})", path.display());
+            return;
+        }
+    };
+    let verb = if dry_run { planned } else { done };
+    match mode.filter(|m| *m != LinkMode::Symlink) {
+        Some(mode) => println!("  {verb} {} ({})", path.display(), mode.as_str()),
+        None => println!("  {verb} {}", path.display()),
+    }
+}
+
+fn cmd_move_changes(
+    branch: &BranchName,
+    include_untracked: bool,
+    repo: Option<PathBuf>,
+    fmt: StatusFormat,
+) -> Result<()> {
+    let repo = resolve_repo(repo)?;
+    let result = worktree::move_changes(&repo, branch, include_untracked)?;
+
+    let source_str = result.source_path.display().to_string();
+    let path_str = result.worktree_path.display().to_string();
+    let files: Vec<String> = result
+        .files
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect();
+    let count = files.len();
+    let noun = if count == 1 { "file" } else { "files" };
+
+    match fmt {
+        StatusFormat::Json => {
+            let resp = JsonResponse::success(format!(
+                "moved {count} changed {noun} to worktree for branch '{}'",
+                result.branch
+            ))
+            .with_repo_root(result.repo_root.display().to_string())
+            .with_source_path(&source_str)
+            .with_worktree_path(&path_str)
+            .with_branch(result.branch.as_str())
+            .with_files(files)
+            .with_warnings(result.warnings);
+            print_json(&resp)?;
+        }
+        StatusFormat::Human => {
+            println!(
+                "Moved {count} changed {noun} from {source_str} to {path_str} ('{}')",
+                result.branch
+            );
+            for file in &files {
+                println!("  {file}");
+            }
+            for w in &result.warnings {
+                eprintln!("warning: {w}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_sparse(action: SparseCommand) -> Result<()> {
+    let (branch, update, patterns, repo, json) = match action {
+        SparseCommand::Add {
+            branch,
+            patterns,
+            repo,
+            json,
+        } => (
+            branch,
+            Some(worktree::SparseUpdate::Add),
+            patterns,
+            repo,
+            json,
+        ),
+        SparseCommand::Set {
+            branch,
+            patterns,
+            repo,
+            json,
+        } => (
+            branch,
+            Some(worktree::SparseUpdate::Set),
+            patterns,
+            repo,
+            json,
+        ),
+        SparseCommand::List { branch, repo, json } => (branch, None, Vec::new(), repo, json),
+    };
+
+    let repo = resolve_repo(repo)?;
+    let branch = BranchName::new(&branch);
+    let result = match update {
+        Some(update) => worktree::sparse_update(&repo, &branch, update, &patterns)?,
+        None => worktree::sparse_list(&repo, &branch)?,
+    };
+
+    match status_fmt(json) {
+        StatusFormat::Json => {
+            print_json(&JsonSparseResponse {
+                ok: true,
+                branch: result.branch.to_string(),
+                worktree_path: result.worktree_path.display().to_string(),
+                sparse: result.sparse,
+                patterns: result.patterns,
+            })?;
+        }
+        StatusFormat::Human => {
+            if !result.sparse {
+                println!(
+                    "Worktree for '{}' is not sparse (full checkout).",
+                    result.branch
+                );
+                return Ok(());
+            }
+            if update.is_some() {
+                println!("Updated sparse checkout for '{}':", result.branch);
+            }
+            for pattern in &result.patterns {
+                println!("  {pattern}");
+            }
+        }
+    }
+    Ok(())
+}
+
 fn cmd_setup(repo: Option<PathBuf>, fmt: StatusFormat) -> Result<()> {
     use crate::output::JsonSetupResponse;
-    use crate::symlinks;
 
     let repo = resolve_repo(repo)?;
     let config_path = symlinks::config_path(&repo);
@@ -1335,26 +2236,93 @@ fn cmd_setup(repo: Option<PathBuf>, fmt: StatusFormat) -> Result<()> {
     Ok(())
 }
 
+/// Print the prompt segment for the worktree containing the cwd.
+///
+/// Runs on every prompt render, so outside a worktree, or on any error, it
+/// prints nothing and still succeeds.
+fn cmd_prompt(format: &str, use_cache: bool) -> Result<()> {
+    let Some(cwd) = std::env::current_dir()
+        .ok()
+        .and_then(|p| p.canonicalize().ok())
+    else {
+        return Ok(());
+    };
+    let cached = use_cache
+        .then(|| worktree::cached_prompt_status(&cwd))
+        .flatten();
+    let segment = cached.or_else(|| {
+        let repo = worktree::repo_root(&cwd).ok()?;
+        let worktrees = backend::detect(&repo).list_worktrees(&repo).ok()?;
+        let wt = &worktrees[find_current_worktree(&worktrees, &cwd)?];
+        worktree::prompt_status(&repo, wt, use_cache).ok()
+    });
+    if let Some(status) = segment {
+        println!("{}", status.render(format));
+    }
+    Ok(())
+}
+
 fn cmd_init(shell: Shell) -> Result<()> {
     let script = match shell {
         Shell::Bash => include_str!("../bindings/bash/wt.bash"),
         Shell::Zsh => include_str!("../bindings/zsh/wt.zsh"),
         Shell::Fish => include_str!("../bindings/fish/wt.fish"),
         Shell::Nu => include_str!("../bindings/nu/wt.nu"),
+        Shell::PowerShell => include_str!("../bindings/powershell/wt.ps1"),
+        Shell::Elvish => include_str!("../bindings/elvish/wt.elv"),
+        Shell::Xonsh => include_str!("../bindings/xonsh/wt.xsh"),
     };
-    print!("{script}");
+    print!("{}\n{script}", completions::script(shell));
     Ok(())
 }
 
-fn cmd_doctor(repo: Option<PathBuf>, fmt: StatusFormat) -> Result<()> {
+/// Print completion candidates, one per line.
+///
+/// Completion must never spam the prompt, so failures (e.g. outside a
+/// repository) print nothing and still succeed.
+fn cmd_complete(context: CompleteContext, repo: Option<PathBuf>) -> Result<()> {
+    let kind = match context {
+        CompleteContext::Worktrees => cli_support::CompletionKind::Worktrees,
+        CompleteContext::Branches => cli_support::CompletionKind::Branches,
+        CompleteContext::Revisions => cli_support::CompletionKind::Revisions,
+    };
+    let candidates = resolve_repo(repo)
+        .and_then(|repo| cli_support::completion_candidates(&repo, kind))
+        .unwrap_or_default();
+    for candidate in candidates {
+        println!("{candidate}");
+    }
+    Ok(())
+}
+
+fn cmd_doctor(repo: Option<PathBuf>, fix: bool, dry_run: bool, fmt: StatusFormat) -> Result<()> {
     let repo = resolve_repo(repo)?;
+    let fixes = if fix {
+        worktree::doctor_fix(&repo, dry_run)?
+    } else {
+        Vec::new()
+    };
     let diags = worktree::doctor(&repo)?;
 
     match fmt {
         StatusFormat::Json => {
-            print_json(&JsonDoctorResponse::from_diagnostics(&diags))?;
+            let mut response = JsonDoctorResponse::from_diagnostics(&diags);
+            if fix {
+                response = response.with_fixes(&fixes, dry_run);
+            }
+            print_json(&response)?;
         }
         StatusFormat::Human => {
+            for f in &fixes {
+                let icon = if f.ok { "✓" } else { "✗" };
+                println!("{icon} {}", f.message);
+            }
+            if fix && fixes.is_empty() {
+                println!("Nothing to fix.");
+            }
+            if fix {
+                println!();
+            }
             for d in &diags {
                 let icon = match d.level {
                     worktree::DiagLevel::Ok => "✓",
@@ -1362,6 +2330,9 @@ fn cmd_doctor(repo: Option<PathBuf>, fmt: StatusFormat) -> Result<()> {
                     worktree::DiagLevel::Error => "✗",
                 };
                 println!("{icon} {}", d.message);
+                if let Some(fix) = &d.fix {
+                    println!("    fix: {fix}");
+                }
             }
         }
     }
@@ -0,0 +1,56 @@
+//! Completion scripts generated from the clap definition.
+//!
+//! Static parts (subcommands, flags) come from clap; the shell bindings add
+//! live branch and revision candidates through `wt-core complete <context>`.
+
+use clap::{Command, CommandFactory};
+use clap_complete::generate;
+
+use crate::cli::{Cli, Shell};
+
+/// Completion script for `shell`, to be emitted ahead of its binding.
+///
+/// Bash, Zsh, Fish, PowerShell and Elvish complete the `wt` function;
+/// Nushell completes `wt-core` itself, since its binding declares `wt`
+/// subcommands natively. clap has no Xonsh generator, so Xonsh gets none.
+pub fn script(shell: Shell) -> String {
+    let mut cmd = visible_command();
+    let mut buf = Vec::new();
+    match shell {
+        Shell::Bash => generate(clap_complete::Shell::Bash, &mut cmd, "wt", &mut buf),
+        Shell::Zsh => generate(clap_complete::Shell::Zsh, &mut cmd, "wt", &mut buf),
+        Shell::Fish => generate(clap_complete::Shell::Fish, &mut cmd, "wt", &mut buf),
+        Shell::PowerShell => generate(clap_complete::Shell::PowerShell, &mut cmd, "wt", &mut buf),
+        Shell::Elvish => generate(clap_complete::Shell::Elvish, &mut cmd, "wt", &mut buf),
+        Shell::Xonsh => {}
+        Shell::Nu => generate(
+            clap_complete_nushell::Nushell,
+            &mut cmd,
+            "wt-core",
+            &mut buf,
+        ),
+    }
+    let script = String::from_utf8_lossy(&buf).into_owned();
+    match shell {
+        // `compdef` only exists once compinit has run.
+        Shell::Zsh => format!("if (( $+functions[compdef] )); then\n{script}fi\n"),
+        _ => script,
+    }
+}
+
+/// The CLI without hidden subcommands, which the generators would list.
+fn visible_command() -> Command {
+    let full = Cli::command();
+    let subcommands: Vec<Command> = full
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .cloned()
+        .collect();
+    let mut cmd = Command::new("wt-core")
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommands(subcommands);
+    if let Some(about) = full.get_about() {
+        cmd = cmd.about(about.clone());
+    }
+    cmd
+}
@@ -36,6 +36,7 @@ impl fmt::Display for RepoRoot {
 pub struct BranchName(pub(crate) String);
 
 impl BranchName {
+    /// Wrap a branch name as given (no validation is performed).
     pub fn new(name: impl Into<String>) -> Self {
         Self(name.into())
     }
@@ -65,28 +66,54 @@ impl fmt::Display for BranchName {
 /// A worktree entry as reported by `git worktree list`.
 #[derive(Debug, Clone)]
 pub struct Worktree {
+    /// Absolute path of the worktree directory.
     pub path: PathBuf,
+    /// Checked-out branch, or `None` for a detached `HEAD`.
     pub branch: Option<String>,
+    /// Abbreviated `HEAD` commit.
     pub commit: String,
+    /// Whether this is the main worktree (the first entry Git reports).
     pub is_main: bool,
 }
 
+/// An uncommitted change reported by `git status`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    /// Path relative to the worktree root.
+    pub path: PathBuf,
+    /// Whether the file is untracked (`??`).
+    pub untracked: bool,
+}
+
 /// Git comparison stats for a worktree branch against a base revision.
 #[derive(Debug, Clone)]
 pub struct WorktreeStats {
+    /// Revision the stats were computed against.
     pub base: String,
+    /// Commits on the branch that are not on `base`.
     pub commits_ahead: u32,
+    /// Commits on `base` that are not on the branch.
     pub commits_behind: u32,
+    /// Files changed relative to the merge base.
     pub files_changed: u32,
+    /// Inserted lines relative to the merge base.
     pub insertions: u32,
+    /// Deleted lines relative to the merge base.
     pub deletions: u32,
 }
 
 /// Stats are unavailable for detached worktrees or refs Git cannot compare.
 #[derive(Debug, Clone)]
 pub enum WorktreeStatsStatus {
+    /// Stats were computed successfully.
     Available(WorktreeStats),
-    Unavailable { base: String, reason: String },
+    /// Stats could not be computed against `base`, with the reason.
+    Unavailable {
+        /// Revision the stats were computed against.
+        base: String,
+        /// Why the stats could not be computed.
+        reason: String,
+    },
 }
 
 /// Slugify a branch name: replace non-alphanumeric chars with hyphens,
@@ -30,11 +30,14 @@ impl From<ExitCode> for process::ExitCode {
 /// Application-level error with a stable exit code.
 #[derive(Debug)]
 pub struct AppError {
+    /// Exit code the CLI reports for this error.
     pub code: ExitCode,
+    /// Human-readable description.
     pub message: String,
 }
 
 impl AppError {
+    /// Invalid arguments or usage (exit code 1).
     pub fn usage(msg: impl Into<String>) -> Self {
         Self {
             code: ExitCode::Usage,
@@ -42,6 +45,7 @@ impl AppError {
         }
     }
 
+    /// A git command failed (exit code 2).
     pub fn git(msg: impl Into<String>) -> Self {
         Self {
             code: ExitCode::Git,
@@ -49,6 +53,7 @@ impl AppError {
         }
     }
 
+    /// The path is not inside a git repository (exit code 3).
     pub fn not_a_repo(msg: impl Into<String>) -> Self {
         Self {
             code: ExitCode::NotARepo,
@@ -56,6 +61,7 @@ impl AppError {
         }
     }
 
+    /// An internal invariant was violated (exit code 4).
     pub fn invariant(msg: impl Into<String>) -> Self {
         Self {
             code: ExitCode::Invariant,
@@ -63,6 +69,7 @@ impl AppError {
         }
     }
 
+    /// The repository state conflicts with the request (exit code 5).
     pub fn conflict(msg: impl Into<String>) -> Self {
         Self {
             code: ExitCode::Conflict,
@@ -79,4 +86,5 @@ impl fmt::Display for AppError {
 
 impl std::error::Error for AppError {}
 
+/// Result type used throughout the crate.
 pub type Result<T> = std::result::Result<T, AppError>;
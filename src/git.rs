@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use std::process::Command as Cmd;
 use std::process::Stdio;
 
-use crate::domain::{BranchName, RepoRoot, Worktree, WorktreeStats};
+use crate::domain::{BranchName, ChangedFile, RepoRoot, Worktree, WorktreeStats};
 use crate::error::{AppError, Result};
 
 /// Environment variables that can leak from parent git processes (e.g. hooks)
@@ -22,6 +22,14 @@ const GIT_ENV_OVERRIDES: &[&str] = &[
 /// Clears inherited `GIT_*` environment variables that could redirect
 /// operations to the wrong repository (common when invoked from git hooks).
 fn git(args: &[&str], cwd: &Path) -> Result<String> {
+    git_raw(args, cwd).map(|out| out.trim().to_string())
+}
+
+/// Like [`git`], but returns stdout untrimmed.
+///
+/// Needed for formats where leading whitespace is significant, such as
+/// `git status --porcelain` status codes.
+fn git_raw(args: &[&str], cwd: &Path) -> Result<String> {
     let mut cmd = Cmd::new("git");
     cmd.args(args).current_dir(cwd);
 
@@ -39,7 +47,7 @@ fn git(args: &[&str], cwd: &Path) -> Result<String> {
         return Err(classify_git_error(msg));
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
 /// Inspect git stderr to map known error patterns to the correct exit code.
@@ -97,12 +105,58 @@ pub fn repo_root(start: &Path) -> Result<RepoRoot> {
 /// List all worktrees via `git worktree list --porcelain`.
 pub fn list_worktrees(repo: &RepoRoot) -> Result<Vec<Worktree>> {
     // Prune stale worktrees first (matches current behavior expectation).
-    let _ = git(&["worktree", "prune"], repo.as_ref());
+    let _ = prune_worktrees(repo);
+    list_registered_worktrees(repo)
+}
 
+/// List worktrees without pruning, so registrations whose directory has
+/// been deleted are still reported.
+pub fn list_registered_worktrees(repo: &RepoRoot) -> Result<Vec<Worktree>> {
     let raw = git(&["worktree", "list", "--porcelain"], repo.as_ref())?;
     parse_worktree_porcelain(&raw, repo)
 }
 
+/// Drop registrations of worktrees whose directory no longer exists.
+pub fn prune_worktrees(repo: &RepoRoot) -> Result<()> {
+    git(&["worktree", "prune"], repo.as_ref())?;
+    Ok(())
+}
+
+/// Relocate a linked worktree with `git worktree move`.
+pub fn move_worktree(repo: &RepoRoot, from: &Path, to: &Path) -> Result<()> {
+    let from_str = from.display().to_string();
+    let to_str = to.display().to_string();
+    git(&["worktree", "move", &from_str, &to_str], repo.as_ref())?;
+    Ok(())
+}
+
+/// Add a worktree for the existing `branch` without populating its files.
+pub fn add_worktree_for_branch_no_checkout(
+    repo: &RepoRoot,
+    dir: &Path,
+    branch: &BranchName,
+) -> Result<()> {
+    let dir_str = dir.display().to_string();
+    git(
+        &[
+            "worktree",
+            "add",
+            "--no-checkout",
+            &dir_str,
+            branch.as_str(),
+        ],
+        repo.as_ref(),
+    )?;
+    Ok(())
+}
+
+/// Reset the index of the worktree at `path` to `HEAD`, leaving its files
+/// untouched.
+pub fn reset_index(path: &Path) -> Result<()> {
+    git(&["reset", "-q"], path)?;
+    Ok(())
+}
+
 /// A raw worktree entry parsed from porcelain lines.
 struct RawEntry {
     path: PathBuf,
@@ -181,16 +235,51 @@ fn parse_worktree_porcelain(raw: &str, _repo: &RepoRoot) -> Result<Vec<Worktree>
     Ok(worktrees)
 }
 
+/// Create a worktree at `dir` with a detached `HEAD` at `rev`.
+pub fn add_worktree_detached(repo: &RepoRoot, dir: &Path, rev: &str) -> Result<()> {
+    let dir_str = dir.display().to_string();
+    git(
+        &["worktree", "add", "--detach", &dir_str, rev],
+        repo.as_ref(),
+    )?;
+    Ok(())
+}
+
 /// Add a new worktree.
 pub fn add_worktree(
     repo: &RepoRoot,
     dir: &Path,
     branch: &BranchName,
     base: Option<&str>,
+) -> Result<()> {
+    add_worktree_with(repo, dir, branch, base, &[])
+}
+
+/// Add a new worktree without populating its files.
+///
+/// Used to configure sparse checkout before the first checkout; finish with
+/// [`checkout_head`].
+pub fn add_worktree_no_checkout(
+    repo: &RepoRoot,
+    dir: &Path,
+    branch: &BranchName,
+    base: Option<&str>,
+) -> Result<()> {
+    add_worktree_with(repo, dir, branch, base, &["--no-checkout"])
+}
+
+fn add_worktree_with(
+    repo: &RepoRoot,
+    dir: &Path,
+    branch: &BranchName,
+    base: Option<&str>,
+    extra: &[&str],
 ) -> Result<()> {
     let base_rev = base.unwrap_or("HEAD");
     let branch_str = branch.as_str();
-    let mut args = vec!["worktree", "add", "-b", branch_str];
+    let mut args = vec!["worktree", "add"];
+    args.extend_from_slice(extra);
+    args.extend(["-b", branch_str]);
     let dir_str = dir.display().to_string();
     args.push(&dir_str);
     args.push(base_rev);
@@ -199,6 +288,150 @@ pub fn add_worktree(
     Ok(())
 }
 
+/// Populate the files of a worktree created with `--no-checkout`.
+pub fn checkout_head(path: &Path) -> Result<()> {
+    git(&["checkout"], path)?;
+    Ok(())
+}
+
+/// Replace the cone-mode sparse-checkout patterns of the worktree at `path`,
+/// enabling sparse checkout if needed.
+///
+/// Git stores the setting in the per-worktree config, so other worktrees of
+/// the repository are unaffected.
+pub fn sparse_checkout_set(path: &Path, patterns: &[String]) -> Result<()> {
+    let mut args = vec!["sparse-checkout", "set", "--cone", "--"];
+    args.extend(patterns.iter().map(String::as_str));
+    git(&args, path)?;
+    Ok(())
+}
+
+/// Add cone-mode sparse-checkout patterns to the worktree at `path`.
+pub fn sparse_checkout_add(path: &Path, patterns: &[String]) -> Result<()> {
+    let mut args = vec!["sparse-checkout", "add", "--"];
+    args.extend(patterns.iter().map(String::as_str));
+    git(&args, path)?;
+    Ok(())
+}
+
+/// List the sparse-checkout patterns of the worktree at `path`.
+pub fn sparse_checkout_list(path: &Path) -> Result<Vec<String>> {
+    let output = git(&["sparse-checkout", "list"], path)?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Whether the worktree at `path` uses sparse checkout.
+pub fn is_sparse(path: &Path) -> bool {
+    matches!(config_get(path, "core.sparseCheckout"), Ok(Some(v)) if v == "true")
+}
+
+/// List the submodules declared in `.gitmodules` at `path` as
+/// `(name, path)` pairs.
+///
+/// Returns an empty vec when the worktree has no `.gitmodules`.
+pub fn submodules(path: &Path) -> Vec<(String, String)> {
+    if !path.join(".gitmodules").is_file() {
+        return Vec::new();
+    }
+    git(
+        &[
+            "config",
+            "--file",
+            ".gitmodules",
+            "--get-regexp",
+            r"^submodule\..*\.path$",
+        ],
+        path,
+    )
+    .unwrap_or_default()
+    .lines()
+    .filter_map(|line| {
+        let (key, sub_path) = line.split_once(' ')?;
+        let name = key.strip_prefix("submodule.")?.strip_suffix(".path")?;
+        Some((name.to_string(), sub_path.to_string()))
+    })
+    .collect()
+}
+
+/// Initialize and check out the submodule at `sub_path` (recursively).
+///
+/// With `reference`, objects are borrowed from an existing clone of the
+/// submodule and then copied (`--dissociate`), so the new checkout does not
+/// depend on the reference repository staying around.
+pub fn submodule_update(path: &Path, sub_path: &str, reference: Option<&Path>) -> Result<()> {
+    let reference = reference.map(|p| p.display().to_string());
+    let mut args = vec!["submodule", "update", "--init", "--recursive"];
+    if let Some(reference) = &reference {
+        args.extend(["--reference", reference, "--dissociate"]);
+    }
+    args.extend(["--", sub_path]);
+    git(&args, path)?;
+    Ok(())
+}
+
+/// Whether any tracked `.gitattributes` in the worktree at `path` routes
+/// files through the Git LFS filter.
+pub fn uses_lfs(path: &Path) -> bool {
+    git_success(
+        &[
+            "grep",
+            "-q",
+            "-F",
+            "filter=lfs",
+            "--",
+            ":(glob)**/.gitattributes",
+        ],
+        path,
+    )
+}
+
+/// Whether the `git lfs` extension is installed.
+pub fn lfs_available(path: &Path) -> bool {
+    git_success(&["lfs", "version"], path)
+}
+
+/// Replace LFS pointer files in the worktree at `path` with their content
+/// from the local LFS cache, without touching the network.
+pub fn lfs_checkout(path: &Path) -> Result<()> {
+    git(&["lfs", "checkout"], path)?;
+    Ok(())
+}
+
+/// List untracked paths (including ignored ones) in the worktree at `path`,
+/// relative to its root.
+///
+/// Wholly untracked directories are collapsed into a single entry with a
+/// trailing `/`, so large ignored trees are not enumerated.
+pub fn untracked_paths(path: &Path) -> Result<Vec<String>> {
+    let output = git_raw(&["ls-files", "-z", "--others", "--directory"], path)?;
+    Ok(output
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Return which of `paths` (relative to the worktree root at `path`) are in
+/// the index.
+pub fn tracked_paths(path: &Path, paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    let specs: Vec<String> = paths
+        .iter()
+        .map(|p| format!(":(literal){}", p.display()))
+        .collect();
+    let mut args = vec!["ls-files", "-z", "--"];
+    args.extend(specs.iter().map(String::as_str));
+    let output = git_raw(&args, path)?;
+    // A directory pathspec also lists files below it; keep exact matches.
+    Ok(output
+        .split('\0')
+        .map(PathBuf::from)
+        .filter(|p| paths.contains(p))
+        .collect())
+}
+
 /// Remove a worktree directory.
 pub fn remove_worktree(repo: &RepoRoot, dir: &Path, force: bool) -> Result<()> {
     let dir_str = dir.display().to_string();
@@ -212,6 +445,26 @@ pub fn remove_worktree(repo: &RepoRoot, dir: &Path, force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Lock the linked worktree at `dir`, recording `reason` when given.
+pub fn lock_worktree(repo: &RepoRoot, dir: &Path, reason: Option<&str>) -> Result<()> {
+    let dir_str = dir.display().to_string();
+    let mut args = vec!["worktree", "lock"];
+    if let Some(reason) = reason {
+        args.extend(["--reason", reason]);
+    }
+    args.push(&dir_str);
+
+    git(&args, repo.as_ref())?;
+    Ok(())
+}
+
+/// Unlock the linked worktree at `dir`.
+pub fn unlock_worktree(repo: &RepoRoot, dir: &Path) -> Result<()> {
+    let dir_str = dir.display().to_string();
+    git(&["worktree", "unlock", &dir_str], repo.as_ref())?;
+    Ok(())
+}
+
 /// Delete a local branch.
 pub fn delete_branch(repo: &RepoRoot, branch: &BranchName, force: bool) -> Result<()> {
     let flag = if force { "-D" } else { "-d" };
@@ -283,6 +536,23 @@ fn resolve_origin_head(repo: &RepoRoot) -> Option<String> {
     Some(symref)
 }
 
+/// Whether `origin/HEAD` is a symbolic ref whose target exists, i.e. whether
+/// [`resolve_mainline`] can use it instead of falling back.
+pub fn origin_head_resolves(repo: &RepoRoot) -> bool {
+    git_success(
+        &["symbolic-ref", "--quiet", "refs/remotes/origin/HEAD"],
+        repo.as_ref(),
+    ) && git_success(
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "refs/remotes/origin/HEAD",
+        ],
+        repo.as_ref(),
+    )
+}
+
 /// Auto-detect the mainline branch.
 ///
 /// Resolution order:
@@ -327,7 +597,7 @@ pub fn resolve_mainline(repo: &RepoRoot) -> Result<String> {
 pub fn worktree_stats(repo: &RepoRoot, base: &str, branch: &str) -> Result<WorktreeStats> {
     let branch_ref = format!("refs/heads/{branch}");
     let range = format!("{base}...{branch_ref}");
-    let (commits_behind, commits_ahead) = rev_list_counts(repo, &range)?;
+    let (commits_behind, commits_ahead) = rev_list_counts(repo.as_ref(), &range)?;
     let (files_changed, insertions, deletions) = diff_numstat(repo, &range)?;
 
     Ok(WorktreeStats {
@@ -340,11 +610,14 @@ pub fn worktree_stats(repo: &RepoRoot, base: &str, branch: &str) -> Result<Workt
     })
 }
 
-fn rev_list_counts(repo: &RepoRoot, range: &str) -> Result<(u32, u32)> {
-    let output = git(
-        &["rev-list", "--left-right", "--count", range],
-        repo.as_ref(),
-    )?;
+/// Commits `HEAD` of the worktree at `path` is `(ahead, behind)` of `base`.
+pub fn ahead_behind(path: &Path, base: &str) -> Result<(u32, u32)> {
+    let (behind, ahead) = rev_list_counts(path, &format!("{base}...HEAD"))?;
+    Ok((ahead, behind))
+}
+
+fn rev_list_counts(path: &Path, range: &str) -> Result<(u32, u32)> {
+    let output = git(&["rev-list", "--left-right", "--count", range], path)?;
     let mut fields = output.split_whitespace();
     let behind = fields
         .next()
@@ -357,7 +630,8 @@ fn rev_list_counts(repo: &RepoRoot, range: &str) -> Result<(u32, u32)> {
     Ok((behind, ahead))
 }
 
-fn diff_numstat(repo: &RepoRoot, range: &str) -> Result<(u32, u32, u32)> {
+/// Files changed, insertions and deletions over `range`.
+pub fn diff_numstat(repo: &RepoRoot, range: &str) -> Result<(u32, u32, u32)> {
     let output = git(&["diff", "--numstat", range], repo.as_ref())?;
     let mut files_changed = 0;
     let mut insertions = 0;
@@ -426,12 +700,300 @@ pub fn merge_no_ff(repo: &RepoRoot, branch: &str) -> Result<()> {
     Ok(())
 }
 
-/// Abort an in-progress merge.
+/// Abort an in-progress merge in the worktree at `path`.
 ///
 /// Best-effort: if there is no merge to abort, git returns an error that
 /// we silently ignore.
-pub fn merge_abort(repo: &RepoRoot) {
-    let _ = git(&["merge", "--abort"], repo.as_ref());
+pub fn merge_abort(path: &Path) {
+    let _ = git(&["merge", "--abort"], path);
+}
+
+/// Merge `upstream` into the branch checked out at `path`.
+///
+/// Fast-forwards when possible; otherwise creates a merge commit with
+/// Git's default message.
+pub fn merge_upstream(path: &Path, upstream: &str) -> Result<()> {
+    git(&["merge", "--no-edit", upstream], path)?;
+    Ok(())
+}
+
+/// Rebase the branch checked out at `path` onto `upstream`.
+pub fn rebase(path: &Path, upstream: &str) -> Result<()> {
+    git(&["rebase", upstream], path)?;
+    Ok(())
+}
+
+/// Abort an in-progress rebase in the worktree at `path`.
+///
+/// Best-effort, like [`merge_abort`].
+pub fn rebase_abort(path: &Path) {
+    let _ = git(&["rebase", "--abort"], path);
+}
+
+/// Whether the index of the worktree at `path` has unmerged paths, i.e. a
+/// merge or rebase stopped on conflicts.
+pub fn has_unmerged_paths(path: &Path) -> bool {
+    git(&["ls-files", "--unmerged"], path).is_ok_and(|out| !out.trim().is_empty())
+}
+
+/// Check whether the worktree at `path` has uncommitted changes to tracked
+/// files (staged or unstaged). Untracked files are ignored.
+pub fn is_dirty(path: &Path) -> Result<bool> {
+    let output = git(&["status", "--porcelain", "--untracked-files=no"], path)?;
+    Ok(!output.is_empty())
+}
+
+/// Local branches whose tip is `commit`.
+pub fn branches_at(repo: &RepoRoot, commit: &str) -> Result<Vec<String>> {
+    let raw = git(
+        &[
+            "for-each-ref",
+            "--points-at",
+            commit,
+            "--format=%(refname:short)",
+            "refs/heads/",
+        ],
+        repo.as_ref(),
+    )?;
+    Ok(raw.lines().map(str::to_string).collect())
+}
+
+/// Full names of the refs under `prefixes` (e.g. `refs/heads/`), sorted.
+pub fn ref_names(repo: &RepoRoot, prefixes: &[&str]) -> Result<Vec<String>> {
+    let mut args = vec!["for-each-ref", "--format=%(refname)"];
+    args.extend_from_slice(prefixes);
+    let raw = git(&args, repo.as_ref())?;
+    Ok(raw.lines().map(str::to_string).collect())
+}
+
+/// Check out `branch` in the worktree at `path`.
+pub fn switch(path: &Path, branch: &str) -> Result<()> {
+    git(&["switch", "--quiet", branch], path)?;
+    Ok(())
+}
+
+/// Whether `rel` (relative to `path`) is ignored by any exclude source.
+pub fn is_ignored(path: &Path, rel: &str) -> bool {
+    git_success(&["check-ignore", "--quiet", "--no-index", rel], path)
+}
+
+/// Resolve a path inside the git directory, e.g. `info/exclude`.
+pub fn git_path(path: &Path, name: &str) -> Result<PathBuf> {
+    let resolved = git(&["rev-parse", "--git-path", name], path)?;
+    Ok(path.join(resolved))
+}
+
+/// Resolve the full commit hash of `HEAD` in the worktree at `path`.
+pub fn head_commit(path: &Path) -> Result<String> {
+    git(&["rev-parse", "HEAD"], path)
+}
+
+/// Pathspec covering the whole worktree except the managed `.worktrees/`
+/// directory, so linked worktrees nested under the main worktree are never
+/// reported or stashed as untracked content.
+const CHANGES_PATHSPEC: [&str; 3] = ["--", ":(top)", ":(top,exclude).worktrees"];
+
+/// List uncommitted changes (staged, unstaged and optionally untracked) in
+/// the worktree at `path`.
+pub fn changed_files(path: &Path, include_untracked: bool) -> Result<Vec<ChangedFile>> {
+    let untracked = if include_untracked {
+        "--untracked-files=all"
+    } else {
+        "--untracked-files=no"
+    };
+    let mut args = vec!["status", "--porcelain", "-z", untracked];
+    args.extend(CHANGES_PATHSPEC);
+    let raw = git_raw(&args, path)?;
+    Ok(parse_status_z(&raw))
+}
+
+/// Parse `git status --porcelain -z` output.
+///
+/// Each record is `XY <path>`; renames and copies are followed by an extra
+/// NUL-separated record holding the original path, which we skip.
+fn parse_status_z(raw: &str) -> Vec<ChangedFile> {
+    let mut files = Vec::new();
+    let mut records = raw.split('\0');
+
+    while let Some(record) = records.next() {
+        if record.len() < 4 {
+            continue;
+        }
+        let (code, path) = record.split_at(3);
+        if code.starts_with('R') || code.starts_with('C') {
+            records.next();
+        }
+        files.push(ChangedFile {
+            path: PathBuf::from(path),
+            untracked: code == "?? ",
+        });
+    }
+
+    files
+}
+
+/// Stash all changes in the worktree at `path` under `message` and return
+/// the stash commit hash.
+pub fn stash_push(path: &Path, message: &str, include_untracked: bool) -> Result<String> {
+    let mut args = vec!["stash", "push", "--message", message];
+    if include_untracked {
+        args.push("--include-untracked");
+    }
+    args.extend(CHANGES_PATHSPEC);
+    git(&args, path)?;
+    git(&["rev-parse", "refs/stash"], path)
+}
+
+/// Apply a stash commit (including its staged state) in the worktree at `path`.
+pub fn stash_apply(path: &Path, stash: &str) -> Result<()> {
+    git(&["stash", "apply", "--index", stash], path)?;
+    Ok(())
+}
+
+/// Find the `stash@{n}` reflog selector for a stash commit hash.
+///
+/// Stashes live in the common dir, so the selector is the same from any
+/// worktree of the repository.
+pub fn stash_selector(path: &Path, stash: &str) -> Option<String> {
+    let list = git(&["stash", "list", "--format=%H"], path).ok()?;
+    list.lines()
+        .position(|hash| hash == stash)
+        .map(|idx| format!("stash@{{{idx}}}"))
+}
+
+/// Drop the stash entry whose commit hash is `stash`.
+pub fn stash_drop(path: &Path, stash: &str) -> Result<()> {
+    let selector = stash_selector(path, stash)
+        .ok_or_else(|| AppError::git(format!("stash {stash} not found")))?;
+    git(&["stash", "drop", &selector], path)?;
+    Ok(())
+}
+
+/// Discard all changes to tracked files in the worktree at `path`.
+pub fn reset_hard(path: &Path) -> Result<()> {
+    git(&["reset", "--hard", "--quiet", "HEAD"], path)?;
+    Ok(())
+}
+
+/// Check whether a remote with the given name is configured.
+pub fn remote_exists(repo: &RepoRoot, remote: &str) -> bool {
+    git_success(&["remote", "get-url", remote], repo.as_ref())
+}
+
+/// Local branches whose configured upstream no longer exists, as
+/// `(branch, upstream)` pairs.
+pub fn gone_upstreams(repo: &RepoRoot) -> Result<Vec<(String, String)>> {
+    let raw = git(
+        &[
+            "for-each-ref",
+            "--format=%(refname:short)%00%(upstream:short)%00%(upstream:track)",
+            "refs/heads/",
+        ],
+        repo.as_ref(),
+    )?;
+    Ok(raw
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let branch = fields.next()?;
+            let upstream = fields.next()?;
+            (fields.next()? == "[gone]").then(|| (branch.to_string(), upstream.to_string()))
+        })
+        .collect())
+}
+
+/// Fetch from `remote`, pruning deleted remote-tracking refs.
+pub fn fetch(repo: &RepoRoot, remote: &str) -> Result<()> {
+    git(&["fetch", "--prune", remote], repo.as_ref())?;
+    Ok(())
+}
+
+/// Fetch a single ref from `remote` and return the fetched commit hash.
+pub fn fetch_ref(repo: &RepoRoot, remote: &str, reference: &str) -> Result<String> {
+    git(&["fetch", "--no-tags", remote, reference], repo.as_ref())?;
+    git(&["rev-parse", "FETCH_HEAD^{commit}"], repo.as_ref())
+}
+
+/// Check whether `remote` still advertises `reference`.
+///
+/// Errors when the remote cannot be queried, so callers can tell a ref
+/// that is gone apart from a remote that is unreachable.
+pub fn remote_ref_exists(repo: &RepoRoot, remote: &str, reference: &str) -> Result<bool> {
+    let output = git(&["ls-remote", remote, reference], repo.as_ref())?;
+    Ok(output
+        .lines()
+        .any(|line| line.split('\t').nth(1) == Some(reference)))
+}
+
+/// Resolve the private git dir of the worktree at `path`.
+///
+/// For linked worktrees this is `<common-dir>/worktrees/<name>`, which Git
+/// deletes together with the worktree.
+pub fn worktree_git_dir(path: &Path) -> Result<PathBuf> {
+    git(&["rev-parse", "--absolute-git-dir"], path).map(PathBuf::from)
+}
+
+/// Whether the worktree at `path` is locked (`git worktree lock`).
+pub fn is_locked(path: &Path) -> bool {
+    worktree_git_dir(path).is_ok_and(|dir| dir.join("locked").exists())
+}
+
+/// The worktree containing a path, as seen by a single `git rev-parse`.
+pub struct HeadInfo {
+    /// Private git directory of the worktree.
+    pub git_dir: PathBuf,
+    /// Abbreviated `HEAD` commit, as in [`list_worktrees`].
+    pub commit: String,
+    /// Branch checked out, or `None` when detached.
+    pub branch: Option<String>,
+}
+
+/// Git dir, `HEAD` commit and branch of the worktree containing `path`.
+///
+/// Fails on an unborn `HEAD`.
+pub fn head_info(path: &Path) -> Result<HeadInfo> {
+    let raw = git(
+        &[
+            "rev-parse",
+            "--absolute-git-dir",
+            "HEAD",
+            "--symbolic-full-name",
+            "HEAD",
+        ],
+        path,
+    )?;
+    let mut lines = raw.lines();
+    let (Some(git_dir), Some(commit), Some(head)) = (lines.next(), lines.next(), lines.next())
+    else {
+        return Err(AppError::git(format!("unexpected rev-parse output: {raw}")));
+    };
+    Ok(HeadInfo {
+        git_dir: PathBuf::from(git_dir),
+        commit: commit[..7.min(commit.len())].to_string(),
+        branch: head.strip_prefix("refs/heads/").map(str::to_string),
+    })
+}
+
+/// Oldest Git release wt is documented to support.
+pub const MIN_GIT_VERSION: (u32, u32) = (2, 39);
+
+/// The installed Git's `(major, minor)` version, if it can be determined.
+pub fn version() -> Option<(u32, u32)> {
+    let raw = git(&["--version"], Path::new(".")).ok()?;
+    parse_version(&raw)
+}
+
+/// Parse `git version 2.43.0` (or vendor suffixes such as
+/// `2.39.3 (Apple Git-146)`) into `(major, minor)`.
+fn parse_version(raw: &str) -> Option<(u32, u32)> {
+    let number = raw
+        .strip_prefix("git version ")?
+        .split_whitespace()
+        .next()?;
+    let mut parts = number.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
 }
 
 /// Verify Git has a usable difftool before launching an interactive diff.
@@ -483,15 +1045,15 @@ Available tools can be inspected with:\n  git difftool --tool-help"
 }
 
 fn configured_difftool(path: &Path) -> Result<Option<String>> {
-    let Some(tool) = git_config_get(path, "diff.tool")? else {
-        return git_config_get(path, "merge.tool");
+    let Some(tool) = config_get(path, "diff.tool")? else {
+        return config_get(path, "merge.tool");
     };
 
     Ok(Some(tool))
 }
 
 fn is_difftool_available(path: &Path, tool: &str) -> Result<bool> {
-    if git_config_get(path, &format!("difftool.{tool}.cmd"))?.is_some() {
+    if config_get(path, &format!("difftool.{tool}.cmd"))?.is_some() {
         return Ok(true);
     }
 
@@ -534,7 +1096,8 @@ fn parse_available_difftools(output: &str) -> HashSet<String> {
     tools
 }
 
-fn git_config_get(path: &Path, key: &str) -> Result<Option<String>> {
+/// Read a git config value, returning `None` when the key is unset or empty.
+pub fn config_get(path: &Path, key: &str) -> Result<Option<String>> {
     let mut cmd = Cmd::new("git");
     cmd.arg("-C").arg(path).arg("config").arg("--get").arg(key);
 
@@ -578,19 +1141,30 @@ fn git_tool_help(path: &Path) -> Result<String> {
 }
 
 /// Run Git's configured difftool for a branch comparison.
-pub fn difftool(repo: &RepoRoot, tool: Option<&str>, range: &str) -> Result<()> {
-    let mut cmd = base_difftool(repo.as_ref(), tool);
+///
+/// With `stdout_to_stderr`, the tool writes to stderr so that it cannot mix
+/// with a path printed on stdout.
+pub fn difftool(
+    repo: &RepoRoot,
+    tool: Option<&str>,
+    range: &str,
+    stdout_to_stderr: bool,
+) -> Result<()> {
+    let mut cmd = base_difftool(repo.as_ref(), tool, stdout_to_stderr);
     cmd.arg(range);
     run_difftool(cmd)
 }
 
 /// Run Git's configured difftool for dirty changes in a linked worktree.
+///
+/// `stdout_to_stderr` is as for [`difftool`].
 pub fn difftool_dirty(
     worktree_path: &Path,
     mode: crate::worktree::DirtyDiffMode,
     tool: Option<&str>,
+    stdout_to_stderr: bool,
 ) -> Result<()> {
-    let mut cmd = base_difftool(worktree_path, tool);
+    let mut cmd = base_difftool(worktree_path, tool, stdout_to_stderr);
 
     match mode {
         crate::worktree::DirtyDiffMode::Dirty => {
@@ -605,13 +1179,18 @@ pub fn difftool_dirty(
     run_difftool(cmd)
 }
 
-fn base_difftool(path: &Path, tool: Option<&str>) -> Cmd {
+fn base_difftool(path: &Path, tool: Option<&str>, stdout_to_stderr: bool) -> Cmd {
+    let stdout = if stdout_to_stderr {
+        Stdio::from(std::io::stderr())
+    } else {
+        Stdio::inherit()
+    };
     let mut cmd = Cmd::new("git");
     cmd.arg("-C")
         .arg(path)
         .arg("difftool")
         .stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit());
 
     if let Some(tool) = tool {
@@ -650,6 +1229,17 @@ pub fn push(repo: &RepoRoot, branch: &str) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_version_variants() {
+        assert_eq!(parse_version("git version 2.43.0"), Some((2, 43)));
+        assert_eq!(
+            parse_version("git version 2.39.3 (Apple Git-146)"),
+            Some((2, 39))
+        );
+        assert_eq!(parse_version("git version 2.45.1.windows.1"), Some((2, 45)));
+        assert_eq!(parse_version("not git"), None);
+    }
+
     #[test]
     fn parse_porcelain_basic() {
         // repo path intentionally differs from the worktree path to prove
@@ -760,6 +1350,25 @@ Some of the tools listed above only work in a windowed environment.\n";
         assert!(!tools.contains("Some"));
     }
 
+    #[test]
+    fn parse_status_z_handles_renames_and_untracked() {
+        let raw = " M src/lib.rs\0R  new.rs\0old.rs\0A  added file.txt\0?? notes.md\0";
+        let files = parse_status_z(raw);
+
+        let paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("new.rs"),
+                PathBuf::from("added file.txt"),
+                PathBuf::from("notes.md"),
+            ]
+        );
+        assert!(!files[0].untracked);
+        assert!(files[3].untracked);
+    }
+
     #[test]
     fn classify_not_a_repo() {
         let err = classify_git_error(
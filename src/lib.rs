@@ -0,0 +1,50 @@
+//! Library API for wt-core, the portable Git worktree lifecycle manager.
+//!
+//! The `wt-core` binary is a thin command-line layer over this crate. Tools
+//! that would otherwise shell out and parse `--json` output can call the same
+//! operations directly:
+//!
+//! ```no_run
+//! use wt_core::domain::BranchName;
+//! use wt_core::worktree::{self, AddOptions};
+//!
+//! # fn main() -> wt_core::error::Result<()> {
+//! let repo = worktree::repo_root(std::path::Path::new("."))?;
+//! let added = worktree::add(&repo, &BranchName::new("feature/auth"), &AddOptions::default())?;
+//! println!("created {}", added.worktree_path.display());
+//!
+//! for diag in worktree::doctor(&repo)? {
+//!     println!("{:?} {}", diag.level, diag.message);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The modules below are the stable surface and follow semantic versioning.
+//! Nothing here depends on the `interactive` feature, which only adds the
+//! terminal picker to the binary.
+
+#![warn(missing_docs)]
+
+/// Pluggable Git access for `merge`, `remove` and `prune_*`.
+///
+/// [`backend::ProcessGit`], the default, runs the `git` executable; the
+/// `*_with` variants in [`worktree`] accept any [`backend::GitBackend`].
+pub mod backend;
+/// Helpers for the `wt-core` binary: tmux sessions, navigation history,
+/// completion and the ui's difftool calls. Not part of the stable API.
+#[doc(hidden)]
+pub mod cli_support;
+/// Core value types: repository roots, branch names, worktrees.
+pub mod domain;
+/// The error type and its stable exit codes.
+pub mod error;
+/// Shared entries from `.wt/symlinks` and their application to worktrees.
+pub mod symlinks;
+/// Worktree lifecycle operations and their result types.
+pub mod worktree;
+
+mod git;
+mod metadata;
+mod sparse;
+mod tmux;
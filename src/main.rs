@@ -1,11 +1,9 @@
 mod cli;
 mod commands;
-mod domain;
-mod error;
-mod git;
+mod completions;
 mod output;
-mod symlinks;
-mod worktree;
+#[cfg(feature = "interactive")]
+mod ui;
 
 use std::process;
 
@@ -0,0 +1,288 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+use crate::git;
+
+/// File name of the metadata record inside a worktree's private git dir.
+///
+/// Living in `<common-dir>/worktrees/<name>/` means Git removes the record
+/// together with the worktree, so it never goes stale.
+const META_FILE: &str = "wt.json";
+
+/// Why a worktree was created, when it is not an ordinary branch worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorktreeKind {
+    /// Throwaway worktree for reviewing a pull request.
+    Review,
+    /// Intentionally detached worktree (`add --detach`).
+    Detached,
+    /// Throwaway detached worktree (`scratch`).
+    Scratch,
+}
+
+impl WorktreeKind {
+    /// Whether a worktree of this kind is expected to have no branch.
+    pub fn is_detached(self) -> bool {
+        matches!(self, WorktreeKind::Detached | WorktreeKind::Scratch)
+    }
+}
+
+/// Metadata recorded by wt-core for a linked worktree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeMeta {
+    pub kind: WorktreeKind,
+    /// Unix timestamp (seconds) when the worktree was created.
+    pub created_at: u64,
+    /// Pull request number (review worktrees).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr: Option<u64>,
+    /// Remote the worktree was fetched from (review worktrees).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    /// Remote ref the worktree was fetched from (review worktrees).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_ref: Option<String>,
+}
+
+impl WorktreeMeta {
+    /// Metadata for a worktree of `kind` created now.
+    pub fn new(kind: WorktreeKind) -> Self {
+        Self {
+            kind,
+            created_at: now(),
+            pr: None,
+            remote: None,
+            remote_ref: None,
+        }
+    }
+
+    /// Metadata for a review worktree of pull request `pr`.
+    pub fn review(pr: u64, remote: impl Into<String>, remote_ref: impl Into<String>) -> Self {
+        Self {
+            pr: Some(pr),
+            remote: Some(remote.into()),
+            remote_ref: Some(remote_ref.into()),
+            ..Self::new(WorktreeKind::Review)
+        }
+    }
+}
+
+fn meta_path(worktree: &Path) -> Result<PathBuf> {
+    Ok(git::worktree_git_dir(worktree)?.join(META_FILE))
+}
+
+/// Read the metadata of the worktree at `worktree`, if any was recorded.
+pub fn read(worktree: &Path) -> Option<WorktreeMeta> {
+    let content = fs::read_to_string(meta_path(worktree).ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Record metadata for the linked worktree at `worktree`.
+pub fn write(worktree: &Path, meta: &WorktreeMeta) -> Result<()> {
+    let path = meta_path(worktree)?;
+    let json =
+        serde_json::to_string(meta).map_err(|e| AppError::invariant(format!("json error: {e}")))?;
+    fs::write(&path, json)
+        .map_err(|e| AppError::git(format!("failed to write {}: {e}", path.display())))
+}
+
+/// File name of the prompt status cache inside a worktree's git dir.
+const PROMPT_CACHE_FILE: &str = "wt-prompt.json";
+
+/// Prompt stats cached between prompt renders.
+///
+/// An entry only applies while `stamp` matches the worktree's current state
+/// and it is younger than the caller's TTL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptCache {
+    pub stamp: PromptStamp,
+    /// Unix timestamp (seconds) when the entry was written.
+    pub written_at: u64,
+    pub ahead: u32,
+    pub behind: u32,
+    pub dirty: bool,
+}
+
+/// What a cached prompt entry was computed against.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptStamp {
+    pub commit: String,
+    /// Modification times (nanoseconds since the epoch, 0 if missing) of
+    /// the worktree's `HEAD` and `index` files.
+    pub head_mtime: u128,
+    pub index_mtime: u128,
+}
+
+impl PromptStamp {
+    /// Stamp the worktree whose private git dir is `git_dir`, at `commit`.
+    pub fn read(git_dir: &Path, commit: &str) -> Self {
+        let mtime = |name: &str| {
+            fs::metadata(git_dir.join(name))
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos())
+        };
+        Self {
+            commit: commit.to_string(),
+            head_mtime: mtime("HEAD"),
+            index_mtime: mtime("index"),
+        }
+    }
+}
+
+/// Cached prompt stats in `git_dir` for `stamp`, if fresher than `ttl` seconds.
+pub fn read_prompt_cache(git_dir: &Path, stamp: &PromptStamp, ttl: u64) -> Option<PromptCache> {
+    let content = fs::read_to_string(git_dir.join(PROMPT_CACHE_FILE)).ok()?;
+    let cache: PromptCache = serde_json::from_str(&content).ok()?;
+    (cache.stamp == *stamp && now().saturating_sub(cache.written_at) < ttl).then_some(cache)
+}
+
+/// Store prompt stats in `git_dir`. Best-effort: a prompt never fails
+/// because its cache could not be written.
+pub fn write_prompt_cache(git_dir: &Path, cache: &PromptCache) {
+    if let Ok(json) = serde_json::to_string(cache) {
+        let _ = fs::write(git_dir.join(PROMPT_CACHE_FILE), json);
+    }
+}
+
+/// File name of the navigation history inside the git common dir.
+const RECENT_FILE: &str = "wt-recent.json";
+
+/// How many worktrees the navigation history remembers.
+const RECENT_LIMIT: usize = 32;
+
+/// One entry of the navigation history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Visit {
+    pub path: PathBuf,
+    /// Unix timestamp (seconds) of the last visit.
+    pub visited_at: u64,
+}
+
+/// The history lives in the main worktree's git dir, which is the common
+/// dir, so every worktree of the repository shares it.
+///
+/// That is `<repo>/.git` unless it is a gitfile, so Git is only asked in
+/// that case; `go` and `list` stay off the subprocess path.
+fn recent_path(repo: &Path) -> Result<PathBuf> {
+    let git_dir = repo.join(".git");
+    if git_dir.is_dir() {
+        return Ok(git_dir.join(RECENT_FILE));
+    }
+    git::git_path(repo, RECENT_FILE)
+}
+
+/// Visited worktrees of the repository rooted at `repo`, most recent first.
+pub fn read_visits(repo: &Path) -> Vec<Visit> {
+    recent_path(repo)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_visits(repo: &Path, visits: &[Visit]) -> Result<()> {
+    let path = recent_path(repo)?;
+    let json = serde_json::to_string(visits)
+        .map_err(|e| AppError::invariant(format!("json error: {e}")))?;
+    fs::write(&path, json)
+        .map_err(|e| AppError::git(format!("failed to write {}: {e}", path.display())))
+}
+
+/// Move `paths` to the front of the history, the last one ending up first.
+pub fn record_visits(repo: &Path, paths: &[&Path]) -> Result<()> {
+    let mut visits = read_visits(repo);
+    let visited_at = now();
+    for path in paths {
+        visits.retain(|v| v.path != *path);
+        visits.insert(
+            0,
+            Visit {
+                path: path.to_path_buf(),
+                visited_at,
+            },
+        );
+    }
+    visits.truncate(RECENT_LIMIT);
+    write_visits(repo, &visits)
+}
+
+/// Drop `paths` from the history.
+pub fn forget_visits(repo: &Path, paths: &[&Path]) -> Result<()> {
+    let mut visits = read_visits(repo);
+    let before = visits.len();
+    visits.retain(|v| !paths.contains(&v.path.as_path()));
+    if visits.len() == before {
+        return Ok(());
+    }
+    write_visits(repo, &visits)
+}
+
+/// Current Unix time in seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Parse a TTL such as `90m`, `72h`, `14d` or `2w` into seconds.
+///
+/// A bare number is taken as seconds.
+pub fn parse_ttl(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let amount: u64 = digits.parse().ok()?;
+    let scale = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    amount.checked_mul(scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ttl_units() {
+        assert_eq!(parse_ttl("30"), Some(30));
+        assert_eq!(parse_ttl("30s"), Some(30));
+        assert_eq!(parse_ttl("90m"), Some(90 * 60));
+        assert_eq!(parse_ttl("72h"), Some(72 * 3600));
+        assert_eq!(parse_ttl("14d"), Some(14 * 86400));
+        assert_eq!(parse_ttl("2w"), Some(14 * 86400));
+    }
+
+    #[test]
+    fn parse_ttl_rejects_garbage() {
+        assert_eq!(parse_ttl(""), None);
+        assert_eq!(parse_ttl("d"), None);
+        assert_eq!(parse_ttl("3 days"), None);
+        assert_eq!(parse_ttl("-1d"), None);
+    }
+
+    #[test]
+    fn meta_round_trips_without_optional_fields() {
+        let meta = WorktreeMeta::review(7, "origin", "refs/pull/7/head");
+        let json = serde_json::to_string(&meta).expect("serialize");
+        assert_eq!(serde_json::from_str::<WorktreeMeta>(&json).ok(), Some(meta));
+
+        let minimal: WorktreeMeta =
+            serde_json::from_str(r#"{"kind":"review","created_at":1}"#).expect("deserialize");
+        assert_eq!(minimal.pr, None);
+    }
+}
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use serde::Serialize;
 
-use crate::domain::{Worktree, WorktreeStatsStatus};
+use wt_core::domain::{Worktree, WorktreeStatsStatus};
 
 /// Output format for commands that produce a navigable path (add, go).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,12 +48,45 @@ pub struct JsonResponse {
     pub removed_path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branch: Option<String>,
+    /// Checked-out commit (only set for detached worktrees).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
     /// Whether the branch tracks a remote branch (only set for `add`).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tracking: Option<bool>,
     /// Symlinks created during `add` (only set when config exists).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub symlinks: Option<Vec<String>>,
+    /// Per-entry link modes for `symlinks` (only set when config exists).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<JsonLinkEntry>>,
+    /// Sparse-checkout patterns applied during `add` (only set when sparse).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sparse: Option<Vec<String>>,
+    /// Worktree changes were moved out of (only set for `move-changes`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<String>,
+    /// Files moved by `move-changes` or `add --carry`, relative to the worktree root.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<String>>,
+    /// Submodule and LFS setup steps run by `add` (only set when any applied).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<JsonSetupStep>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonLinkEntry {
+    pub path: String,
+    pub mode: wt_core::symlinks::LinkMode,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonSetupStep {
+    pub step: wt_core::worktree::SetupStepKind,
+    pub status: wt_core::worktree::SetupStepStatus,
+    pub detail: String,
 }
 
 impl JsonResponse {
@@ -67,8 +100,15 @@ impl JsonResponse {
             cd_path: None,
             removed_path: None,
             branch: None,
+            commit: None,
             tracking: None,
             symlinks: None,
+            links: None,
+            sparse: None,
+            source_path: None,
+            files: None,
+            steps: None,
+            warnings: None,
         }
     }
 
@@ -97,6 +137,11 @@ impl JsonResponse {
         self
     }
 
+    pub fn with_commit(mut self, commit: impl Into<String>) -> Self {
+        self.commit = Some(commit.into());
+        self
+    }
+
     pub fn with_tracking(mut self, tracking: bool) -> Self {
         self.tracking = Some(tracking);
         self
@@ -109,6 +154,61 @@ impl JsonResponse {
         self
     }
 
+    pub fn with_links(mut self, links: &[wt_core::symlinks::LinkedEntry]) -> Self {
+        if !links.is_empty() {
+            self.links = Some(
+                links
+                    .iter()
+                    .map(|l| JsonLinkEntry {
+                        path: l.path.display().to_string(),
+                        mode: l.mode,
+                    })
+                    .collect(),
+            );
+        }
+        self
+    }
+
+    pub fn with_sparse(mut self, patterns: Vec<String>) -> Self {
+        if !patterns.is_empty() {
+            self.sparse = Some(patterns);
+        }
+        self
+    }
+
+    pub fn with_source_path(mut self, path: impl Into<String>) -> Self {
+        self.source_path = Some(path.into());
+        self
+    }
+
+    pub fn with_files(mut self, files: Vec<String>) -> Self {
+        self.files = Some(files);
+        self
+    }
+
+    pub fn with_steps(mut self, steps: &[wt_core::worktree::SetupStep]) -> Self {
+        if !steps.is_empty() {
+            self.steps = Some(
+                steps
+                    .iter()
+                    .map(|s| JsonSetupStep {
+                        step: s.kind,
+                        status: s.status,
+                        detail: s.detail.clone(),
+                    })
+                    .collect(),
+            );
+        }
+        self
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        if !warnings.is_empty() {
+            self.warnings = Some(warnings);
+        }
+        self
+    }
+
     pub fn with_event(mut self, event: impl Into<String>) -> Self {
         self.event = Some(event.into());
         self
@@ -129,6 +229,10 @@ pub struct JsonWorktreeEntry {
     pub commit: String,
     pub is_main: bool,
     pub is_current: bool,
+    pub sparse: bool,
+    /// Name of the worktree's tmux session, when one is running.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmux_session: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stats: Option<JsonWorktreeStats>,
 }
@@ -193,6 +297,8 @@ impl JsonListResponse {
                 commit: wt.commit.clone(),
                 is_main: wt.is_main,
                 is_current: current_idx == Some(i),
+                sparse: false,
+                tmux_session: None,
                 stats: None,
             })
             .collect();
@@ -221,6 +327,8 @@ impl JsonListResponse {
                 commit: wt.commit.clone(),
                 is_main: wt.is_main,
                 is_current: current_idx == Some(i),
+                sparse: false,
+                tmux_session: None,
                 stats: Some(JsonWorktreeStats::from_status(stat)),
             })
             .collect();
@@ -230,6 +338,22 @@ impl JsonListResponse {
             worktrees: entries,
         }
     }
+
+    /// Mark sparse worktrees; `sparse` is parallel to the listed worktrees.
+    pub fn with_sparse(mut self, sparse: &[bool]) -> Self {
+        for (entry, &is_sparse) in self.worktrees.iter_mut().zip(sparse) {
+            entry.sparse = is_sparse;
+        }
+        self
+    }
+
+    /// Attach tmux session names; `sessions` is parallel to the listed worktrees.
+    pub fn with_tmux_sessions(mut self, sessions: &[Option<String>]) -> Self {
+        for (entry, session) in self.worktrees.iter_mut().zip(sessions) {
+            entry.tmux_session.clone_from(session);
+        }
+        self
+    }
 }
 
 /// Find the index of the worktree whose path is the longest prefix of `cwd`.
@@ -253,31 +377,71 @@ pub fn find_current_worktree(worktrees: &[Worktree], cwd: &Path) -> Option<usize
 #[derive(Debug, Serialize)]
 pub struct JsonDoctorResponse {
     pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    /// Remediations attempted by `--fix` (absent without `--fix`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixes: Option<Vec<JsonDoctorFix>>,
     pub diagnostics: Vec<JsonDiagEntry>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct JsonDiagEntry {
-    pub level: crate::worktree::DiagLevel,
+    pub level: wt_core::worktree::DiagLevel,
+    pub code: wt_core::worktree::DiagCode,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+    pub fixable: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonDoctorFix {
+    pub code: wt_core::worktree::DiagCode,
+    pub message: String,
+    pub ok: bool,
 }
 
 impl JsonDoctorResponse {
-    pub fn from_diagnostics(diags: &[crate::worktree::Diagnostic]) -> Self {
+    pub fn from_diagnostics(diags: &[wt_core::worktree::Diagnostic]) -> Self {
         let has_errors = diags
             .iter()
-            .any(|d| d.level == crate::worktree::DiagLevel::Error);
+            .any(|d| d.level == wt_core::worktree::DiagLevel::Error);
         Self {
             ok: !has_errors,
+            dry_run: None,
+            fixes: None,
             diagnostics: diags
                 .iter()
                 .map(|d| JsonDiagEntry {
                     level: d.level,
+                    code: d.code,
                     message: d.message.clone(),
+                    path: d.path.as_ref().map(|p| p.display().to_string()),
+                    fix: d.fix.clone(),
+                    fixable: d.fixable,
                 })
                 .collect(),
         }
     }
+
+    pub fn with_fixes(mut self, fixes: &[wt_core::worktree::FixOutcome], dry_run: bool) -> Self {
+        self.ok = self.ok && fixes.iter().all(|f| f.ok);
+        self.dry_run = Some(dry_run);
+        self.fixes = Some(
+            fixes
+                .iter()
+                .map(|f| JsonDoctorFix {
+                    code: f.code,
+                    message: f.message.clone(),
+                    ok: f.ok,
+                })
+                .collect(),
+        );
+        self
+    }
 }
 
 /// Output format for the prune command.
@@ -304,6 +468,9 @@ pub struct JsonPruneDryRunEntry {
     pub status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub method: Option<String>,
+    /// Why a review worktree expired (`ref_gone`, `ttl_elapsed`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
     pub path: String,
 }
 
@@ -319,7 +486,7 @@ pub struct JsonPruneExecuteResponse {
 
 #[derive(Debug, Serialize)]
 pub struct JsonPrunedEntry {
-    pub branch: String,
+    pub branch: Option<String>,
     pub path: String,
 }
 
@@ -330,6 +497,125 @@ pub struct JsonSkippedEntry {
     pub path: String,
 }
 
+/// JSON response for sync. Shares the skipped/warnings shape of prune execute.
+#[derive(Debug, Serialize)]
+pub struct JsonSyncResponse {
+    pub ok: bool,
+    pub mainline: String,
+    pub strategy: wt_core::worktree::SyncStrategy,
+    pub synced: Vec<JsonSyncedEntry>,
+    pub skipped: Vec<JsonSkippedEntry>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonSyncedEntry {
+    pub branch: String,
+    pub path: String,
+}
+
+/// JSON response for `wt link`.
+#[derive(Debug, Serialize)]
+pub struct JsonLinkResponse {
+    pub ok: bool,
+    pub dry_run: bool,
+    pub worktrees: Vec<JsonLinkWorktree>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonLinkWorktree {
+    pub branch: Option<String>,
+    pub path: String,
+    pub links: Vec<JsonLinkOutcome>,
+}
+
+/// A single `wt link` outcome: `created`, `repaired`, `removed` or `skipped`.
+#[derive(Debug, Serialize)]
+pub struct JsonLinkOutcome {
+    pub path: String,
+    pub action: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<wt_core::symlinks::LinkMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl From<&wt_core::symlinks::SymlinkOutcome> for JsonLinkOutcome {
+    fn from(outcome: &wt_core::symlinks::SymlinkOutcome) -> Self {
+        use wt_core::symlinks::SymlinkOutcome;
+
+        let (path, action, mode, reason) = match outcome {
+            SymlinkOutcome::Created(path, mode) => (path, "created", Some(*mode), None),
+            SymlinkOutcome::Repaired(path, mode) => (path, "repaired", Some(*mode), None),
+            SymlinkOutcome::Removed(path) => (path, "removed", None, None),
+            SymlinkOutcome::Skipped(path, reason) => (path, "skipped", None, Some(reason.clone())),
+        };
+        Self {
+            path: path.display().to_string(),
+            action,
+            mode,
+            reason,
+        }
+    }
+}
+
+/// JSON response for `wt adopt`.
+#[derive(Debug, Serialize)]
+pub struct JsonAdoptResponse {
+    pub ok: bool,
+    pub dry_run: bool,
+    pub worktrees: Vec<JsonAdoptWorktree>,
+}
+
+/// A single adopted worktree: `status` is `moved`, `unchanged` or `failed`.
+#[derive(Debug, Serialize)]
+pub struct JsonAdoptWorktree {
+    pub branch: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub links: Vec<JsonLinkOutcome>,
+}
+
+impl From<&wt_core::worktree::AdoptReport> for JsonAdoptWorktree {
+    fn from(report: &wt_core::worktree::AdoptReport) -> Self {
+        use wt_core::worktree::AdoptStatus;
+
+        let (status, reason) = match &report.status {
+            AdoptStatus::Moved => ("moved", None),
+            AdoptStatus::Unchanged => ("unchanged", None),
+            AdoptStatus::Failed(reason) => ("failed", Some(reason.clone())),
+        };
+        Self {
+            branch: report.branch.clone(),
+            from: report.from.display().to_string(),
+            to: report.to.display().to_string(),
+            status,
+            reason,
+            links: report.links.iter().map(JsonLinkOutcome::from).collect(),
+        }
+    }
+}
+
+/// JSON response for the recent command.
+#[derive(Debug, Serialize)]
+pub struct JsonRecentResponse {
+    pub ok: bool,
+    pub worktrees: Vec<JsonRecentEntry>,
+}
+
+/// A visited worktree, most recent first in [`JsonRecentResponse`].
+#[derive(Debug, Serialize)]
+pub struct JsonRecentEntry {
+    pub path: String,
+    pub branch: Option<String>,
+    /// Unix timestamp (seconds) of the last visit.
+    pub visited_at: u64,
+    pub is_current: bool,
+}
+
 /// Output format for the merge command.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MergeFormat {
@@ -356,6 +642,16 @@ pub struct JsonMergeResponse {
     pub pushed: bool,
 }
 
+/// JSON response for the sparse command.
+#[derive(Debug, Serialize)]
+pub struct JsonSparseResponse {
+    pub ok: bool,
+    pub branch: String,
+    pub worktree_path: String,
+    pub sparse: bool,
+    pub patterns: Vec<String>,
+}
+
 /// JSON response for the setup command.
 #[derive(Debug, Serialize)]
 pub struct JsonSetupResponse {
@@ -366,11 +662,11 @@ pub struct JsonSetupResponse {
 }
 
 /// Serialize a value as a compact single-line JSON object to stdout.
-pub fn print_json(value: &impl Serialize) -> crate::error::Result<()> {
+pub fn print_json(value: &impl Serialize) -> wt_core::error::Result<()> {
     println!(
         "{}",
         serde_json::to_string(value)
-            .map_err(|e| crate::error::AppError::invariant(format!("json error: {e}")))?
+            .map_err(|e| wt_core::error::AppError::invariant(format!("json error: {e}")))?
     );
     Ok(())
 }
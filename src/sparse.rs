@@ -0,0 +1,84 @@
+use std::fs;
+
+use crate::domain::{BranchName, RepoRoot};
+
+const CONFIG_DIR: &str = ".wt";
+const CONFIG_FILE: &str = "sparse";
+
+/// Resolve the sparse-checkout patterns configured for `branch` in
+/// `.wt/sparse`.
+///
+/// Returns an empty vec when the file is missing or no patterns apply, in
+/// which case the worktree gets a full checkout.
+pub fn load_patterns(repo: &RepoRoot, branch: &BranchName) -> Vec<String> {
+    let path = repo.as_ref().join(CONFIG_DIR).join(CONFIG_FILE);
+    fs::read_to_string(path)
+        .map(|content| parse_config(&content, branch.as_str()))
+        .unwrap_or_default()
+}
+
+/// Parse `.wt/sparse` content for `branch`.
+///
+/// Patterns before the first section apply to every branch. A `[prefix]`
+/// section adds its patterns for branches starting with `prefix`. Blank
+/// lines and `#` comments are ignored; duplicates are dropped.
+fn parse_config(content: &str, branch: &str) -> Vec<String> {
+    let mut patterns: Vec<String> = Vec::new();
+    let mut active = true;
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(prefix) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            active = branch.starts_with(prefix.trim());
+            continue;
+        }
+        if active && !patterns.iter().any(|p| p == line) {
+            patterns.push(line.to_string());
+        }
+    }
+
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "\
+# shared tooling for everyone
+tools
+
+[feature/web-]
+web
+docs
+
+[feature/api-]
+services/api
+tools
+";
+
+    #[test]
+    fn top_level_patterns_apply_to_every_branch() {
+        assert_eq!(parse_config(CONFIG, "bugfix/x"), vec!["tools"]);
+    }
+
+    #[test]
+    fn prefix_sections_add_patterns() {
+        assert_eq!(
+            parse_config(CONFIG, "feature/web-login"),
+            vec!["tools", "web", "docs"]
+        );
+        assert_eq!(
+            parse_config(CONFIG, "feature/api-auth"),
+            vec!["tools", "services/api"]
+        );
+    }
+
+    #[test]
+    fn sections_only_config_leaves_other_branches_full() {
+        let config = "[feature/web-]\nweb\n";
+        assert!(parse_config(config, "main").is_empty());
+    }
+}
@@ -4,11 +4,73 @@ use std::io;
 use std::path::{Component, Path, PathBuf};
 
 use crate::domain::RepoRoot;
+use crate::git;
 
 const CONFIG_DIR: &str = ".wt";
 const CONFIG_FILE: &str = "symlinks";
 const CONFIG_LOCAL_FILE: &str = "symlinks.local";
 
+/// How a configured entry is materialized in a new worktree.
+///
+/// Selected per entry with a `<mode>:` prefix in `.wt/symlinks`, e.g.
+/// `copy: .env.local`. Entries without a prefix are symlinked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    /// Relative symlink back into the main worktree.
+    #[default]
+    Symlink,
+    /// Independent copy.
+    Copy,
+    /// Hard links to the main worktree's files (directories are recreated).
+    Hardlink,
+    /// Copy-on-write clone, falling back to a copy where unsupported.
+    Reflink,
+}
+
+impl LinkMode {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "symlink" => Some(LinkMode::Symlink),
+            "copy" => Some(LinkMode::Copy),
+            "hardlink" => Some(LinkMode::Hardlink),
+            "reflink" => Some(LinkMode::Reflink),
+            _ => None,
+        }
+    }
+
+    /// Config prefix naming this mode.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LinkMode::Symlink => "symlink",
+            LinkMode::Copy => "copy",
+            LinkMode::Hardlink => "hardlink",
+            LinkMode::Reflink => "reflink",
+        }
+    }
+
+    /// Past-tense verb for human output, e.g. `Copied .env`.
+    pub fn verb(self) -> &'static str {
+        match self {
+            LinkMode::Symlink => "Symlinked",
+            LinkMode::Copy => "Copied",
+            LinkMode::Hardlink => "Hardlinked",
+            LinkMode::Reflink => "Reflinked",
+        }
+    }
+}
+
+/// Split an optional `<mode>:` prefix off a config entry.
+///
+/// Only known mode names count as a prefix, so paths containing `:` are
+/// left intact.
+fn split_mode(entry: &str) -> (LinkMode, &str) {
+    entry
+        .split_once(':')
+        .and_then(|(prefix, rest)| Some((LinkMode::from_prefix(prefix.trim())?, rest.trim())))
+        .unwrap_or((LinkMode::Symlink, entry))
+}
+
 /// Read and merge symlink config from `.wt/symlinks` and `.wt/symlinks.local`.
 ///
 /// Returns an empty vec when neither file exists.
@@ -46,122 +108,224 @@ fn parse_config_file(path: &Path) -> Vec<String> {
         .collect()
 }
 
-/// Expand a single config entry (possibly containing `*` globs) into concrete
-/// paths relative to the repo root.
-///
-/// Only single-level globs are supported: `*` matches within one path segment.
-/// Dispatch a single path segment: standalone wildcard, filename glob, or literal.
-fn expand_segment(repo_root: &Path, candidates: Vec<PathBuf>, part: &str) -> Vec<PathBuf> {
-    if part == "*" {
-        return expand_wildcard_segment(repo_root, &candidates);
-    }
-    if part.contains('*') {
-        return expand_glob_segment(repo_root, &candidates, part);
-    }
-    candidates
-        .into_iter()
-        .map(|mut c| {
-            c.push(part);
-            c
-        })
-        .collect()
+/// Maximum number of directory levels walked below the literal prefix of a
+/// glob pattern. Keeps `**` expansion bounded in very deep trees.
+const MAX_GLOB_DEPTH: usize = 12;
+
+/// Directories never entered while expanding globs: Git's own data and the
+/// managed worktrees, which would otherwise match as copies of the repo.
+const PRUNED_DIRS: &[&str] = &[".git", ".worktrees"];
+
+fn has_glob_meta(segment: &str) -> bool {
+    segment.contains(['*', '?', '['])
 }
 
+/// Expand a single config entry into concrete paths relative to the repo
+/// root.
+///
+/// Supports `*`, `?` and `[abc]` within a segment and `**` for any number of
+/// directories. Leading literal segments are joined directly; the rest of
+/// the pattern is matched while walking the tree. A matching directory is
+/// not descended into, since linking it already covers its contents.
 fn expand_entry(repo_root: &Path, pattern: &str) -> Vec<PathBuf> {
-    if !pattern.contains('*') {
+    let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let literal = segments
+        .iter()
+        .take_while(|segment| !has_glob_meta(segment))
+        .count();
+    if literal == segments.len() {
         return vec![PathBuf::from(pattern)];
     }
 
-    let parts: Vec<&str> = pattern.split('/').collect();
-    let mut candidates = vec![PathBuf::new()];
+    let base: PathBuf = segments[..literal].iter().collect();
+    let mut expanded = Vec::new();
+    walk_matches(
+        repo_root,
+        &base,
+        &mut Vec::new(),
+        &segments[literal..],
+        &mut expanded,
+    );
+    expanded
+}
 
-    for part in &parts {
-        candidates = expand_segment(repo_root, candidates, part);
-    }
+/// Recursively collect paths under `base` whose segments (relative to
+/// `base`) match `pattern`.
+fn walk_matches(
+    repo_root: &Path,
+    base: &Path,
+    rel: &mut Vec<String>,
+    pattern: &[&str],
+    out: &mut Vec<PathBuf>,
+) {
+    let dir = repo_root.join(base).join(rel.iter().collect::<PathBuf>());
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
 
-    candidates
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if PRUNED_DIRS.contains(&name.as_str()) {
+            continue;
+        }
+        rel.push(name);
+        if segments_match(pattern, rel, false) {
+            out.push(base.join(rel.iter().collect::<PathBuf>()));
+        } else if rel.len() < MAX_GLOB_DEPTH
+            && entry.file_type().is_ok_and(|t| t.is_dir())
+            && segments_match(pattern, rel, true)
+        {
+            // `file_type` does not follow symlinks, so linked directories
+            // (and any cycles through them) are never walked.
+            walk_matches(repo_root, base, rel, pattern, out);
+        }
+        rel.pop();
+    }
 }
 
-/// Match a glob pattern against a filename.
+/// Match path segments against pattern segments, where `**` matches zero or
+/// more whole segments.
 ///
-/// Supports `*` as a wildcard that matches any sequence of characters.
-/// Only single `*` within the pattern is supported (e.g. `.env.*`, `*.txt`).
-fn glob_matches(pattern: &str, name: &str) -> bool {
-    match pattern.split_once('*') {
-        Some((prefix, suffix)) => {
-            name.len() >= prefix.len() + suffix.len()
-                && name.starts_with(prefix)
-                && name.ends_with(suffix)
-        }
-        None => pattern == name,
+/// With `partial`, succeed when `path` could be a proper prefix of a match,
+/// i.e. it is worth descending into.
+fn segments_match(pattern: &[&str], path: &[String], partial: bool) -> bool {
+    let Some((first, rest)) = pattern.split_first() else {
+        return path.is_empty() && !partial;
+    };
+    let Some((head, tail)) = path.split_first() else {
+        return partial || pattern.iter().all(|segment| *segment == "**");
+    };
+    if *first == "**" {
+        return segments_match(rest, path, partial) || segments_match(pattern, tail, partial);
     }
+    glob_matches(first, head) && segments_match(rest, tail, partial)
 }
 
-/// For each accumulated prefix, read the directory and keep entries matching
-/// a filename glob pattern (e.g. `.env.*`).
-fn expand_glob_segment(repo_root: &Path, prefixes: &[PathBuf], pattern: &str) -> Vec<PathBuf> {
-    let mut expanded = Vec::new();
-
-    for prefix in prefixes {
-        let abs_dir = repo_root.join(prefix);
-        let entries = match fs::read_dir(&abs_dir) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
+/// Match a glob pattern against a single path segment.
+///
+/// Supports `*` (any run of characters), `?` (one character) and bracket
+/// classes such as `[abc]`, `[a-z]` and `[!abc]`.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    glob_matches_chars(&pattern, &name)
+}
 
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-            if glob_matches(pattern, &name_str) {
-                expanded.push(prefix.join(&*name_str));
+fn glob_matches_chars(pattern: &[char], name: &[char]) -> bool {
+    let Some((&first, rest)) = pattern.split_first() else {
+        return name.is_empty();
+    };
+    match first {
+        '*' => (0..=name.len()).any(|skip| glob_matches_chars(rest, &name[skip..])),
+        '?' => !name.is_empty() && glob_matches_chars(rest, &name[1..]),
+        '[' => match (parse_class(rest), name.split_first()) {
+            (Some((matches, after)), Some((&c, name_rest))) => {
+                matches(c) && glob_matches_chars(after, name_rest)
             }
-        }
+            // An unterminated `[` is matched literally.
+            (None, Some((&c, name_rest))) => c == '[' && glob_matches_chars(rest, name_rest),
+            (_, None) => false,
+        },
+        c => name.first() == Some(&c) && glob_matches_chars(rest, &name[1..]),
     }
-
-    expanded
 }
 
-/// For each accumulated prefix, read the corresponding directory in the main
-/// worktree and replace with all child entries.
-fn expand_wildcard_segment(repo_root: &Path, prefixes: &[PathBuf]) -> Vec<PathBuf> {
-    let mut expanded = Vec::new();
-
-    for prefix in prefixes {
-        let abs_dir = repo_root.join(prefix);
-        let entries = match fs::read_dir(&abs_dir) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-            if name_str == ".git" {
-                continue;
+/// Parse a bracket class following `[`, returning a predicate and the
+/// pattern remaining after the closing `]`.
+fn parse_class(pattern: &[char]) -> Option<(impl Fn(char) -> bool + '_, &[char])> {
+    let (negated, body) = match pattern.first() {
+        Some('!' | '^') => (true, &pattern[1..]),
+        _ => (false, pattern),
+    };
+    // A `]` right after the opening bracket is a literal member.
+    let close = body.iter().skip(1).position(|&c| c == ']')? + 1;
+    let members = &body[..close];
+    let matches = move |c: char| {
+        let mut i = 0;
+        let mut found = false;
+        while i < members.len() {
+            if i + 2 < members.len() && members[i + 1] == '-' {
+                found |= (members[i]..=members[i + 2]).contains(&c);
+                i += 3;
+            } else {
+                found |= members[i] == c;
+                i += 1;
             }
-            expanded.push(prefix.join(&*name_str));
         }
-    }
+        found != negated
+    };
+    Some((matches, &body[close + 1..]))
+}
 
-    expanded
+/// Whether `rel_path` matches an exclusion pattern or lies under a path
+/// that does.
+fn is_excluded(rel_path: &Path, exclusions: &[Vec<&str>]) -> bool {
+    let segments: Vec<String> = rel_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    exclusions.iter().any(|pattern| {
+        (1..=segments.len()).any(|len| segments_match(pattern, &segments[..len], false))
+    })
+}
+
+/// A config entry resolved to a concrete path in the main worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedEntry {
+    /// Path relative to the repo root.
+    pub path: PathBuf,
+    /// How the entry is materialized.
+    pub mode: LinkMode,
 }
 
 /// Resolve config entries into concrete paths that exist in the main worktree.
-pub fn resolve_entries(repo: &RepoRoot, patterns: &[String]) -> Vec<PathBuf> {
+///
+/// Entries starting with `!` exclude matching paths from every other entry,
+/// regardless of their position in the config. Paths nested under another
+/// resolved path are dropped, since linking the parent already covers them.
+/// When several entries resolve to the same path, the first one's mode wins.
+pub fn resolve_entries(repo: &RepoRoot, patterns: &[String]) -> Vec<ResolvedEntry> {
     let repo_root = repo.as_ref();
+    let exclusions: Vec<Vec<&str>> = patterns
+        .iter()
+        .filter_map(|p| p.strip_prefix('!'))
+        .map(|p| p.split('/').filter(|s| !s.is_empty()).collect())
+        .collect();
+
     let mut seen = BTreeSet::new();
     let mut resolved = Vec::new();
 
-    for pattern in patterns {
+    for entry in patterns.iter().filter(|p| !p.starts_with('!')) {
+        let (mode, pattern) = split_mode(entry);
         for rel_path in expand_entry(repo_root, pattern) {
             let abs_path = repo_root.join(&rel_path);
-            if abs_path.exists() && seen.insert(rel_path.clone()) {
-                resolved.push(rel_path);
+            if abs_path.exists()
+                && !is_excluded(&rel_path, &exclusions)
+                && seen.insert(rel_path.clone())
+            {
+                resolved.push(ResolvedEntry {
+                    path: rel_path,
+                    mode,
+                });
             }
         }
     }
 
+    let nested: Vec<bool> = resolved
+        .iter()
+        .map(|entry| {
+            resolved
+                .iter()
+                .any(|other| other.path != entry.path && entry.path.starts_with(&other.path))
+        })
+        .collect();
     resolved
+        .into_iter()
+        .zip(nested)
+        .filter_map(|(entry, nested)| (!nested).then_some(entry))
+        .collect()
 }
 
 fn is_node_modules_path(path: &Path) -> bool {
@@ -173,13 +337,15 @@ fn pnpm_unsafe_node_modules_warning() -> String {
     "pnpm workspace detected; skipping node_modules symlink because workspace dependencies may resolve to the main worktree. Run `pnpm install --prefer-offline --frozen-lockfile` in the new worktree instead.".to_string()
 }
 
-fn filter_pnpm_unsafe_entries(entries: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<(PathBuf, String)>) {
+fn filter_pnpm_unsafe_entries(
+    entries: Vec<ResolvedEntry>,
+) -> (Vec<ResolvedEntry>, Vec<(PathBuf, String)>) {
     let mut safe = Vec::new();
     let mut skipped = Vec::new();
 
     for entry in entries {
-        if is_node_modules_path(&entry) {
-            skipped.push((entry, pnpm_unsafe_node_modules_warning()));
+        if is_node_modules_path(&entry.path) {
+            skipped.push((entry.path, pnpm_unsafe_node_modules_warning()));
         } else {
             safe.push(entry);
         }
@@ -193,8 +359,9 @@ fn filter_pnpm_unsafe_patterns(patterns: Vec<String>) -> (Vec<String>, Vec<(Path
     let mut skipped = Vec::new();
 
     for pattern in patterns {
-        let path = PathBuf::from(&pattern);
-        if is_node_modules_path(&path) {
+        let path = PathBuf::from(split_mode(&pattern).1);
+        // Exclusions only remove entries, so they are always safe.
+        if !pattern.starts_with('!') && is_node_modules_path(&path) {
             skipped.push((path, pnpm_unsafe_node_modules_warning()));
         } else {
             safe.push(pattern);
@@ -207,7 +374,14 @@ fn filter_pnpm_unsafe_patterns(patterns: Vec<String>) -> (Vec<String>, Vec<(Path
 /// Outcome of a single symlink attempt.
 #[derive(Debug)]
 pub enum SymlinkOutcome {
-    Created(PathBuf),
+    /// The entry was materialized; carries the mode actually used, which is
+    /// `Copy` when a reflink fell back.
+    Created(PathBuf, LinkMode),
+    /// A symlink pointing somewhere else was replaced (`wt link`).
+    Repaired(PathBuf, LinkMode),
+    /// A shared link no longer in config was removed (`wt link --prune`).
+    Removed(PathBuf),
+    /// Entry left untouched, with the reason.
     Skipped(PathBuf, String),
 }
 
@@ -222,12 +396,13 @@ fn ensure_parent_dirs(path: &Path) -> Result<(), String> {
     fs::create_dir_all(parent).map_err(|e| format!("failed to create parent directory: {e}"))
 }
 
-/// Create a single symlink, returning the outcome.
+/// Materialize a single entry, returning the outcome.
 fn create_one_symlink(
     repo_root: &Path,
     worktree_path: &Path,
-    rel_path: &PathBuf,
+    entry: &ResolvedEntry,
 ) -> SymlinkOutcome {
+    let rel_path = &entry.path;
     let target_in_wt = worktree_path.join(rel_path);
 
     if target_in_wt.exists() || target_in_wt.symlink_metadata().is_ok() {
@@ -239,14 +414,68 @@ fn create_one_symlink(
     }
 
     let source_in_main = repo_root.join(rel_path);
+    if entry.mode != LinkMode::Symlink {
+        return match copy_tree(&source_in_main, &target_in_wt, entry.mode) {
+            Ok(mode) => SymlinkOutcome::Created(rel_path.clone(), mode),
+            Err(e) => {
+                // Never leave a half-populated copy behind.
+                let _ =
+                    fs::remove_dir_all(&target_in_wt).or_else(|_| fs::remove_file(&target_in_wt));
+                SymlinkOutcome::Skipped(
+                    rel_path.clone(),
+                    format!("{} failed: {e}", entry.mode.as_str()),
+                )
+            }
+        };
+    }
+
     let link_target = compute_relative_symlink(&target_in_wt, &source_in_main);
 
     match create_symlink(&link_target, &target_in_wt, &source_in_main) {
-        Ok(()) => SymlinkOutcome::Created(rel_path.clone()),
+        Ok(()) => SymlinkOutcome::Created(rel_path.clone(), LinkMode::Symlink),
         Err(e) => SymlinkOutcome::Skipped(rel_path.clone(), format!("symlink failed: {e}")),
     }
 }
 
+/// Recreate `src` at `dst` using `mode` for regular files.
+///
+/// Directories are recreated and symlinks inside the tree are copied as
+/// symlinks. Returns the mode actually used: a reflink that had to fall back
+/// to copying any file is reported as `Copy`.
+fn copy_tree(src: &Path, dst: &Path, mode: LinkMode) -> io::Result<LinkMode> {
+    let file_type = fs::symlink_metadata(src)?.file_type();
+
+    if file_type.is_symlink() {
+        create_symlink(&fs::read_link(src)?, dst, src)?;
+        return Ok(mode);
+    }
+    if !file_type.is_dir() {
+        return copy_file(src, dst, mode);
+    }
+
+    fs::create_dir(dst)?;
+    let mut used = mode;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let child_mode = copy_tree(&entry.path(), &dst.join(entry.file_name()), mode)?;
+        if child_mode != mode {
+            used = child_mode;
+        }
+    }
+    Ok(used)
+}
+
+fn copy_file(src: &Path, dst: &Path, mode: LinkMode) -> io::Result<LinkMode> {
+    match mode {
+        LinkMode::Hardlink => fs::hard_link(src, dst).map(|()| mode),
+        LinkMode::Reflink => reflink_copy::reflink_or_copy(src, dst).map(|copied| match copied {
+            Some(_) => LinkMode::Copy,
+            None => LinkMode::Reflink,
+        }),
+        LinkMode::Copy | LinkMode::Symlink => fs::copy(src, dst).map(|_| LinkMode::Copy),
+    }
+}
+
 #[cfg(unix)]
 fn create_symlink(
     link_target: &Path,
@@ -272,20 +501,21 @@ fn create_symlink(
     }
 }
 
-/// Create symlinks in the new worktree for all resolved entries.
+/// Materialize all resolved entries in the new worktree.
 ///
-/// For each entry, computes a relative symlink from the target location
-/// back to the corresponding path in the main worktree.
+/// Symlink entries get a relative symlink from the target location back to
+/// the corresponding path in the main worktree; other modes copy, hard-link
+/// or reflink the content.
 pub fn create_symlinks(
     repo: &RepoRoot,
     worktree_path: &Path,
-    entries: &[PathBuf],
+    entries: &[ResolvedEntry],
 ) -> Vec<SymlinkOutcome> {
     let repo_root = repo.as_ref();
     let mut outcomes = Vec::new();
 
-    for rel_path in entries {
-        outcomes.push(create_one_symlink(repo_root, worktree_path, rel_path));
+    for entry in entries {
+        outcomes.push(create_one_symlink(repo_root, worktree_path, entry));
     }
 
     outcomes
@@ -319,17 +549,31 @@ fn relative_path(from: &Path, to: &Path) -> PathBuf {
     result
 }
 
+/// A materialized entry and the mode used for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedEntry {
+    /// Path relative to the worktree root.
+    pub path: PathBuf,
+    /// How the entry was materialized.
+    pub mode: LinkMode,
+}
+
 /// Result of the symlink phase during `wt add`.
 #[derive(Debug)]
 pub struct SymlinkReport {
-    pub created: Vec<PathBuf>,
+    /// Entries that were created.
+    pub created: Vec<LinkedEntry>,
+    /// Entries that were skipped, with the reason.
     pub skipped: Vec<(PathBuf, String)>,
 }
 
-/// Run the full symlink pipeline: load config, resolve, create.
+/// Entries left out of linking, with the reason.
+type SkippedEntries = Vec<(PathBuf, String)>;
+
+/// Load, resolve and pnpm-filter the configured entries.
 ///
 /// Returns `None` when no config file exists (feature not configured).
-pub fn apply_symlinks(repo: &RepoRoot, worktree_path: &Path) -> Option<SymlinkReport> {
+fn configured_entries(repo: &RepoRoot) -> Option<(Vec<ResolvedEntry>, SkippedEntries)> {
     let patterns = load_config(repo);
     if patterns.is_empty() {
         return None;
@@ -348,20 +592,240 @@ pub fn apply_symlinks(repo: &RepoRoot, worktree_path: &Path) -> Option<SymlinkRe
         (entries, Vec::new())
     };
     skipped.extend(expanded_skipped);
+    Some((entries, skipped))
+}
+
+/// Run the full symlink pipeline: load config, resolve, create.
+///
+/// Returns `None` when no config file exists (feature not configured).
+pub fn apply_symlinks(repo: &RepoRoot, worktree_path: &Path) -> Option<SymlinkReport> {
+    let (entries, mut skipped) = configured_entries(repo)?;
     let outcomes = create_symlinks(repo, worktree_path, &entries);
 
     let mut created = Vec::new();
 
     for outcome in outcomes {
         match outcome {
-            SymlinkOutcome::Created(p) => created.push(p),
+            SymlinkOutcome::Created(path, mode) | SymlinkOutcome::Repaired(path, mode) => {
+                created.push(LinkedEntry { path, mode })
+            }
             SymlinkOutcome::Skipped(p, reason) => skipped.push((p, reason)),
+            SymlinkOutcome::Removed(_) => {}
         }
     }
 
     Some(SymlinkReport { created, skipped })
 }
 
+/// Re-apply the config to an existing worktree.
+///
+/// Missing entries are created and symlinks pointing anywhere other than
+/// the main worktree's copy are repaired. A symlink left where the config
+/// now asks for a copy is replaced too. Real files and directories are never
+/// overwritten. With `prune`, shared links that are no longer configured are
+/// removed. With `dry_run`, the outcomes are only computed.
+///
+/// Entries that are already in place produce no outcome.
+pub fn refresh_links(
+    repo: &RepoRoot,
+    worktree_path: &Path,
+    dry_run: bool,
+    prune: bool,
+) -> Vec<SymlinkOutcome> {
+    let repo_root = repo.as_ref();
+    let (entries, skipped) = configured_entries(repo).unwrap_or_default();
+
+    let mut outcomes: Vec<SymlinkOutcome> = entries
+        .iter()
+        .filter_map(|entry| refresh_one(repo_root, worktree_path, entry, dry_run))
+        .collect();
+    outcomes.extend(
+        skipped
+            .into_iter()
+            .map(|(path, reason)| SymlinkOutcome::Skipped(path, reason)),
+    );
+
+    if !prune {
+        return outcomes;
+    }
+    let keep: BTreeSet<&Path> = entries.iter().map(|e| e.path.as_path()).collect();
+    for rel_path in shared_links(repo_root, worktree_path) {
+        if keep.contains(rel_path.as_path()) {
+            continue;
+        }
+        let removed = dry_run || fs::remove_file(worktree_path.join(&rel_path)).is_ok();
+        outcomes.push(if removed {
+            SymlinkOutcome::Removed(rel_path)
+        } else {
+            SymlinkOutcome::Skipped(rel_path, "could not remove stale link".to_string())
+        });
+    }
+
+    outcomes
+}
+
+fn refresh_one(
+    repo_root: &Path,
+    worktree_path: &Path,
+    entry: &ResolvedEntry,
+    dry_run: bool,
+) -> Option<SymlinkOutcome> {
+    let target = worktree_path.join(&entry.path);
+    let Ok(metadata) = target.symlink_metadata() else {
+        return Some(if dry_run {
+            SymlinkOutcome::Created(entry.path.clone(), entry.mode)
+        } else {
+            create_one_symlink(repo_root, worktree_path, entry)
+        });
+    };
+
+    if !metadata.is_symlink() {
+        // Copies are expected to be real paths; for symlink entries this is
+        // likely local state we must not destroy.
+        return (entry.mode == LinkMode::Symlink).then(|| {
+            SymlinkOutcome::Skipped(
+                entry.path.clone(),
+                "exists and is not a symlink".to_string(),
+            )
+        });
+    }
+
+    if entry.mode == LinkMode::Symlink && is_shared_link(repo_root, worktree_path, &entry.path) {
+        return None;
+    }
+    if dry_run {
+        return Some(SymlinkOutcome::Repaired(entry.path.clone(), entry.mode));
+    }
+    if let Err(e) = fs::remove_file(&target) {
+        return Some(SymlinkOutcome::Skipped(
+            entry.path.clone(),
+            format!("could not replace link: {e}"),
+        ));
+    }
+    Some(match create_one_symlink(repo_root, worktree_path, entry) {
+        SymlinkOutcome::Created(path, mode) => SymlinkOutcome::Repaired(path, mode),
+        other => other,
+    })
+}
+
+/// Whether `rel_path` in the worktree is a symlink to the same path in the
+/// main worktree, exactly as [`create_symlinks`] would have created it.
+pub fn is_shared_link(repo_root: &Path, worktree_path: &Path, rel_path: &Path) -> bool {
+    let link = worktree_path.join(rel_path);
+    let expected = compute_relative_symlink(&link, &repo_root.join(rel_path));
+    fs::read_link(&link).is_ok_and(|target| target == expected)
+}
+
+/// Find shared links in the worktree among its untracked paths.
+///
+/// Git collapses wholly untracked directories into one `dir/` entry; those
+/// are walked for symlinks so links created under new directories are found.
+fn shared_links(repo_root: &Path, worktree_path: &Path) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    for path in git::untracked_paths(worktree_path).unwrap_or_default() {
+        match path.strip_suffix('/') {
+            Some(dir) => collect_symlinks(worktree_path, Path::new(dir), &mut candidates),
+            None => candidates.push(PathBuf::from(path)),
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|p| is_shared_link(repo_root, worktree_path, p))
+        .collect()
+}
+
+/// Collect the symlinks below `rel_dir` (relative to `root`) without
+/// following them. Nested repositories are left alone.
+fn collect_symlinks(root: &Path, rel_dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(root.join(rel_dir)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let rel_path = rel_dir.join(entry.file_name());
+        if file_type.is_symlink() {
+            out.push(rel_path);
+        } else if file_type.is_dir() && !root.join(&rel_path).join(".git").exists() {
+            collect_symlinks(root, &rel_path, out);
+        }
+    }
+}
+
+/// A problem with a configured shared entry in a worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkProblem {
+    /// The entry was never created (or was deleted).
+    Missing,
+    /// A shared link whose target no longer exists in the main worktree.
+    Dangling,
+    /// A symlink pointing somewhere other than the main worktree's copy.
+    WrongTarget,
+    /// A symlink entry replaced by a real file or directory.
+    Replaced,
+    /// A shared link that is in the index and would be committed.
+    Tracked,
+}
+
+/// A shared-entry problem found by [`check_links`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkFinding {
+    /// Path relative to the worktree root.
+    pub path: PathBuf,
+    /// What is wrong with the entry.
+    pub problem: LinkProblem,
+}
+
+/// Inspect a worktree's shared entries against the current config.
+///
+/// Real paths that are tracked in the worktree are the branch's own content
+/// and are not reported as replaced.
+pub fn check_links(repo: &RepoRoot, worktree_path: &Path) -> Vec<LinkFinding> {
+    let repo_root = repo.as_ref();
+    let (entries, _) = configured_entries(repo).unwrap_or_default();
+    let paths: Vec<PathBuf> = entries.iter().map(|e| e.path.clone()).collect();
+    let tracked = git::tracked_paths(worktree_path, &paths).unwrap_or_default();
+
+    let mut findings: Vec<LinkFinding> = entries
+        .iter()
+        .filter_map(|entry| {
+            let metadata = worktree_path.join(&entry.path).symlink_metadata();
+            let is_tracked = tracked.contains(&entry.path);
+            let problem = match metadata {
+                Err(_) => LinkProblem::Missing,
+                Ok(m) if m.is_symlink() && is_tracked => LinkProblem::Tracked,
+                Ok(m)
+                    if m.is_symlink()
+                        && entry.mode == LinkMode::Symlink
+                        && !is_shared_link(repo_root, worktree_path, &entry.path) =>
+                {
+                    LinkProblem::WrongTarget
+                }
+                Ok(m) if !m.is_symlink() && entry.mode == LinkMode::Symlink && !is_tracked => {
+                    LinkProblem::Replaced
+                }
+                Ok(_) => return None,
+            };
+            Some(LinkFinding {
+                path: entry.path.clone(),
+                problem,
+            })
+        })
+        .collect();
+
+    findings.extend(
+        shared_links(repo_root, worktree_path)
+            .into_iter()
+            .filter(|p| !worktree_path.join(p).exists())
+            .map(|path| LinkFinding {
+                path,
+                problem: LinkProblem::Dangling,
+            }),
+    );
+    findings
+}
+
 // ── Ecosystem detection and config generation (`wt setup`) ──────────
 
 /// Marker-to-ecosystem mapping: (ecosystem_name, marker_file, entries).
@@ -441,6 +905,7 @@ const PNPM_SAFE_NODE_ENTRIES: &[&str] = &[
     ".angular",
 ];
 
+/// Install hint shown after creating a worktree in a pnpm workspace.
 pub fn pnpm_install_recommendation() -> &'static str {
     "pnpm workspace detected; run `pnpm install --prefer-offline --frozen-lockfile` in the new worktree to create correct per-worktree links."
 }
@@ -458,6 +923,7 @@ fn package_manager_is_pnpm(package_json: &Path) -> bool {
         .is_some_and(|package_manager| package_manager.starts_with("pnpm@"))
 }
 
+/// Whether the repository is a pnpm workspace (has `pnpm-workspace.yaml`).
 pub fn is_pnpm_workspace(repo: &RepoRoot) -> bool {
     let root = repo.as_ref();
     root.join("pnpm-workspace.yaml").exists()
@@ -608,6 +1074,20 @@ mod tests {
         tempfile::TempDir::new().expect("failed to create temp dir")
     }
 
+    fn symlink_entries(paths: &[&str]) -> Vec<ResolvedEntry> {
+        paths
+            .iter()
+            .map(|p| ResolvedEntry {
+                path: PathBuf::from(p),
+                mode: LinkMode::Symlink,
+            })
+            .collect()
+    }
+
+    fn paths(entries: &[ResolvedEntry]) -> Vec<PathBuf> {
+        entries.iter().map(|e| e.path.clone()).collect()
+    }
+
     #[test]
     fn parse_config_skips_blanks_and_comments() {
         let dir = make_temp_dir();
@@ -671,6 +1151,117 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn glob_matches_full_segment_syntax() {
+        assert!(glob_matches("*.env.*", "web.env.local"));
+        assert!(glob_matches("a*b*c", "aXbYc"));
+        assert!(!glob_matches("a*b*c", "aXbY"));
+        assert!(glob_matches("file?.txt", "file1.txt"));
+        assert!(!glob_matches("file?.txt", "file.txt"));
+        assert!(glob_matches("[abc]pp", "app"));
+        assert!(glob_matches("v[0-9]", "v7"));
+        assert!(!glob_matches("[!a]pp", "app"));
+        assert!(glob_matches("[!a]pp", "opp"));
+        assert!(glob_matches("a[b", "a[b"));
+    }
+
+    #[test]
+    fn expand_entry_double_star_matches_any_depth() {
+        let dir = make_temp_dir();
+        for path in [
+            "node_modules",
+            "apps/web/node_modules",
+            "libs/a/b/node_modules",
+        ] {
+            fs::create_dir_all(dir.path().join(path)).expect("mkdir");
+        }
+        // Nested matches are not walked into.
+        fs::create_dir_all(dir.path().join("node_modules/pkg/node_modules")).expect("mkdir");
+
+        let result = expand_entry(dir.path(), "**/node_modules");
+        assert_eq!(
+            result,
+            vec![
+                PathBuf::from("apps/web/node_modules"),
+                PathBuf::from("libs/a/b/node_modules"),
+                PathBuf::from("node_modules"),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_entry_double_star_with_literal_prefix() {
+        let dir = make_temp_dir();
+        fs::create_dir_all(dir.path().join("apps/web/site")).expect("mkdir");
+        fs::write(dir.path().join("apps/web/site/.env.local"), "").expect("write");
+        fs::write(dir.path().join("apps/.env.local"), "").expect("write");
+        fs::write(dir.path().join(".env.local"), "").expect("write");
+
+        let result = expand_entry(dir.path(), "apps/**/.env.local");
+        assert_eq!(
+            result,
+            vec![
+                PathBuf::from("apps/.env.local"),
+                PathBuf::from("apps/web/site/.env.local"),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_entry_prunes_git_and_worktrees() {
+        let dir = make_temp_dir();
+        for path in [".git/x/target", ".worktrees/feat/target", "crates/a/target"] {
+            fs::create_dir_all(dir.path().join(path)).expect("mkdir");
+        }
+
+        let result = expand_entry(dir.path(), "**/target");
+        assert_eq!(result, vec![PathBuf::from("crates/a/target")]);
+    }
+
+    #[test]
+    fn expand_entry_stops_at_depth_limit() {
+        let dir = make_temp_dir();
+        let shallow: PathBuf = (0..3).map(|i| format!("d{i}")).collect();
+        let deep: PathBuf = (0..MAX_GLOB_DEPTH + 1).map(|i| format!("d{i}")).collect();
+        fs::create_dir_all(dir.path().join(&deep).join(".env")).expect("mkdir");
+        fs::create_dir_all(dir.path().join(&shallow).join(".env")).expect("mkdir");
+
+        let result = expand_entry(dir.path(), "**/.env");
+        assert_eq!(result, vec![shallow.join(".env")]);
+    }
+
+    #[test]
+    fn resolve_entries_applies_exclusions_anywhere_in_config() {
+        let dir = make_temp_dir();
+        let repo = RepoRoot(dir.path().to_path_buf());
+        for path in ["apps/web/node_modules", "legacy/old/node_modules", "target"] {
+            fs::create_dir_all(dir.path().join(path)).expect("mkdir");
+        }
+
+        let patterns = vec![
+            "!legacy".to_string(),
+            "**/node_modules".to_string(),
+            "target".to_string(),
+            "!target".to_string(),
+        ];
+        let resolved = resolve_entries(&repo, &patterns);
+        assert_eq!(
+            paths(&resolved),
+            vec![PathBuf::from("apps/web/node_modules")]
+        );
+    }
+
+    #[test]
+    fn resolve_entries_drops_paths_nested_under_other_entries() {
+        let dir = make_temp_dir();
+        let repo = RepoRoot(dir.path().to_path_buf());
+        fs::create_dir_all(dir.path().join("apps/web/.next")).expect("mkdir");
+
+        let patterns = vec!["apps/*/.next".to_string(), "apps".to_string()];
+        let resolved = resolve_entries(&repo, &patterns);
+        assert_eq!(paths(&resolved), vec![PathBuf::from("apps")]);
+    }
+
     #[test]
     fn resolve_entries_filters_nonexistent() {
         let dir = make_temp_dir();
@@ -679,7 +1270,7 @@ mod tests {
 
         let patterns = vec!["node_modules".to_string(), "target".to_string()];
         let resolved = resolve_entries(&repo, &patterns);
-        assert_eq!(resolved, vec![PathBuf::from("node_modules")]);
+        assert_eq!(paths(&resolved), vec![PathBuf::from("node_modules")]);
     }
 
     #[test]
@@ -727,11 +1318,14 @@ mod tests {
         fs::create_dir(repo_root.join("node_modules")).expect("mkdir");
 
         let repo = RepoRoot(repo_root);
-        let entries = vec![PathBuf::from("node_modules")];
+        let entries = symlink_entries(&["node_modules"]);
         let outcomes = create_symlinks(&repo, &wt_path, &entries);
 
         assert_eq!(outcomes.len(), 1);
-        assert!(matches!(outcomes[0], SymlinkOutcome::Created(_)));
+        assert!(matches!(
+            outcomes[0],
+            SymlinkOutcome::Created(_, LinkMode::Symlink)
+        ));
 
         let link = wt_path.join("node_modules");
         assert!(link
@@ -752,11 +1346,14 @@ mod tests {
         fs::write(repo_root.join(".env"), "SECRET=1").expect("write");
 
         let repo = RepoRoot(repo_root);
-        let entries = vec![PathBuf::from(".env")];
+        let entries = symlink_entries(&[".env"]);
         let outcomes = create_symlinks(&repo, &wt_path, &entries);
 
         assert_eq!(outcomes.len(), 1);
-        assert!(matches!(outcomes[0], SymlinkOutcome::Created(_)));
+        assert!(matches!(
+            outcomes[0],
+            SymlinkOutcome::Created(_, LinkMode::Symlink)
+        ));
 
         let link = wt_path.join(".env");
         assert!(link
@@ -778,7 +1375,7 @@ mod tests {
         fs::create_dir(wt_path.join("node_modules")).expect("mkdir");
 
         let repo = RepoRoot(repo_root);
-        let entries = vec![PathBuf::from("node_modules")];
+        let entries = symlink_entries(&["node_modules"]);
         let outcomes = create_symlinks(&repo, &wt_path, &entries);
 
         assert!(matches!(outcomes[0], SymlinkOutcome::Skipped(_, _)));
@@ -795,10 +1392,13 @@ mod tests {
         fs::write(repo_root.join("apps/api/.env"), "SECRET=x").expect("write");
 
         let repo = RepoRoot(repo_root);
-        let entries = vec![PathBuf::from("apps/api/.env")];
+        let entries = symlink_entries(&["apps/api/.env"]);
         let outcomes = create_symlinks(&repo, &wt_path, &entries);
 
-        assert!(matches!(outcomes[0], SymlinkOutcome::Created(_)));
+        assert!(matches!(
+            outcomes[0],
+            SymlinkOutcome::Created(_, LinkMode::Symlink)
+        ));
         assert!(wt_path.join("apps/api/.env").exists());
     }
 
@@ -814,12 +1414,134 @@ mod tests {
         symlink("/nonexistent", wt_path.join("node_modules")).expect("symlink");
 
         let repo = RepoRoot(repo_root);
-        let entries = vec![PathBuf::from("node_modules")];
+        let entries = symlink_entries(&["node_modules"]);
         let outcomes = create_symlinks(&repo, &wt_path, &entries);
 
         assert!(matches!(outcomes[0], SymlinkOutcome::Skipped(_, _)));
     }
 
+    #[test]
+    fn split_mode_recognizes_known_prefixes_only() {
+        assert_eq!(
+            split_mode("copy: .env.local"),
+            (LinkMode::Copy, ".env.local")
+        );
+        assert_eq!(split_mode("reflink:target"), (LinkMode::Reflink, "target"));
+        assert_eq!(
+            split_mode("hardlink: node_modules"),
+            (LinkMode::Hardlink, "node_modules")
+        );
+        assert_eq!(
+            split_mode("node_modules"),
+            (LinkMode::Symlink, "node_modules")
+        );
+        assert_eq!(split_mode("c:weird"), (LinkMode::Symlink, "c:weird"));
+    }
+
+    #[test]
+    fn resolve_entries_carries_mode_per_entry() {
+        let dir = make_temp_dir();
+        let repo = RepoRoot(dir.path().to_path_buf());
+        fs::write(dir.path().join(".env"), "A=1").expect("write");
+        fs::create_dir(dir.path().join("target")).expect("mkdir");
+
+        let patterns = vec!["copy: .env".to_string(), "reflink: target".to_string()];
+        let resolved = resolve_entries(&repo, &patterns);
+        assert_eq!(
+            resolved.iter().map(|e| e.mode).collect::<Vec<_>>(),
+            vec![LinkMode::Copy, LinkMode::Reflink]
+        );
+    }
+
+    fn entry(path: &str, mode: LinkMode) -> Vec<ResolvedEntry> {
+        vec![ResolvedEntry {
+            path: PathBuf::from(path),
+            mode,
+        }]
+    }
+
+    #[test]
+    fn create_symlinks_copy_mode_makes_independent_tree() {
+        let dir = make_temp_dir();
+        let repo_root = dir.path().join("repo");
+        let wt_path = repo_root.join(".worktrees/feat--eeee5555");
+        fs::create_dir_all(&wt_path).expect("mkdir");
+        fs::create_dir_all(repo_root.join(".venv/bin")).expect("mkdir");
+        fs::write(repo_root.join(".venv/bin/python"), "v1").expect("write");
+
+        let repo = RepoRoot(repo_root.clone());
+        let outcomes = create_symlinks(&repo, &wt_path, &entry(".venv", LinkMode::Copy));
+
+        assert!(matches!(
+            outcomes[0],
+            SymlinkOutcome::Created(_, LinkMode::Copy)
+        ));
+        let copied = wt_path.join(".venv");
+        assert!(!copied.symlink_metadata().expect("stat").is_symlink());
+        fs::write(copied.join("bin/python"), "v2").expect("write");
+        assert_eq!(
+            fs::read_to_string(repo_root.join(".venv/bin/python")).expect("read"),
+            "v1"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_symlinks_hardlink_mode_shares_inodes() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = make_temp_dir();
+        let repo_root = dir.path().join("repo");
+        let wt_path = repo_root.join(".worktrees/feat--ffff6666");
+        fs::create_dir_all(&wt_path).expect("mkdir");
+        fs::create_dir_all(repo_root.join("node_modules/pkg")).expect("mkdir");
+        fs::write(repo_root.join("node_modules/pkg/index.js"), "x").expect("write");
+        symlink("pkg", repo_root.join("node_modules/alias")).expect("symlink");
+
+        let repo = RepoRoot(repo_root.clone());
+        let outcomes = create_symlinks(&repo, &wt_path, &entry("node_modules", LinkMode::Hardlink));
+
+        assert!(matches!(
+            outcomes[0],
+            SymlinkOutcome::Created(_, LinkMode::Hardlink)
+        ));
+        let original = fs::metadata(repo_root.join("node_modules/pkg/index.js")).expect("stat");
+        let linked = fs::metadata(wt_path.join("node_modules/pkg/index.js")).expect("stat");
+        assert_eq!(original.ino(), linked.ino());
+        assert!(!wt_path
+            .join("node_modules")
+            .symlink_metadata()
+            .expect("stat")
+            .is_symlink());
+        assert_eq!(
+            fs::read_link(wt_path.join("node_modules/alias")).expect("readlink"),
+            PathBuf::from("pkg")
+        );
+    }
+
+    #[test]
+    fn create_symlinks_reflink_mode_falls_back_to_copy() {
+        let dir = make_temp_dir();
+        let repo_root = dir.path().join("repo");
+        let wt_path = repo_root.join(".worktrees/feat--abab7777");
+        fs::create_dir_all(&wt_path).expect("mkdir");
+        fs::create_dir_all(repo_root.join("target/debug")).expect("mkdir");
+        fs::write(repo_root.join("target/debug/app"), "bin").expect("write");
+
+        let repo = RepoRoot(repo_root);
+        let outcomes = create_symlinks(&repo, &wt_path, &entry("target", LinkMode::Reflink));
+
+        // Which mode is used depends on the filesystem backing the temp dir.
+        assert!(matches!(
+            outcomes[0],
+            SymlinkOutcome::Created(_, LinkMode::Reflink | LinkMode::Copy)
+        ));
+        assert_eq!(
+            fs::read_to_string(wt_path.join("target/debug/app")).expect("read"),
+            "bin"
+        );
+    }
+
     #[test]
     fn ensure_gitignore_creates_file_when_missing() {
         let dir = make_temp_dir();
@@ -951,11 +1673,15 @@ mod tests {
             "apps/*/node_modules".to_string(),
             "target".to_string(),
             "unknown-extra".to_string(),
+            "!legacy/node_modules".to_string(),
         ];
 
         let (safe, skipped) = filter_pnpm_unsafe_patterns(patterns);
 
-        assert_eq!(safe, vec!["target", "unknown-extra"]);
+        assert_eq!(
+            safe,
+            vec!["target", "unknown-extra", "!legacy/node_modules"]
+        );
         assert_eq!(
             skipped.iter().map(|(path, _)| path).collect::<Vec<_>>(),
             vec![
@@ -983,15 +1709,11 @@ mod tests {
 
     #[test]
     fn pnpm_filter_skips_nested_node_modules_entries() {
-        let entries = vec![
-            PathBuf::from("node_modules"),
-            PathBuf::from("apps/web/node_modules"),
-            PathBuf::from("target"),
-        ];
+        let entries = symlink_entries(&["node_modules", "apps/web/node_modules", "target"]);
 
         let (safe, skipped) = filter_pnpm_unsafe_entries(entries);
 
-        assert_eq!(safe, vec![PathBuf::from("target")]);
+        assert_eq!(paths(&safe), vec![PathBuf::from("target")]);
         assert_eq!(skipped.len(), 2);
         assert!(skipped[0].1.contains("workspace dependencies may resolve"));
     }
@@ -0,0 +1,299 @@
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use crate::error::{AppError, Result};
+
+const CONFIG_DIR: &str = ".wt";
+const LAYOUT_FILE: &str = "tmux";
+
+/// Session option tagging a session with the worktree it was created for.
+///
+/// Sessions are found and killed through this tag rather than by name, so
+/// a user's own session that happens to share a name is never touched.
+const PATH_OPTION: &str = "@wt-path";
+
+/// A tmux session created for a worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// One window of a `.wt/tmux` layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutWindow {
+    pub name: String,
+    /// Typed into the window once it is created.
+    pub command: Option<String>,
+}
+
+fn tmux(args: &[&str]) -> Result<Output> {
+    Command::new("tmux")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| AppError::git(format!("failed to run tmux: {e}")))
+}
+
+/// Run tmux and return its trimmed stdout, failing on a non-zero exit.
+fn tmux_ok(args: &[&str]) -> Result<String> {
+    let output = tmux(args)?;
+    if !output.status.success() {
+        return Err(AppError::git(format!(
+            "tmux {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether a tmux server may be running, judged from its socket directory
+/// so that machines without one never spawn tmux.
+fn server_may_be_running() -> bool {
+    if std::env::var_os("TMUX").is_some() {
+        return true;
+    }
+    let socket_root =
+        std::env::var_os("TMUX_TMPDIR").map_or_else(|| PathBuf::from("/tmp"), PathBuf::from);
+    std::fs::read_dir(socket_root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .any(|entry| entry.file_name().to_string_lossy().starts_with("tmux-"))
+}
+
+/// Sessions tagged with a worktree path. Empty when tmux is not installed
+/// or no server is running.
+pub fn sessions() -> Vec<Session> {
+    if !server_may_be_running() {
+        return Vec::new();
+    }
+    // Session names never contain `:`, and tmux may escape a tab separator.
+    let format = format!("#{{session_name}}:#{{{PATH_OPTION}}}");
+    let Ok(output) = tmux(&["list-sessions", "-F", &format]) else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (name, path) = line.split_once(':')?;
+            (!path.is_empty()).then(|| Session {
+                name: name.to_string(),
+                path: PathBuf::from(path),
+            })
+        })
+        .collect()
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    canonical(a) == canonical(b)
+}
+
+/// The session created for the worktree at `path`, if any.
+pub fn session_for(sessions: &[Session], path: &Path) -> Option<Session> {
+    sessions.iter().find(|s| same_path(&s.path, path)).cloned()
+}
+
+/// tmux turns `.` and `:` in session names into `_`; do it up front so the
+/// name we report is the one tmux uses.
+fn sanitize(name: &str) -> String {
+    name.replace(['.', ':'], "_")
+}
+
+/// Return the session for the worktree at `path`, creating it when missing.
+///
+/// A new session is named `name`, or `fallback` when another session already
+/// uses that name, and gets the windows of `layout`. Returns the session and
+/// whether it was created.
+pub fn ensure_session(
+    path: &Path,
+    name: &str,
+    fallback: &str,
+    layout: &[LayoutWindow],
+) -> Result<(Session, bool)> {
+    let existing = sessions();
+    if let Some(session) = session_for(&existing, path) {
+        return Ok((session, false));
+    }
+
+    let taken = |candidate: &str| {
+        tmux(&["has-session", "-t", &format!("={candidate}")])
+            .is_ok_and(|output| output.status.success())
+    };
+    let name = match sanitize(name) {
+        name if !taken(&name) => name,
+        _ => sanitize(fallback),
+    };
+
+    let dir = path.display().to_string();
+    let mut windows = layout.iter();
+    let first = windows.next();
+    let mut args = vec!["new-session", "-d", "-P", "-F", "#{window_id}"];
+    args.extend(["-s", &name, "-c", &dir]);
+    if let Some(window) = first {
+        args.extend(["-n", &window.name]);
+    }
+    let first_id = tmux_ok(&args)?;
+    let session = Session {
+        name: name.clone(),
+        path: path.to_path_buf(),
+    };
+    tmux_ok(&[
+        "set-option",
+        "-t",
+        &format!("{}:", target(&session)),
+        PATH_OPTION,
+        &dir,
+    ])?;
+
+    if let Some(command) = first.and_then(|w| w.command.as_deref()) {
+        tmux_ok(&["send-keys", "-t", &first_id, command, "Enter"])?;
+    }
+    for window in windows {
+        let id = tmux_ok(&[
+            "new-window",
+            "-d",
+            "-P",
+            "-F",
+            "#{window_id}",
+            "-t",
+            &format!("{}:", target(&session)),
+            "-n",
+            &window.name,
+            "-c",
+            &dir,
+        ])?;
+        if let Some(command) = &window.command {
+            tmux_ok(&["send-keys", "-t", &id, command, "Enter"])?;
+        }
+    }
+
+    Ok((session, true))
+}
+
+/// Exact-match target for `session`; a bare name would also match prefixes.
+fn target(session: &Session) -> String {
+    format!("={}", session.name)
+}
+
+/// Bring `session` to the terminal: switch the client when running inside
+/// tmux, otherwise attach when there is a terminal to attach.
+///
+/// Returns whether the terminal was switched. Attaching blocks until the
+/// user detaches; the client draws on stderr so stdout stays usable for
+/// `--print-cd-path`.
+pub fn switch_to(session: &Session) -> Result<bool> {
+    if std::env::var_os("TMUX").is_some() {
+        tmux_ok(&["switch-client", "-t", &target(session)])?;
+        return Ok(true);
+    }
+    if !(std::io::stdin().is_terminal() && std::io::stderr().is_terminal()) {
+        return Ok(false);
+    }
+    let status = Command::new("tmux")
+        .args(["attach-session", "-t", &target(session)])
+        .stdout(std::io::stderr())
+        .status()
+        .map_err(|e| AppError::git(format!("failed to run tmux: {e}")))?;
+    Ok(status.success())
+}
+
+/// Kill the session created for the worktree at `path`. Best-effort: returns
+/// the killed session's name, or `None` when there was nothing to kill.
+///
+/// The session `wt` itself runs in is left alone, since killing it would
+/// take the running command down with it.
+pub fn kill_session_for(path: &Path) -> Option<String> {
+    let session = session_for(&sessions(), path)?;
+    if current_session().as_deref() == Some(session.name.as_str()) {
+        return None;
+    }
+    let output = tmux(&["kill-session", "-t", &target(&session)]).ok()?;
+    output.status.success().then_some(session.name)
+}
+
+/// Name of the session this process runs in, when inside tmux.
+fn current_session() -> Option<String> {
+    std::env::var_os("TMUX")?;
+    tmux_ok(&["display-message", "-p", "#{session_name}"]).ok()
+}
+
+/// Read the layout for a new session of the worktree at `worktree`: the
+/// file at `configured` (relative to the worktree) if set, else `.wt/tmux`.
+///
+/// Missing files give an empty layout, i.e. a single shell window.
+pub fn load_layout(worktree: &Path, configured: Option<&str>) -> Vec<LayoutWindow> {
+    let path = match configured {
+        Some(file) => worktree.join(file),
+        None => worktree.join(CONFIG_DIR).join(LAYOUT_FILE),
+    };
+    std::fs::read_to_string(path)
+        .map(|content| parse_layout(&content))
+        .unwrap_or_default()
+}
+
+/// Parse layout content: one window per line, `<name> [command...]`.
+///
+/// Blank lines and `#` comments are ignored.
+fn parse_layout(content: &str) -> Vec<LayoutWindow> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, command) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let command = command.trim();
+            LayoutWindow {
+                name: name.to_string(),
+                command: (!command.is_empty()).then(|| command.to_string()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_layout_reads_names_and_commands() {
+        let layout = parse_layout(
+            "\
+# editor first
+editor  nvim .
+
+server cargo watch -x run
+shell
+",
+        );
+        assert_eq!(
+            layout,
+            [
+                LayoutWindow {
+                    name: "editor".into(),
+                    command: Some("nvim .".into()),
+                },
+                LayoutWindow {
+                    name: "server".into(),
+                    command: Some("cargo watch -x run".into()),
+                },
+                LayoutWindow {
+                    name: "shell".into(),
+                    command: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sanitize_replaces_characters_tmux_rejects() {
+        assert_eq!(sanitize("feature-auth"), "feature-auth");
+        assert_eq!(sanitize("my.repo:1"), "my_repo_1");
+    }
+}
@@ -0,0 +1,642 @@
+//! `wt ui`: a terminal dashboard over the `worktree::*` operations.
+//!
+//! The dashboard draws on stderr so that the path of the worktree to go to
+//! can be printed on stdout for the shell bindings, like `go --print-cd-path`.
+
+use std::collections::BTreeSet;
+use std::io::{self, IsTerminal, Stderr};
+use std::path::{Path, PathBuf};
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{Frame, Terminal};
+
+use wt_core::cli_support;
+use wt_core::domain::{BranchName, RepoRoot, WorktreeStatsStatus};
+use wt_core::error::{AppError, Result};
+use wt_core::worktree::{
+    self, DirtyDiffMode, IntegrationMethod, IntegrationStatus, Overview, OverviewEntry,
+};
+
+type Term = Terminal<CrosstermBackend<Stderr>>;
+
+const HELP: &str = "↑/↓ move  space mark  enter go  d diff  D dirty diff  m merge  x remove  \
+                    l lock  p prune  r refresh  q quit";
+
+/// Run the dashboard until the user quits, returning the worktree to go to.
+///
+/// Quitting returns the main worktree when an action removed the one holding
+/// the working directory, so that the shell does not stay in a deleted one.
+pub fn run(repo: &RepoRoot) -> Result<Option<PathBuf>> {
+    if !(io::stdin().is_terminal() && io::stderr().is_terminal()) {
+        return Err(AppError::usage("wt ui requires a terminal".to_string()));
+    }
+
+    let mut app = App::new(repo.clone())?;
+    let _screen = Screen::enter()?;
+    let mut term = Terminal::new(CrosstermBackend::new(io::stderr())).map_err(term_err)?;
+    app.event_loop(&mut term)
+}
+
+fn term_err(e: io::Error) -> AppError {
+    AppError::usage(format!("terminal error: {e}"))
+}
+
+/// Raw mode on the alternate screen; restored on drop, panics included.
+struct Screen;
+
+impl Screen {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode().map_err(term_err)?;
+        execute!(io::stderr(), EnterAlternateScreen).map_err(term_err)?;
+        Ok(Self)
+    }
+
+    fn leave() {
+        let _ = execute!(io::stderr(), LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        Self::leave();
+    }
+}
+
+/// A destructive action waiting for `y`.
+enum Pending {
+    Merge(BranchName),
+    Remove(BranchName),
+    Prune(Vec<PathBuf>),
+}
+
+struct App {
+    repo: RepoRoot,
+    mainline: String,
+    entries: Vec<OverviewEntry>,
+    state: TableState,
+    marked: BTreeSet<PathBuf>,
+    message: Option<String>,
+    pending: Option<Pending>,
+    /// The working directory, to tell when an action removes it.
+    cwd: Option<PathBuf>,
+    /// Whether an action removed the worktree holding `cwd`.
+    cwd_removed: bool,
+}
+
+impl App {
+    fn new(repo: RepoRoot) -> Result<Self> {
+        let overview = worktree::overview(&repo)?;
+        let cwd = std::env::current_dir()
+            .ok()
+            .map(|cwd| cwd.canonicalize().unwrap_or(cwd));
+        Ok(Self::from_overview(repo, overview, cwd))
+    }
+
+    fn from_overview(repo: RepoRoot, overview: Overview, cwd: Option<PathBuf>) -> Self {
+        Self {
+            repo,
+            mainline: overview.mainline,
+            entries: overview.entries,
+            state: TableState::default().with_selected(Some(0)),
+            marked: BTreeSet::new(),
+            message: None,
+            pending: None,
+            cwd,
+            cwd_removed: false,
+        }
+    }
+
+    fn event_loop(&mut self, term: &mut Term) -> Result<Option<PathBuf>> {
+        loop {
+            term.draw(|frame| self.draw(frame)).map_err(term_err)?;
+            let Event::Key(key) = event::read().map_err(term_err)? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(pending) = self.pending.take() {
+                self.answer(pending, key.code);
+                continue;
+            }
+
+            self.message = None;
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(self.quit_target()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(self.quit_target())
+                }
+                KeyCode::Down | KeyCode::Char('j') => self.step(1),
+                KeyCode::Up | KeyCode::Char('k') => self.step(-1),
+                KeyCode::Enter | KeyCode::Char('g') => {
+                    if let Some(entry) = self.selected() {
+                        return Ok(Some(entry.worktree.path.clone()));
+                    }
+                }
+                KeyCode::Char(' ') => self.toggle_mark(),
+                KeyCode::Char('d') => self.diff(term, false)?,
+                KeyCode::Char('D') => self.diff(term, true)?,
+                KeyCode::Char('m') => self.ask_merge(),
+                KeyCode::Char('x') => self.ask_remove(),
+                KeyCode::Char('l') => self.toggle_lock(),
+                KeyCode::Char('p') => self.ask_prune(),
+                KeyCode::Char('r') => self.refresh(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Where to go on quit: nowhere, unless the working directory was
+    /// removed along with its worktree.
+    fn quit_target(&self) -> Option<PathBuf> {
+        self.cwd_removed.then(|| self.repo.as_ref().to_path_buf())
+    }
+
+    fn note_removed<'a>(&mut self, paths: impl IntoIterator<Item = &'a Path>) {
+        if let Some(cwd) = &self.cwd {
+            self.cwd_removed |= paths.into_iter().any(|path| cwd.starts_with(path));
+        }
+    }
+
+    fn selected(&self) -> Option<&OverviewEntry> {
+        self.state.selected().and_then(|idx| self.entries.get(idx))
+    }
+
+    /// The selected worktree's branch, for actions that need a linked
+    /// worktree with a branch.
+    fn selected_branch(&mut self, action: &str) -> Option<BranchName> {
+        let entry = self.selected()?;
+        let reason = if entry.worktree.is_main {
+            Some("the main worktree")
+        } else if entry.worktree.branch.is_none() {
+            Some("a detached worktree")
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => {
+                self.message = Some(format!("cannot {action} {reason}"));
+                None
+            }
+            None => entry.worktree.branch.as_deref().map(BranchName::new),
+        }
+    }
+
+    fn step(&mut self, delta: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let last = self.entries.len() - 1;
+        let current = self.state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(last);
+        self.state.select(Some(next));
+    }
+
+    fn toggle_mark(&mut self) {
+        let Some(entry) = self.selected() else {
+            return;
+        };
+        if entry.worktree.is_main {
+            return;
+        }
+        let path = entry.worktree.path.clone();
+        if !self.marked.remove(&path) {
+            self.marked.insert(path);
+        }
+        self.step(1);
+    }
+
+    fn refresh(&mut self) {
+        match worktree::overview(&self.repo) {
+            Ok(overview) => self.apply_overview(overview),
+            Err(err) => self.message = Some(format!("error: {err}")),
+        }
+    }
+
+    /// Show `overview`, keeping the selection in range and dropping marks
+    /// on worktrees that are gone.
+    fn apply_overview(&mut self, overview: Overview) {
+        self.mainline = overview.mainline;
+        self.entries = overview.entries;
+        self.marked
+            .retain(|path| self.entries.iter().any(|e| e.worktree.path == *path));
+        let last = self.entries.len().saturating_sub(1);
+        self.state
+            .select(Some(self.state.selected().unwrap_or(0).min(last)));
+    }
+
+    /// Run the difftool with the terminal handed back to it. Its stdout goes
+    /// to stderr, keeping stdout for the path printed on quit.
+    fn diff(&mut self, term: &mut Term, dirty: bool) -> Result<()> {
+        let result = if dirty {
+            let Some(entry) = self.selected() else {
+                return Ok(());
+            };
+            let worktree = entry.worktree.clone();
+            suspended(term, || {
+                cli_support::diff_dirty_to_stderr(&worktree, DirtyDiffMode::Dirty, None).map(|_| ())
+            })?
+        } else {
+            let Some(branch) = self.selected_branch("diff") else {
+                return Ok(());
+            };
+            let repo = self.repo.clone();
+            suspended(term, || {
+                cli_support::diff_to_stderr(&repo, &branch, None, None).map(|_| ())
+            })?
+        };
+        if let Err(err) = result {
+            self.message = Some(format!("error: {err}"));
+        }
+        Ok(())
+    }
+
+    fn ask_merge(&mut self) {
+        if let Some(branch) = self.selected_branch("merge") {
+            self.message = Some(format!(
+                "Merge '{branch}' into {} and remove its worktree? [y/N]",
+                self.mainline
+            ));
+            self.pending = Some(Pending::Merge(branch));
+        }
+    }
+
+    fn ask_remove(&mut self) {
+        if let Some(branch) = self.selected_branch("remove") {
+            self.message = Some(format!("Remove '{branch}' and its worktree? [y/N]"));
+            self.pending = Some(Pending::Remove(branch));
+        }
+    }
+
+    /// Prune the marked worktrees, or the selected one when none is marked.
+    fn ask_prune(&mut self) {
+        let paths: Vec<PathBuf> = if self.marked.is_empty() {
+            self.selected()
+                .filter(|e| !e.worktree.is_main)
+                .map(|e| e.worktree.path.clone())
+                .into_iter()
+                .collect()
+        } else {
+            self.marked.iter().cloned().collect()
+        };
+        if paths.is_empty() {
+            self.message = Some("cannot prune the main worktree".to_string());
+            return;
+        }
+        self.message = Some(format!(
+            "Prune {} worktree(s)? Only integrated or expired ones are removed. [y/N]",
+            paths.len()
+        ));
+        self.pending = Some(Pending::Prune(paths));
+    }
+
+    fn toggle_lock(&mut self) {
+        let Some(entry) = self.selected() else {
+            return;
+        };
+        if entry.worktree.is_main {
+            self.message = Some("cannot lock the main worktree".to_string());
+            return;
+        }
+        let path = entry.worktree.path.clone();
+        let (result, verb) = if entry.locked {
+            (worktree::unlock(&self.repo, &path), "Unlocked")
+        } else {
+            (worktree::lock(&self.repo, &path, None), "Locked")
+        };
+        self.message = Some(match result {
+            Ok(()) => format!("{verb} {}", path.display()),
+            Err(err) => format!("error: {err}"),
+        });
+        self.refresh();
+    }
+
+    /// Run `pending` on `y`; any other key cancels it.
+    fn answer(&mut self, pending: Pending, key: KeyCode) {
+        match key {
+            KeyCode::Char('y') => self.confirm(pending),
+            _ => self.message = Some("Cancelled".to_string()),
+        }
+    }
+
+    fn confirm(&mut self, pending: Pending) {
+        self.message = None;
+        let message = match pending {
+            Pending::Merge(branch) => {
+                worktree::merge(&self.repo, Some(&branch), None, false, false).map(|result| {
+                    self.note_removed(result.removed_path.as_deref());
+                    with_warnings(
+                        format!("Merged '{}' into {}", result.branch, result.mainline),
+                        &result.warnings,
+                    )
+                })
+            }
+            Pending::Remove(branch) => {
+                worktree::remove(&self.repo, Some(&branch), false).map(|result| {
+                    self.note_removed([result.removed_path.as_path()]);
+                    with_warnings(
+                        format!("Removed '{}'", result.branch),
+                        result.warning.as_slice(),
+                    )
+                })
+            }
+            Pending::Prune(paths) => {
+                worktree::prune_selected(&self.repo, &paths, false).map(|result| {
+                    self.marked.clear();
+                    self.note_removed(result.pruned.iter().map(|entry| entry.path.as_path()));
+                    with_warnings(
+                        format!(
+                            "Pruned {}, skipped {}",
+                            result.pruned.len(),
+                            result.skipped.len()
+                        ),
+                        &result.warnings,
+                    )
+                })
+            }
+        };
+        self.refresh();
+        // A failed refresh is reported over the action's outcome.
+        if self.message.is_none() {
+            self.message = Some(message.unwrap_or_else(|err| format!("error: {err}")));
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                "wt ui — {} (mainline: {})",
+                self.repo.as_ref().display(),
+                self.mainline
+            ))
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+            header,
+        );
+
+        let rows: Vec<Row> = self.entries.iter().map(|entry| self.row(entry)).collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Min(16),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(18),
+                Constraint::Length(5),
+                Constraint::Length(6),
+                Constraint::Min(20),
+            ],
+        )
+        .header(
+            Row::new([
+                "", "Branch", "Status", "↑/↓", "Changes", "Dirty", "Lock", "Path",
+            ])
+            .style(Style::default().add_modifier(Modifier::UNDERLINED)),
+        )
+        .block(Block::default().borders(Borders::TOP | Borders::BOTTOM))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, body, &mut self.state);
+
+        frame.render_widget(
+            Paragraph::new(self.message.as_deref().unwrap_or(HELP)),
+            footer,
+        );
+    }
+
+    fn row(&self, entry: &OverviewEntry) -> Row<'static> {
+        let wt = &entry.worktree;
+        let mark = if self.marked.contains(&wt.path) {
+            "*"
+        } else {
+            ""
+        };
+        let (status, color) = match &entry.integration {
+            None => ("main", Color::Blue),
+            Some(IntegrationStatus::Integrated(IntegrationMethod::Merged)) => {
+                ("merged", Color::Green)
+            }
+            Some(IntegrationStatus::Integrated(IntegrationMethod::Rebase)) => {
+                ("rebased", Color::Green)
+            }
+            Some(IntegrationStatus::Expired(_)) => ("expired", Color::Yellow),
+            Some(IntegrationStatus::NotIntegrated) => ("active", Color::Reset),
+            Some(IntegrationStatus::NoBranch) => ("detached", Color::Magenta),
+        };
+        let (ahead_behind, changes) = match &entry.stats {
+            WorktreeStatsStatus::Available(stats) => (
+                format!("{}↑{}↓", stats.commits_ahead, stats.commits_behind),
+                format!(
+                    "{} files +{} -{}",
+                    stats.files_changed, stats.insertions, stats.deletions
+                ),
+            ),
+            WorktreeStatsStatus::Unavailable { .. } => ("-".to_string(), "-".to_string()),
+        };
+        let dirty = match entry.dirty {
+            Some(true) => "*",
+            Some(false) => "",
+            None => "?",
+        };
+
+        Row::new([
+            Cell::from(mark),
+            Cell::from(wt.branch.clone().unwrap_or_else(|| wt.commit.clone())),
+            Cell::from(status).style(Style::default().fg(color)),
+            Cell::from(ahead_behind),
+            Cell::from(changes),
+            Cell::from(dirty),
+            Cell::from(if entry.locked { "locked" } else { "" }),
+            Cell::from(wt.path.display().to_string()),
+        ])
+    }
+}
+
+fn with_warnings(message: String, warnings: &[String]) -> String {
+    match warnings.first() {
+        Some(warning) => format!("{message} (warning: {warning})"),
+        None => message,
+    }
+}
+
+/// Leave the dashboard's screen while `f` runs, then redraw it from scratch.
+fn suspended<T>(term: &mut Term, f: impl FnOnce() -> T) -> Result<T> {
+    Screen::leave();
+    let result = f();
+    terminal::enable_raw_mode().map_err(term_err)?;
+    execute!(io::stderr(), EnterAlternateScreen).map_err(term_err)?;
+    term.clear().map_err(term_err)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wt_core::domain::Worktree;
+
+    /// An app over a throwaway repository whose overview lists `branches`
+    /// after the main worktree.
+    fn app(branches: &[&str]) -> (tempfile::TempDir, App) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .arg(dir.path())
+            .status()
+            .expect("git init");
+        assert!(status.success());
+        let repo = worktree::repo_root(dir.path()).expect("repo root");
+        let overview = overview(&repo, branches);
+        let app = App::from_overview(repo, overview, None);
+        (dir, app)
+    }
+
+    /// The main worktree of `repo` followed by a linked one per branch.
+    fn overview(repo: &RepoRoot, branches: &[&str]) -> Overview {
+        let entry = |path: PathBuf, branch: &str, is_main: bool| OverviewEntry {
+            worktree: Worktree {
+                path,
+                branch: Some(branch.to_string()),
+                commit: "abc1234".to_string(),
+                is_main,
+            },
+            stats: WorktreeStatsStatus::Unavailable {
+                base: "main".to_string(),
+                reason: "test".to_string(),
+            },
+            integration: (!is_main).then_some(IntegrationStatus::NotIntegrated),
+            dirty: Some(false),
+            locked: false,
+        };
+        let mut entries = vec![entry(repo.as_ref().to_path_buf(), "main", true)];
+        entries.extend(
+            branches
+                .iter()
+                .map(|branch| entry(repo.worktrees_dir().join(branch), branch, false)),
+        );
+        Overview {
+            mainline: "main".to_string(),
+            entries,
+        }
+    }
+
+    fn selected_branch(app: &App) -> Option<&str> {
+        app.selected()?.worktree.branch.as_deref()
+    }
+
+    fn pending_prune(app: &App) -> Vec<PathBuf> {
+        match &app.pending {
+            Some(Pending::Prune(paths)) => paths.clone(),
+            _ => panic!("no prune pending"),
+        }
+    }
+
+    #[test]
+    fn quit_goes_nowhere_by_default() {
+        let (_dir, app) = app(&["feat"]);
+        assert_eq!(app.quit_target(), None);
+    }
+
+    #[test]
+    fn quit_goes_to_the_main_worktree_after_removing_the_cwd() {
+        let (_dir, mut app) = app(&["feat", "other"]);
+        let feat = app.entries[1].worktree.path.clone();
+        let other = app.entries[2].worktree.path.clone();
+        app.cwd = Some(feat.join("src"));
+
+        app.note_removed([other.as_path()]);
+        assert_eq!(app.quit_target(), None);
+
+        app.note_removed([feat.as_path()]);
+        assert_eq!(app.quit_target(), Some(app.repo.as_ref().to_path_buf()));
+    }
+
+    #[test]
+    fn step_stays_within_the_list() {
+        let (_dir, mut app) = app(&["a", "b"]);
+        app.step(-1);
+        assert_eq!(selected_branch(&app), Some("main"));
+        app.step(1);
+        app.step(1);
+        app.step(1);
+        assert_eq!(selected_branch(&app), Some("b"));
+        app.step(-1);
+        assert_eq!(selected_branch(&app), Some("a"));
+    }
+
+    #[test]
+    fn toggle_mark_skips_main_and_moves_down() {
+        let (_dir, mut app) = app(&["a", "b"]);
+        app.toggle_mark();
+        assert!(app.marked.is_empty());
+        assert_eq!(selected_branch(&app), Some("main"));
+
+        app.step(1);
+        app.toggle_mark();
+        assert_eq!(selected_branch(&app), Some("b"));
+        app.step(-1);
+        app.toggle_mark();
+        assert!(app.marked.is_empty());
+    }
+
+    #[test]
+    fn prune_takes_marked_worktrees_over_the_selected_one() {
+        let (_dir, mut app) = app(&["a", "b", "c"]);
+        let path = |app: &App, idx: usize| app.entries[idx].worktree.path.clone();
+
+        app.ask_prune();
+        assert!(app.pending.is_none());
+        assert_eq!(
+            app.message.as_deref(),
+            Some("cannot prune the main worktree")
+        );
+
+        app.step(2);
+        app.ask_prune();
+        assert_eq!(pending_prune(&app), [path(&app, 2)]);
+
+        app.step(-1);
+        app.toggle_mark();
+        app.toggle_mark();
+        app.toggle_mark();
+        app.ask_prune();
+        assert_eq!(
+            pending_prune(&app),
+            [path(&app, 1), path(&app, 2), path(&app, 3)]
+        );
+    }
+
+    #[test]
+    fn refresh_clamps_the_selection_and_drops_stale_marks() {
+        let (_dir, mut app) = app(&["a", "b"]);
+        app.step(1);
+        app.toggle_mark();
+        app.toggle_mark();
+        assert_eq!(app.marked.len(), 2);
+
+        let repo = app.repo.clone();
+        app.apply_overview(overview(&repo, &["a"]));
+        assert_eq!(selected_branch(&app), Some("a"));
+        assert_eq!(
+            app.marked.iter().collect::<Vec<_>>(),
+            [&app.entries[1].worktree.path]
+        );
+
+        app.apply_overview(overview(&repo, &[]));
+        assert_eq!(selected_branch(&app), Some("main"));
+        assert!(app.marked.is_empty());
+    }
+}
@@ -1,9 +1,30 @@
 use std::path::{Path, PathBuf};
 
-use crate::domain::{BranchName, RepoRoot, Worktree};
+use crate::backend::{self, GitBackend, ProcessGit};
+use crate::cli_support;
+use crate::domain::{BranchName, RepoRoot, Worktree, WorktreeStatsStatus};
 use crate::error::{AppError, Result};
 use crate::git;
+use crate::metadata::{self, WorktreeKind, WorktreeMeta};
+use crate::sparse;
 use crate::symlinks;
+use crate::tmux;
+
+pub use crate::git::{is_sparse, list_worktrees, resolve_mainline, rev_exists, worktree_stats};
+
+/// Resolve the main repository root from a starting path.
+///
+/// Returns the main worktree root even when invoked from inside a linked
+/// worktree. With the `gix` feature the repository is found in-process,
+/// falling back to `git rev-parse` when gitoxide cannot open it.
+pub fn repo_root(start: &Path) -> Result<RepoRoot> {
+    backend::discover_root(start).map_or_else(|| git::repo_root(start), Ok)
+}
+
+/// [`is_sparse`] against an arbitrary [`GitBackend`].
+pub fn is_sparse_with(backend: &dyn GitBackend, path: &Path) -> bool {
+    matches!(backend.config_get(path, "core.sparseCheckout"), Ok(Some(v)) if v == "true")
+}
 
 /// Find the worktree that most specifically contains `cwd`.
 ///
@@ -34,10 +55,73 @@ fn resolve_branch_from_cwd(worktrees: &[Worktree]) -> Result<BranchName> {
     }
 }
 
+/// Find the worktree containing the current directory.
+fn current_worktree(worktrees: &[Worktree]) -> Result<&Worktree> {
+    let cwd = std::env::current_dir()
+        .map_err(|e| AppError::usage(format!("cannot determine cwd: {e}")))?;
+    let cwd = cwd.canonicalize().unwrap_or(cwd);
+    worktree_for_cwd(worktrees, &cwd)
+        .ok_or_else(|| AppError::usage("cwd is not inside a worktree".to_string()))
+}
+
+/// Options for [`add`].
+#[derive(Debug, Default)]
+pub struct AddOptions<'a> {
+    /// Base revision to branch from (defaults to `HEAD`).
+    pub base: Option<&'a str>,
+    /// Carry uncommitted changes from the current worktree into the new one.
+    pub carry: bool,
+    /// Include untracked files when carrying changes.
+    pub carry_untracked: bool,
+    /// Cone-mode sparse-checkout patterns (defaults to `.wt/sparse`).
+    pub sparse: &'a [String],
+    /// Skip submodule initialization (`--no-submodules`).
+    pub no_submodules: bool,
+}
+
+/// A checkout step run after `git worktree add`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SetupStepKind {
+    /// `git submodule update --init --recursive`.
+    Submodules,
+    /// `git lfs checkout` from the local LFS cache.
+    Lfs,
+}
+
+/// How a setup step ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SetupStepStatus {
+    /// The step completed.
+    Ok,
+    /// The step did not apply or could not run.
+    Skipped,
+    /// The step ran and failed; the worktree is kept.
+    Failed,
+}
+
+/// Outcome of one post-checkout setup step.
+///
+/// Steps are only reported when they apply, i.e. when the checkout has a
+/// `.gitmodules` file or LFS attributes.
+#[derive(Debug, Clone)]
+pub struct SetupStep {
+    /// Which step ran.
+    pub kind: SetupStepKind,
+    /// How it went.
+    pub status: SetupStepStatus,
+    /// Human-readable summary or error.
+    pub detail: String,
+}
+
 /// Result of a successful `add` operation.
 pub struct AddResult {
+    /// Path of the new worktree.
     pub worktree_path: PathBuf,
+    /// Branch checked out in the new worktree.
     pub branch: BranchName,
+    /// Root of the main worktree.
     pub repo_root: PathBuf,
     /// Whether the branch was created to track an existing remote branch.
     pub tracking: bool,
@@ -45,39 +129,240 @@ pub struct AddResult {
     pub symlinks: Option<symlinks::SymlinkReport>,
     /// Safe per-worktree setup recommendation for pnpm workspaces.
     pub setup_recommendation: Option<String>,
+    /// Sparse-checkout patterns applied (empty for a full checkout).
+    pub sparse: Vec<String>,
+    /// Files carried over from the source worktree (`--carry`).
+    pub carried: Vec<PathBuf>,
+    /// Submodule and LFS setup outcomes.
+    pub steps: Vec<SetupStep>,
+    /// Non-fatal warnings (e.g. carried changes could not be applied).
+    pub warnings: Vec<String>,
+}
+
+/// Result of a successful `move-changes` operation.
+pub struct MoveChangesResult {
+    /// Worktree the changes were taken from.
+    pub source_path: PathBuf,
+    /// Worktree the changes were applied to.
+    pub worktree_path: PathBuf,
+    /// Branch of the destination worktree.
+    pub branch: BranchName,
+    /// Root of the main worktree.
+    pub repo_root: PathBuf,
+    /// Files moved, relative to the worktree root.
+    pub files: Vec<PathBuf>,
+    /// Non-fatal warnings (e.g. the temporary stash could not be dropped).
+    pub warnings: Vec<String>,
+}
+
+/// Default refspec template for pull-request heads; `{n}` is the PR number.
+const DEFAULT_PR_REFSPEC: &str = "refs/pull/{n}/head";
+
+/// Create a review worktree for pull request `number`.
+///
+/// Fetches the PR head from the `wt.prRemote` remote (default `origin`)
+/// using the `wt.prRefspec` template (default `refs/pull/{n}/head`) into a
+/// local branch `pr/<number>`, then records the worktree as a review
+/// worktree so `prune` can remove it once the ref is gone or its TTL
+/// (`wt.reviewTtl`) has elapsed.
+pub fn add_pr(repo: &RepoRoot, number: u64, submodules: bool) -> Result<AddResult> {
+    let remote =
+        git::config_get(repo.as_ref(), "wt.prRemote")?.unwrap_or_else(|| "origin".to_string());
+    if !git::remote_exists(repo, &remote) {
+        return Err(AppError::usage(format!(
+            "remote '{remote}' does not exist (configure it with `git config wt.prRemote <remote>`)"
+        )));
+    }
+
+    let template = git::config_get(repo.as_ref(), "wt.prRefspec")?
+        .unwrap_or_else(|| DEFAULT_PR_REFSPEC.to_string());
+    let remote_ref = template.replace("{n}", &number.to_string());
+
+    let branch = BranchName::new(format!("pr/{number}"));
+    if git::branch_exists(repo, &branch) {
+        return Err(AppError::conflict(format!(
+            "branch '{branch}' already exists"
+        )));
+    }
+
+    let commit = git::fetch_ref(repo, &remote, &remote_ref).map_err(|e| {
+        AppError::git(format!(
+            "could not fetch '{remote_ref}' from '{remote}': {e}"
+        ))
+    })?;
+
+    let opts = AddOptions {
+        base: Some(&commit),
+        no_submodules: !submodules,
+        ..AddOptions::default()
+    };
+    let mut result = add(repo, &branch, &opts)?;
+
+    let meta = WorktreeMeta::review(number, remote, remote_ref);
+    if let Err(e) = metadata::write(&result.worktree_path, &meta) {
+        result
+            .warnings
+            .push(format!("worktree created but not marked for review: {e}"));
+    }
+
+    Ok(result)
+}
+
+/// Result of a successful detached `add` or `scratch` operation.
+pub struct DetachedAddResult {
+    /// Path of the new worktree.
+    pub worktree_path: PathBuf,
+    /// Root of the main worktree.
+    pub repo_root: PathBuf,
+    /// Full hash of the checked-out commit.
+    pub commit: String,
+    /// Symlink outcomes, if a `.wt/symlinks` config was present.
+    pub symlinks: Option<symlinks::SymlinkReport>,
+    /// Safe per-worktree setup recommendation for pnpm workspaces.
+    pub setup_recommendation: Option<String>,
+    /// Submodule and LFS setup outcomes.
+    pub steps: Vec<SetupStep>,
+    /// Non-fatal warnings (e.g. metadata could not be recorded).
+    pub warnings: Vec<String>,
+}
+
+/// Create a worktree with a detached `HEAD` at `rev`, for inspecting a tag
+/// or commit without creating a branch.
+pub fn add_detached(repo: &RepoRoot, rev: &str, submodules: bool) -> Result<DetachedAddResult> {
+    let dir = repo
+        .worktrees_dir()
+        .join(BranchName::new(rev).to_dir_name());
+    create_detached(repo, dir, rev, WorktreeKind::Detached, submodules)
+}
+
+/// Create a throwaway detached worktree at `rev` (default `HEAD`).
+///
+/// Scratch worktrees are auto-named `scratch-<n>` and are removed by
+/// `prune --scratch` or once older than `wt.scratchTtl`.
+pub fn scratch(repo: &RepoRoot, rev: Option<&str>, submodules: bool) -> Result<DetachedAddResult> {
+    let worktrees_dir = repo.worktrees_dir();
+    let dir = (1u32..)
+        .map(|n| worktrees_dir.join(BranchName::new(format!("scratch-{n}")).to_dir_name()))
+        .find(|dir| !dir.exists())
+        .ok_or_else(|| AppError::invariant("no free scratch worktree name".to_string()))?;
+    create_detached(
+        repo,
+        dir,
+        rev.unwrap_or("HEAD"),
+        WorktreeKind::Scratch,
+        submodules,
+    )
+}
+
+fn create_detached(
+    repo: &RepoRoot,
+    dir: PathBuf,
+    rev: &str,
+    kind: WorktreeKind,
+    submodules: bool,
+) -> Result<DetachedAddResult> {
+    if !git::rev_exists(repo, rev) {
+        return Err(AppError::git(format!("revision '{rev}' not found")));
+    }
+
+    if dir.exists() {
+        return Err(AppError::conflict(format!(
+            "worktree directory already exists: {}",
+            dir.display()
+        )));
+    }
+
+    git::add_worktree_detached(repo, &dir, rev)?;
+    let commit = git::head_commit(&dir)?;
+
+    let (steps, mut warnings) = setup_checkout(repo, &dir, submodules);
+    if let Err(e) = metadata::write(&dir, &WorktreeMeta::new(kind)) {
+        warnings.push(format!(
+            "worktree created but not recorded as intentionally detached: {e}"
+        ));
+    }
+
+    let symlink_report = symlinks::apply_symlinks(repo, &dir);
+    let setup_recommendation = symlinks::is_pnpm_workspace(repo)
+        .then(|| symlinks::pnpm_install_recommendation().to_string());
+
+    Ok(DetachedAddResult {
+        worktree_path: dir,
+        repo_root: repo.to_path_buf(),
+        commit,
+        symlinks: symlink_report,
+        setup_recommendation,
+        steps,
+        warnings,
+    })
 }
 
 /// Result of a successful `go` operation.
 pub struct GoResult {
+    /// Path of the worktree.
     pub worktree_path: PathBuf,
+    /// Branch checked out in the worktree (the commit for a detached main
+    /// worktree reached through [`MAIN_ALIASES`]).
     pub branch: BranchName,
+    /// Root of the main worktree.
     pub repo_root: PathBuf,
 }
 
 /// Result of a resolved branch-vs-mainline `diff` operation.
 pub struct DiffResult {
+    /// Branch being compared.
     pub branch: BranchName,
+    /// Revision the branch is compared against.
     pub base: String,
+    /// The difftool command line (`git difftool ...`).
     pub command: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Which uncommitted changes a dirty-worktree `diff` shows.
 pub enum DirtyDiffMode {
+    /// Staged and unstaged changes against `HEAD`.
     Dirty,
+    /// Staged changes only.
     Staged,
+    /// Unstaged changes only.
     Unstaged,
 }
 
 /// Result of a resolved dirty-worktree `diff` operation.
 pub struct DirtyDiffResult {
+    /// Description of the compared changes.
     pub label: String,
+    /// The difftool command line (`git difftool ...`).
+    pub command: Vec<String>,
+}
+
+/// Result of a resolved `open` operation.
+pub struct OpenResult {
+    /// Worktree being opened.
+    pub worktree_path: PathBuf,
+    /// The command line, with `{path}` substituted.
     pub command: Vec<String>,
 }
 
+impl OpenResult {
+    /// The command line quoted for a POSIX shell.
+    pub fn display_command(&self) -> String {
+        self.command
+            .iter()
+            .map(|arg| shell_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
 /// Result of a successful `remove` operation.
 pub struct RemoveResult {
+    /// Path of the removed worktree.
     pub removed_path: PathBuf,
+    /// Branch that was checked out in it.
     pub branch: BranchName,
+    /// Root of the main worktree.
     pub repo_root: PathBuf,
     /// Non-fatal warning (e.g. branch deletion failed after worktree removal).
     pub warning: Option<String>,
@@ -86,15 +371,99 @@ pub struct RemoveResult {
 /// Diagnostic from the `doctor` command.
 #[derive(Debug)]
 pub struct Diagnostic {
+    /// Severity of the finding.
     pub level: DiagLevel,
+    /// Stable identifier of the finding.
+    pub code: DiagCode,
+    /// Human-readable description.
     pub message: String,
+    /// Worktree or directory the finding applies to.
+    pub path: Option<PathBuf>,
+    /// Suggested shell command that resolves the finding.
+    pub fix: Option<String>,
+    /// Whether `doctor --fix` can repair the finding.
+    pub fixable: bool,
+}
+
+impl Diagnostic {
+    fn new(level: DiagLevel, code: DiagCode, message: String) -> Self {
+        Self {
+            level,
+            code,
+            message,
+            path: None,
+            fix: None,
+            fixable: false,
+        }
+    }
+
+    fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    fn with_fix(mut self, fix: String) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    fn fixable(mut self) -> Self {
+        self.fixable = true;
+        self
+    }
+}
+
+/// Stable identifier of a doctor finding, for scripts and `--fix`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagCode {
+    /// There is no `.worktrees/` directory yet.
+    NoWorktrees,
+    /// No problems were found.
+    Healthy,
+    /// `.worktrees/` is not ignored by git.
+    WorktreesNotIgnored,
+    /// A directory under `.worktrees/` is not a registered worktree.
+    OrphanedDirectory,
+    /// A registered worktree's directory no longer exists.
+    MissingDirectory,
+    /// A worktree has a detached `HEAD` it was not created with.
+    DetachedHead,
+    /// A configured shared entry is missing.
+    LinkMissing,
+    /// A shared link's target was removed from the main worktree.
+    LinkDangling,
+    /// A shared link points somewhere other than the main worktree's copy.
+    LinkWrongTarget,
+    /// A shared symlink was replaced by a real file or directory.
+    LinkReplaced,
+    /// A shared link is tracked by git.
+    LinkTracked,
+    /// The installed Git is older than the supported minimum.
+    GitTooOld,
+    /// `origin/HEAD` is missing or does not resolve.
+    OriginHeadMissing,
+    /// A worktree has a leftover `index.lock`.
+    StaleIndexLock,
+    /// A merge, rebase, cherry-pick or revert is in progress.
+    OperationInProgress,
+    /// A worktree lives outside `.worktrees/`.
+    OutsideManagedRoot,
+    /// A worktree's directory name does not match its branch.
+    DirNameMismatch,
+    /// A worktree branch's upstream was deleted on the remote.
+    UpstreamGone,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 #[serde(rename_all = "lowercase")]
+/// Severity of a doctor finding.
 pub enum DiagLevel {
+    /// Informational; nothing to do.
     Ok,
+    /// Something is off but wt keeps working.
     Warn,
+    /// Something is broken.
     Error,
 }
 
@@ -105,6 +474,17 @@ pub fn diff(
     against: Option<&str>,
     tool: Option<&str>,
     dry_run: bool,
+) -> Result<DiffResult> {
+    diff_impl(repo, branch, against, tool, dry_run, false)
+}
+
+pub(crate) fn diff_impl(
+    repo: &RepoRoot,
+    branch: &BranchName,
+    against: Option<&str>,
+    tool: Option<&str>,
+    dry_run: bool,
+    stdout_to_stderr: bool,
 ) -> Result<DiffResult> {
     let worktrees = git::list_worktrees(repo)?;
     let has_worktree = worktrees
@@ -134,7 +514,7 @@ pub fn diff(
 
     if !dry_run {
         git::ensure_difftool_available(repo.as_ref(), tool)?;
-        git::difftool(repo, tool, &range)?;
+        git::difftool(repo, tool, &range, stdout_to_stderr)?;
     }
 
     Ok(DiffResult {
@@ -150,12 +530,22 @@ pub fn diff_dirty(
     mode: DirtyDiffMode,
     tool: Option<&str>,
     dry_run: bool,
+) -> Result<DirtyDiffResult> {
+    diff_dirty_impl(worktree, mode, tool, dry_run, false)
+}
+
+pub(crate) fn diff_dirty_impl(
+    worktree: &Worktree,
+    mode: DirtyDiffMode,
+    tool: Option<&str>,
+    dry_run: bool,
+    stdout_to_stderr: bool,
 ) -> Result<DirtyDiffResult> {
     let command = dirty_difftool_command(&worktree.path, mode, tool);
 
     if !dry_run {
         git::ensure_difftool_available(&worktree.path, tool)?;
-        git::difftool_dirty(&worktree.path, mode, tool)?;
+        git::difftool_dirty(&worktree.path, mode, tool, stdout_to_stderr)?;
     }
 
     Ok(DirtyDiffResult {
@@ -206,6 +596,213 @@ fn base_difftool_command(path: &std::path::Path, tool: Option<&str>) -> Vec<Stri
     command
 }
 
+/// Resolve and optionally run the command that opens `worktree_path`.
+///
+/// The command template comes from `editor`, then `git config
+/// wt.openCommand`, then `$VISUAL` and `$EDITOR`. Editor output goes to
+/// stderr so stdout stays usable for `--print-cd-path` and `--json`.
+pub fn open(worktree_path: &Path, editor: Option<&str>, dry_run: bool) -> Result<OpenResult> {
+    let template = match editor {
+        Some(editor) => editor.to_string(),
+        None => open_template(worktree_path)?,
+    };
+    let command = open_command(&template, worktree_path)?;
+
+    if !dry_run {
+        run_open_command(&command, worktree_path)?;
+    }
+
+    Ok(OpenResult {
+        worktree_path: worktree_path.to_path_buf(),
+        command,
+    })
+}
+
+/// Run the editor command in the worktree, failing on a non-zero exit.
+fn run_open_command(command: &[String], worktree_path: &Path) -> Result<()> {
+    let status = std::process::Command::new(&command[0])
+        .args(&command[1..])
+        .current_dir(worktree_path)
+        .stdout(std::io::stderr())
+        .status()
+        .map_err(|e| AppError::usage(format!("failed to run '{}': {e}", command[0])))?;
+    if !status.success() {
+        return Err(AppError::usage(format!(
+            "'{}' failed with status {status}",
+            command[0]
+        )));
+    }
+    Ok(())
+}
+
+fn open_template(worktree_path: &Path) -> Result<String> {
+    if let Some(template) = git::config_get(worktree_path, "wt.openCommand")? {
+        return Ok(template);
+    }
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .ok_or_else(|| {
+            AppError::usage(
+                "no editor configured: pass --editor, set `git config wt.openCommand` \
+                 or $VISUAL/$EDITOR"
+                    .to_string(),
+            )
+        })
+}
+
+/// Split `template` into a command line and substitute `{path}`.
+///
+/// A template without `{path}`, such as a plain `$EDITOR` value, gets the
+/// path appended as its last argument.
+pub fn open_command(template: &str, worktree_path: &Path) -> Result<Vec<String>> {
+    let words = shell_words::split(template)
+        .map_err(|e| AppError::usage(format!("invalid open command '{template}': {e}")))?;
+    if words.is_empty() {
+        return Err(AppError::usage(
+            "open command must not be empty".to_string(),
+        ));
+    }
+
+    let path = worktree_path.display().to_string();
+    let has_placeholder = words.iter().any(|w| w.contains("{path}"));
+    let mut command: Vec<String> = words
+        .into_iter()
+        .map(|w| w.replace("{path}", &path))
+        .collect();
+    if !has_placeholder {
+        command.push(path);
+    }
+    Ok(command)
+}
+
+/// Undo a failed stash apply in a worktree that was clean beforehand.
+fn rollback_carry(target: &Path, untracked: &[&PathBuf]) {
+    let _ = git::reset_hard(target);
+    for path in untracked {
+        let _ = std::fs::remove_file(target.join(path));
+    }
+}
+
+/// Move uncommitted changes from `source` into the clean worktree `target`.
+///
+/// Changes are stashed under a descriptive message, applied in `target`
+/// (preserving the staged state), and the stash is dropped only after a
+/// successful apply. If the apply fails, `target` is rolled back, the
+/// changes are restored in `source`, and the stash is kept as a safety net.
+///
+/// Returns the moved files and any non-fatal warnings; an empty file list
+/// means there was nothing to move.
+fn carry_changes(
+    source: &Path,
+    target: &Path,
+    target_branch: &str,
+    include_untracked: bool,
+) -> Result<(Vec<PathBuf>, Vec<String>)> {
+    let changes = git::changed_files(source, include_untracked)?;
+    if changes.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    if git::is_dirty(target)? {
+        return Err(AppError::conflict(format!(
+            "worktree for '{target_branch}' has uncommitted changes; commit or stash them first"
+        )));
+    }
+
+    let untracked: Vec<&PathBuf> = changes
+        .iter()
+        .filter(|c| c.untracked)
+        .map(|c| &c.path)
+        .collect();
+    let existing = untracked
+        .iter()
+        .find(|p| target.join(p).symlink_metadata().is_ok());
+    if let Some(path) = existing {
+        return Err(AppError::conflict(format!(
+            "untracked file '{}' already exists in worktree for '{target_branch}'",
+            path.display()
+        )));
+    }
+
+    let message = format!("wt move-changes: {} -> {target_branch}", source.display());
+    let stash = git::stash_push(source, &message, include_untracked)?;
+
+    if let Err(e) = git::stash_apply(target, &stash) {
+        rollback_carry(target, &untracked);
+        let kept = match git::stash_apply(source, &stash) {
+            Ok(()) => "changes were restored in the source worktree and kept",
+            Err(_) => "changes were kept",
+        };
+        let selector = git::stash_selector(source, &stash).unwrap_or_else(|| stash.clone());
+        return Err(AppError::conflict(format!(
+            "could not apply changes in worktree for '{target_branch}'; {kept} in stash '{message}' ({selector})\n{e}"
+        )));
+    }
+
+    let mut warnings = Vec::new();
+    if let Err(e) = git::stash_drop(source, &stash) {
+        warnings.push(format!(
+            "changes moved but temporary stash was not dropped: {e}"
+        ));
+    }
+
+    let files = changes.into_iter().map(|c| c.path).collect();
+    Ok((files, warnings))
+}
+
+/// The error for a `move-changes` that found no changes.
+fn nothing_to_move(include_untracked: bool) -> AppError {
+    let hint = if include_untracked {
+        ""
+    } else {
+        " (use --include-untracked to move untracked files)"
+    };
+    AppError::usage(format!("no uncommitted changes to move{hint}"))
+}
+
+/// Move uncommitted changes from the current worktree into the worktree
+/// for `branch`.
+pub fn move_changes(
+    repo: &RepoRoot,
+    branch: &BranchName,
+    include_untracked: bool,
+) -> Result<MoveChangesResult> {
+    let worktrees = git::list_worktrees(repo)?;
+    let source = current_worktree(&worktrees)?;
+
+    let target = worktrees
+        .iter()
+        .find(|wt| wt.branch.as_deref() == Some(branch.as_str()))
+        .ok_or_else(|| AppError::usage(format!("no worktree found for branch '{branch}'")))?;
+
+    if source.path == target.path {
+        return Err(AppError::usage(format!(
+            "already in the worktree for branch '{branch}'"
+        )));
+    }
+
+    let (files, warnings) = carry_changes(
+        &source.path,
+        &target.path,
+        branch.as_str(),
+        include_untracked,
+    )?;
+    if files.is_empty() {
+        return Err(nothing_to_move(include_untracked));
+    }
+
+    Ok(MoveChangesResult {
+        source_path: source.path.clone(),
+        worktree_path: target.path.clone(),
+        branch: branch.clone(),
+        repo_root: repo.to_path_buf(),
+        files,
+        warnings,
+    })
+}
+
 /// Create a new worktree for the given branch.
 ///
 /// When `base` is `None` and the branch does not exist locally but does
@@ -214,7 +811,14 @@ fn base_difftool_command(path: &std::path::Path, tool: Option<&str>) -> Vec<Stri
 ///
 /// When `base` is provided, a new branch is always created from that
 /// revision (remote tracking is skipped).
-pub fn add(repo: &RepoRoot, branch: &BranchName, base: Option<&str>) -> Result<AddResult> {
+///
+/// With `carry`, uncommitted changes in the current worktree are moved into
+/// the new worktree, which then defaults to branching from the current
+/// worktree's `HEAD`. A failed carry leaves the changes in place and is
+/// reported as a warning; the new worktree is kept.
+pub fn add(repo: &RepoRoot, branch: &BranchName, opts: &AddOptions) -> Result<AddResult> {
+    let base = opts.base;
+
     // Refuse if branch already exists locally.
     if git::branch_exists(repo, branch) {
         return Err(AppError::conflict(format!(
@@ -237,24 +841,63 @@ pub fn add(repo: &RepoRoot, branch: &BranchName, base: Option<&str>) -> Result<A
         )));
     }
 
+    let carry_source = if opts.carry {
+        let worktrees = git::list_worktrees(repo)?;
+        Some(current_worktree(&worktrees)?.path.clone())
+    } else {
+        None
+    };
+
     // Determine whether to track a remote branch:
     // - Only when no explicit --base is provided
     // - Only when origin/<branch> exists
     let tracking = base.is_none() && git::remote_branch_exists(repo, branch);
 
-    let effective_base = if tracking {
-        Some(format!("origin/{}", branch.as_str()))
+    let effective_base = match (&carry_source, tracking) {
+        (_, true) => Some(format!("origin/{}", branch.as_str())),
+        // Carried changes apply cleanly only on top of the commit they were
+        // made against, so branch from the source worktree's HEAD.
+        (Some(source), false) if base.is_none() => Some(git::head_commit(source)?),
+        _ => None,
+    };
+
+    let sparse_patterns = if opts.sparse.is_empty() {
+        sparse::load_patterns(repo, branch)
     } else {
-        None
+        opts.sparse.to_vec()
     };
 
-    git::add_worktree(repo, &wt_dir, branch, effective_base.as_deref().or(base))?;
+    let start = effective_base.as_deref().or(base);
+    if sparse_patterns.is_empty() {
+        git::add_worktree(repo, &wt_dir, branch, start)?;
+    } else {
+        add_sparse_worktree(repo, &wt_dir, branch, start, &sparse_patterns)?;
+    }
 
     // Set upstream so `git pull`/`git push` work without arguments.
     if tracking {
         git::set_upstream(repo, branch)?;
     }
 
+    let (steps, mut warnings) = setup_checkout(repo, &wt_dir, !opts.no_submodules);
+    let carried = match &carry_source {
+        Some(source) => {
+            match carry_changes(source, &wt_dir, branch.as_str(), opts.carry_untracked) {
+                Ok((files, carry_warnings)) => {
+                    warnings.extend(carry_warnings);
+                    files
+                }
+                Err(e) => {
+                    warnings.push(format!(
+                        "worktree created but changes were not carried: {e}"
+                    ));
+                    Vec::new()
+                }
+            }
+        }
+        None => Vec::new(),
+    };
+
     let symlink_report = symlinks::apply_symlinks(repo, &wt_dir);
     let setup_recommendation = symlinks::is_pnpm_workspace(repo)
         .then(|| symlinks::pnpm_install_recommendation().to_string());
@@ -266,43 +909,720 @@ pub fn add(repo: &RepoRoot, branch: &BranchName, base: Option<&str>) -> Result<A
         tracking,
         symlinks: symlink_report,
         setup_recommendation,
+        sparse: sparse_patterns,
+        carried,
+        steps,
+        warnings,
     })
 }
 
-/// Resolve and return the path of an existing worktree for the given branch.
-pub fn go(repo: &RepoRoot, branch: &BranchName) -> Result<GoResult> {
-    let worktrees = git::list_worktrees(repo)?;
+/// Create a worktree whose files are populated only after cone-mode sparse
+/// checkout is configured, so excluded paths are never written to disk.
+///
+/// If configuring sparse checkout fails, the half-created worktree and its
+/// branch are removed again.
+fn add_sparse_worktree(
+    repo: &RepoRoot,
+    dir: &Path,
+    branch: &BranchName,
+    base: Option<&str>,
+    patterns: &[String],
+) -> Result<()> {
+    git::add_worktree_no_checkout(repo, dir, branch, base)?;
+
+    let populated = git::sparse_checkout_set(dir, patterns).and_then(|()| git::checkout_head(dir));
+    if let Err(e) = populated {
+        let _ = git::remove_worktree(repo, dir, true);
+        let _ = git::delete_branch(repo, branch, true);
+        return Err(AppError::git(format!(
+            "failed to set up sparse checkout: {e}"
+        )));
+    }
+    Ok(())
+}
 
-    let found = worktrees
-        .iter()
-        .find(|wt| wt.branch.as_deref() == Some(branch.as_str()));
+/// Initialize submodules and LFS content in a freshly checked-out worktree.
+///
+/// Failures never undo the worktree; they are reported as failed steps plus
+/// warnings so the user can finish the setup by hand.
+fn setup_checkout(repo: &RepoRoot, dir: &Path, submodules: bool) -> (Vec<SetupStep>, Vec<String>) {
+    let steps: Vec<SetupStep> = [submodule_step(repo, dir, submodules), lfs_step(dir)]
+        .into_iter()
+        .flatten()
+        .collect();
+    let warnings = steps.iter().filter_map(step_warning).collect();
+    (steps, warnings)
+}
 
-    match found {
-        Some(wt) => Ok(GoResult {
-            worktree_path: wt.path.clone(),
-            branch: branch.clone(),
-            repo_root: repo.to_path_buf(),
-        }),
-        None => Err(AppError::usage(format!(
-            "no worktree found for branch '{branch}'"
-        ))),
+/// The warning for a step that did not complete, telling how to finish it.
+fn step_warning(step: &SetupStep) -> Option<String> {
+    match (step.kind, step.status) {
+        (SetupStepKind::Submodules, SetupStepStatus::Failed) => Some(format!(
+            "submodules were not initialized: {} (run `git submodule update --init --recursive`)",
+            step.detail
+        )),
+        (SetupStepKind::Lfs, SetupStepStatus::Failed | SetupStepStatus::Skipped) => {
+            Some(format!("LFS files were left as pointers: {}", step.detail))
+        }
+        _ => None,
     }
 }
 
-/// Remove a worktree and delete its local branch.
-pub fn remove(repo: &RepoRoot, branch: Option<&BranchName>, force: bool) -> Result<RemoveResult> {
-    let worktrees = git::list_worktrees(repo)?;
+/// The submodule step, or `None` when the worktree declares no submodules.
+fn submodule_step(repo: &RepoRoot, dir: &Path, submodules: bool) -> Option<SetupStep> {
+    let declared = git::submodules(dir);
+    if declared.is_empty() {
+        return None;
+    }
+    if !submodules {
+        return Some(SetupStep {
+            kind: SetupStepKind::Submodules,
+            status: SetupStepStatus::Skipped,
+            detail: "disabled by --no-submodules".to_string(),
+        });
+    }
+    Some(init_submodules(repo, dir, &declared))
+}
 
-    // Resolve which branch to remove.
-    let target_branch = match branch {
-        Some(b) => b.clone(),
-        None => resolve_branch_from_cwd(&worktrees)?,
-    };
+/// The LFS step, or `None` when the worktree does not use LFS.
+fn lfs_step(dir: &Path) -> Option<SetupStep> {
+    git::uses_lfs(dir).then(|| lfs_checkout(dir))
+}
 
-    // Find the worktree entry.
-    let wt = worktrees
-        .iter()
-        .find(|wt| wt.branch.as_deref() == Some(target_branch.as_str()))
+/// Run `submodule update` for each declared submodule, borrowing objects
+/// from the main worktree's clone of it when one exists.
+///
+/// Set `wt.submoduleReference` to `false` to always clone from the
+/// submodule's URL.
+fn init_submodules(repo: &RepoRoot, dir: &Path, declared: &[(String, String)]) -> SetupStep {
+    let use_reference = !matches!(
+        git::config_get(repo.as_ref(), "wt.submoduleReference"),
+        Ok(Some(v)) if v == "false"
+    );
+    let modules_dir = git::worktree_git_dir(repo.as_ref())
+        .map(|d| d.join("modules"))
+        .ok()
+        .filter(|_| use_reference);
+
+    let mut reused = 0;
+    let mut errors = Vec::new();
+    for (name, sub_path) in declared {
+        let reference = modules_dir
+            .as_ref()
+            .map(|d| d.join(name))
+            .filter(|d| d.is_dir());
+        match git::submodule_update(dir, sub_path, reference.as_deref()) {
+            Ok(()) => reused += usize::from(reference.is_some()),
+            Err(e) => errors.push(format!("{sub_path}: {e}")),
+        }
+    }
+
+    let initialized = declared.len() - errors.len();
+    if !errors.is_empty() {
+        return SetupStep {
+            kind: SetupStepKind::Submodules,
+            status: SetupStepStatus::Failed,
+            detail: errors.join("; "),
+        };
+    }
+    let noun = if initialized == 1 {
+        "submodule"
+    } else {
+        "submodules"
+    };
+    let detail = if reused > 0 {
+        format!("Initialized {initialized} {noun} ({reused} from local objects)")
+    } else {
+        format!("Initialized {initialized} {noun}")
+    };
+    SetupStep {
+        kind: SetupStepKind::Submodules,
+        status: SetupStepStatus::Ok,
+        detail,
+    }
+}
+
+fn lfs_checkout(dir: &Path) -> SetupStep {
+    let (status, detail) = if !git::lfs_available(dir) {
+        (
+            SetupStepStatus::Skipped,
+            "git-lfs is not installed".to_string(),
+        )
+    } else {
+        match git::lfs_checkout(dir) {
+            Ok(()) => (
+                SetupStepStatus::Ok,
+                "Checked out LFS files from the local cache".to_string(),
+            ),
+            Err(e) => (SetupStepStatus::Failed, e.to_string()),
+        }
+    };
+    SetupStep {
+        kind: SetupStepKind::Lfs,
+        status,
+        detail,
+    }
+}
+
+/// Which worktrees `wt link` refreshes.
+#[derive(Debug, Clone, Copy)]
+pub enum LinkTarget<'a> {
+    /// The worktree containing the current directory.
+    Current,
+    /// The worktree of this branch.
+    Branch(&'a BranchName),
+    /// Every non-main worktree.
+    All,
+}
+
+/// Per-worktree result of `wt link`.
+pub struct LinkReport {
+    /// Branch of the worktree, or `None` when detached.
+    pub branch: Option<String>,
+    /// Path of the worktree.
+    pub worktree_path: PathBuf,
+    /// What changed (or would change) in the worktree.
+    pub outcomes: Vec<symlinks::SymlinkOutcome>,
+}
+
+/// Re-apply `.wt/symlinks` to existing worktrees.
+///
+/// See [`symlinks::refresh_links`] for what is changed. The main worktree is
+/// the link source and cannot be targeted.
+pub fn link(
+    repo: &RepoRoot,
+    target: LinkTarget,
+    dry_run: bool,
+    prune: bool,
+) -> Result<Vec<LinkReport>> {
+    let worktrees = git::list_worktrees(repo)?;
+    let selected: Vec<&Worktree> = match target {
+        LinkTarget::All => worktrees.iter().filter(|wt| !wt.is_main).collect(),
+        LinkTarget::Branch(branch) => vec![branch_worktree(&worktrees, branch)?],
+        LinkTarget::Current => vec![current_worktree(&worktrees)?],
+    };
+    if selected.iter().any(|wt| wt.is_main) {
+        return Err(AppError::usage(
+            "the main worktree is the link source; pass a branch or --all".to_string(),
+        ));
+    }
+
+    Ok(selected
+        .into_iter()
+        .map(|wt| LinkReport {
+            branch: wt.branch.clone(),
+            worktree_path: wt.path.clone(),
+            outcomes: symlinks::refresh_links(repo, &wt.path, dry_run, prune),
+        })
+        .collect())
+}
+
+/// Which worktrees `wt adopt` relocates.
+#[derive(Debug, Clone, Copy)]
+pub enum AdoptTarget<'a> {
+    /// The worktree containing the current directory.
+    Current,
+    /// The worktree containing this path.
+    Path(&'a Path),
+    /// Every worktree not at its managed path.
+    All,
+}
+
+/// What `wt adopt` did with one worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdoptStatus {
+    /// Moved (or, in a dry run, would be moved) to the managed path.
+    Moved,
+    /// Already at the managed path.
+    Unchanged,
+    /// `git worktree move` refused; only reported with [`AdoptTarget::All`].
+    Failed(String),
+}
+
+/// Per-worktree result of `wt adopt`.
+pub struct AdoptReport {
+    /// Branch of the worktree, or `None` when detached.
+    pub branch: Option<String>,
+    /// Where the worktree was.
+    pub from: PathBuf,
+    /// Its managed path.
+    pub to: PathBuf,
+    /// What happened.
+    pub status: AdoptStatus,
+    /// Shared-entry changes applied at the new location.
+    pub links: Vec<symlinks::SymlinkOutcome>,
+}
+
+/// Managed location of a worktree: `.worktrees/<slug>--<hash>` of its
+/// branch, or of its commit when detached (as `add --detach` names them).
+fn managed_path(repo: &RepoRoot, wt: &Worktree) -> PathBuf {
+    let name = wt.branch.as_deref().unwrap_or(&wt.commit);
+    repo.worktrees_dir()
+        .join(BranchName::new(name).to_dir_name())
+}
+
+/// Whether `wt` already follows the `.worktrees/` convention. Detached
+/// worktrees inside `.worktrees/` are named after the revision they were
+/// created from, so any name is accepted for them.
+fn is_managed(repo: &RepoRoot, wt: &Worktree) -> bool {
+    match wt.branch {
+        Some(_) => wt.path == managed_path(repo, wt),
+        None => wt.path.parent() == Some(repo.worktrees_dir().as_path()),
+    }
+}
+
+/// Relocate worktrees created outside wt (e.g. `git worktree add ../foo`)
+/// to their managed path with `git worktree move`, then apply
+/// `.wt/symlinks` there.
+///
+/// With `dry_run`, nothing is moved and `links` previews the shared entries
+/// at the current location. A single target whose move fails is an error;
+/// with [`AdoptTarget::All`] failures are reported per worktree.
+pub fn adopt(repo: &RepoRoot, target: AdoptTarget, dry_run: bool) -> Result<Vec<AdoptReport>> {
+    let worktrees = git::list_worktrees(repo)?;
+    let selected: Vec<&Worktree> = match target {
+        AdoptTarget::All => worktrees
+            .iter()
+            .filter(|wt| !wt.is_main && !is_managed(repo, wt))
+            .collect(),
+        AdoptTarget::Path(path) => {
+            let path = path
+                .canonicalize()
+                .map_err(|e| AppError::usage(format!("{}: {e}", path.display())))?;
+            vec![worktree_for_cwd(&worktrees, &path).ok_or_else(|| {
+                AppError::usage(format!(
+                    "not a worktree of this repository: {}",
+                    path.display()
+                ))
+            })?]
+        }
+        AdoptTarget::Current => vec![current_worktree(&worktrees)?],
+    };
+    if selected.iter().any(|wt| wt.is_main) {
+        return Err(AppError::usage(
+            "the main worktree cannot be adopted; pass a worktree path or --all".to_string(),
+        ));
+    }
+
+    let keep_going = matches!(target, AdoptTarget::All);
+    selected
+        .into_iter()
+        .map(|wt| adopt_one(repo, wt, dry_run, keep_going))
+        .collect()
+}
+
+/// Move one worktree to its managed path; with `keep_going`, a failed move
+/// is reported instead of returned.
+fn adopt_one(
+    repo: &RepoRoot,
+    wt: &Worktree,
+    dry_run: bool,
+    keep_going: bool,
+) -> Result<AdoptReport> {
+    let to = managed_path(repo, wt);
+    let mut report = AdoptReport {
+        branch: wt.branch.clone(),
+        from: wt.path.clone(),
+        to: to.clone(),
+        status: AdoptStatus::Moved,
+        links: Vec::new(),
+    };
+    if is_managed(repo, wt) {
+        report.to = wt.path.clone();
+        report.status = AdoptStatus::Unchanged;
+    } else if dry_run {
+        report.links = symlinks::refresh_links(repo, &wt.path, true, false);
+    } else {
+        match move_worktree(repo, &wt.path, &to) {
+            Ok(()) => report.links = symlinks::refresh_links(repo, &to, false, false),
+            Err(e) if keep_going => report.status = AdoptStatus::Failed(e.to_string()),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(report)
+}
+
+fn move_worktree(repo: &RepoRoot, from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        return Err(AppError::conflict(format!(
+            "worktree directory already exists: {}",
+            to.display()
+        )));
+    }
+    std::fs::create_dir_all(repo.worktrees_dir()).map_err(|e| {
+        AppError::git(format!(
+            "failed to create {}: {e}",
+            repo.worktrees_dir().display()
+        ))
+    })?;
+    git::move_worktree(repo, from, to)
+}
+
+/// How `wt sparse` changes a worktree's sparse-checkout patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SparseUpdate {
+    /// Add patterns to an already sparse worktree.
+    Add,
+    /// Replace the patterns, enabling sparse checkout if needed.
+    Set,
+}
+
+/// Sparse-checkout state of a worktree.
+pub struct SparseResult {
+    /// Branch of the worktree.
+    pub branch: BranchName,
+    /// Path of the worktree.
+    pub worktree_path: PathBuf,
+    /// Whether sparse checkout is enabled.
+    pub sparse: bool,
+    /// Cone-mode patterns (empty when `sparse` is false).
+    pub patterns: Vec<String>,
+}
+
+fn branch_worktree<'a>(worktrees: &'a [Worktree], branch: &BranchName) -> Result<&'a Worktree> {
+    worktrees
+        .iter()
+        .find(|wt| wt.branch.as_deref() == Some(branch.as_str()))
+        .ok_or_else(|| AppError::usage(format!("no worktree found for branch '{branch}'")))
+}
+
+fn sparse_state(branch: &BranchName, path: &Path) -> Result<SparseResult> {
+    let sparse = git::is_sparse(path);
+    let patterns = if sparse {
+        git::sparse_checkout_list(path)?
+    } else {
+        Vec::new()
+    };
+    Ok(SparseResult {
+        branch: branch.clone(),
+        worktree_path: path.to_path_buf(),
+        sparse,
+        patterns,
+    })
+}
+
+/// Report the sparse-checkout patterns of the worktree for `branch`.
+pub fn sparse_list(repo: &RepoRoot, branch: &BranchName) -> Result<SparseResult> {
+    let worktrees = git::list_worktrees(repo)?;
+    let wt = branch_worktree(&worktrees, branch)?;
+    sparse_state(branch, &wt.path)
+}
+
+/// Add or replace the sparse-checkout patterns of the worktree for `branch`.
+pub fn sparse_update(
+    repo: &RepoRoot,
+    branch: &BranchName,
+    update: SparseUpdate,
+    patterns: &[String],
+) -> Result<SparseResult> {
+    let worktrees = git::list_worktrees(repo)?;
+    let wt = branch_worktree(&worktrees, branch)?;
+
+    match update {
+        SparseUpdate::Add if !git::is_sparse(&wt.path) => {
+            return Err(AppError::usage(format!(
+                "worktree for '{branch}' is not sparse; use `wt sparse set` to enable sparse checkout"
+            )));
+        }
+        SparseUpdate::Add => git::sparse_checkout_add(&wt.path, patterns)?,
+        SparseUpdate::Set => git::sparse_checkout_set(&wt.path, patterns)?,
+    }
+
+    sparse_state(branch, &wt.path)
+}
+
+/// Resolve and return the path of an existing worktree for the given branch.
+///
+/// `branch` may also be any identifier [`match_worktrees`] accepts; it must
+/// resolve to exactly one worktree.
+pub fn go(repo: &RepoRoot, branch: &BranchName) -> Result<GoResult> {
+    go_with(&ProcessGit, repo, branch)
+}
+
+/// [`go`] against an arbitrary [`GitBackend`].
+pub fn go_with(backend: &dyn GitBackend, repo: &RepoRoot, branch: &BranchName) -> Result<GoResult> {
+    let worktrees = backend.list_worktrees(repo)?;
+
+    match match_worktrees(&worktrees, branch.as_str()).as_slice() {
+        [] => Err(AppError::usage(format!(
+            "no worktree found for branch '{branch}'"
+        ))),
+        [wt] => Ok(GoResult {
+            worktree_path: wt.path.clone(),
+            branch: BranchName::new(wt.branch.as_deref().unwrap_or(&wt.commit)),
+            repo_root: repo.to_path_buf(),
+        }),
+        matches => {
+            let candidates: Vec<_> = matches
+                .iter()
+                .filter_map(|wt| wt.branch.as_deref())
+                .map(|b| format!("  {b}"))
+                .collect();
+            Err(AppError::usage(format!(
+                "'{branch}' matches {} worktrees:\n{}",
+                matches.len(),
+                candidates.join("\n")
+            )))
+        }
+    }
+}
+
+/// Queries that always identify the main worktree.
+pub const MAIN_ALIASES: [&str; 2] = ["@main", "^"];
+
+/// Branch worktrees identified by `query`, best match first.
+///
+/// Any of [`MAIN_ALIASES`] selects the main worktree, even when detached.
+/// Otherwise matching is tiered, and the first tier with any match wins:
+/// 1. exact — the branch name, the directory name (slug), or a path to the
+///    worktree or a trailing fragment of it such as `.worktrees/feat--1a2b`;
+/// 2. substring of the branch or directory name;
+/// 3. fuzzy, scored like the interactive picker's filter.
+///
+/// Detached worktrees are never matched.
+pub fn match_worktrees<'a>(worktrees: &'a [Worktree], query: &str) -> Vec<&'a Worktree> {
+    use fuzzy_matcher::skim::SkimMatcherV2;
+    use fuzzy_matcher::FuzzyMatcher;
+
+    if MAIN_ALIASES.contains(&query) {
+        return worktrees.iter().filter(|wt| wt.is_main).collect();
+    }
+
+    let candidates: Vec<(&Worktree, &str, &str)> = worktrees
+        .iter()
+        .filter_map(|wt| {
+            let branch = wt.branch.as_deref()?;
+            let dir = wt.path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            Some((wt, branch, dir))
+        })
+        .collect();
+
+    let query_path = Path::new(query);
+    let resolved = query_path.canonicalize().ok();
+    let exact: Vec<_> = candidates
+        .iter()
+        .filter(|(wt, branch, dir)| {
+            *branch == query
+                || *dir == query
+                || resolved
+                    .as_ref()
+                    .is_some_and(|r| wt.path.canonicalize().is_ok_and(|p| &p == r))
+                || (query.contains(std::path::is_separator) && wt.path.ends_with(query_path))
+        })
+        .map(|(wt, ..)| *wt)
+        .collect();
+    if !exact.is_empty() {
+        return exact;
+    }
+
+    let substring: Vec<_> = candidates
+        .iter()
+        .filter(|(_, branch, dir)| branch.contains(query) || dir.contains(query))
+        .map(|(wt, ..)| *wt)
+        .collect();
+    if !substring.is_empty() {
+        return substring;
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<_> = candidates
+        .iter()
+        .filter_map(|(wt, branch, dir)| {
+            let score = matcher
+                .fuzzy_match(branch, query)
+                .max(matcher.fuzzy_match(dir, query))?;
+            Some((score, *wt))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, wt)| wt).collect()
+}
+
+/// A worktree from the navigation history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentWorktree {
+    /// Path of the worktree.
+    pub path: PathBuf,
+    /// Branch checked out, or `None` when detached.
+    pub branch: Option<String>,
+    /// Unix timestamp (seconds) of the last visit.
+    pub visited_at: u64,
+}
+
+/// Visited worktrees, most recent first.
+///
+/// Visits are recorded as `wt` navigates; entries whose worktree no longer
+/// exists are skipped.
+pub fn recent(repo: &RepoRoot) -> Result<Vec<RecentWorktree>> {
+    let worktrees = backend::detect(repo).list_worktrees(repo)?;
+    Ok(metadata::read_visits(repo.as_ref())
+        .into_iter()
+        .filter_map(|visit| {
+            let wt = worktrees.iter().find(|wt| wt.path == visit.path)?;
+            Some(RecentWorktree {
+                path: visit.path,
+                branch: wt.branch.clone(),
+                visited_at: visit.visited_at,
+            })
+        })
+        .collect())
+}
+
+/// Resolve the most recently visited worktree other than `current`, like
+/// `cd -`.
+pub fn go_back(repo: &RepoRoot, current: Option<&Path>) -> Result<GoResult> {
+    recent(repo)?
+        .into_iter()
+        .filter(|wt| Some(wt.path.as_path()) != current)
+        .find_map(|wt| {
+            Some(GoResult {
+                branch: BranchName::new(wt.branch?),
+                worktree_path: wt.path,
+                repo_root: repo.to_path_buf(),
+            })
+        })
+        .ok_or_else(|| AppError::usage("no previously visited worktree".to_string()))
+}
+
+/// Default template for [`PromptStatus::render`].
+pub const PROMPT_FORMAT: &str = "{branch} {ahead}↑{behind}↓ {dirty}";
+
+/// Seconds a cached [`prompt_status`] stays valid while the worktree's
+/// commit, `HEAD` and index are unchanged.
+const PROMPT_CACHE_TTL: u64 = 5;
+
+/// Compact status of one worktree for a shell prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptStatus {
+    /// Branch checked out, or `None` when detached.
+    pub branch: Option<String>,
+    /// Abbreviated `HEAD` commit.
+    pub commit: String,
+    /// Commits on `HEAD` that are not on the mainline.
+    pub ahead: u32,
+    /// Commits on the mainline that are not on `HEAD`.
+    pub behind: u32,
+    /// Whether tracked files have uncommitted changes.
+    pub dirty: bool,
+}
+
+impl PromptStatus {
+    /// Fill `format`'s `{branch}`, `{commit}`, `{ahead}`, `{behind}` and
+    /// `{dirty}` placeholders. A detached `{branch}` shows the commit and a
+    /// clean `{dirty}` is empty; trailing whitespace is trimmed.
+    pub fn render(&self, format: &str) -> String {
+        let rendered = format
+            .replace("{branch}", self.branch.as_deref().unwrap_or(&self.commit))
+            .replace("{commit}", &self.commit)
+            .replace("{ahead}", &self.ahead.to_string())
+            .replace("{behind}", &self.behind.to_string())
+            .replace("{dirty}", if self.dirty { "*" } else { "" });
+        rendered.trim_end().to_string()
+    }
+}
+
+/// Prompt status of `wt`, counting commits against the mainline.
+///
+/// With `use_cache`, stats computed in the last five seconds
+/// are reused as long as the worktree's commit and the mtimes of its `HEAD`
+/// and index are unchanged, so this can run on every prompt render.
+pub fn prompt_status(repo: &RepoRoot, wt: &Worktree, use_cache: bool) -> Result<PromptStatus> {
+    let git_dir = git::worktree_git_dir(&wt.path)?;
+    let cached = if use_cache {
+        let stamp = metadata::PromptStamp::read(&git_dir, &wt.commit);
+        metadata::read_prompt_cache(&git_dir, &stamp, PROMPT_CACHE_TTL)
+    } else {
+        None
+    };
+    let cache = match cached {
+        Some(cache) => cache,
+        None => {
+            // A repository without a resolvable mainline still gets a prompt.
+            let (ahead, behind) = git::resolve_mainline(repo)
+                .and_then(|mainline| git::ahead_behind(&wt.path, &mainline))
+                .unwrap_or((0, 0));
+            let dirty = git::is_dirty(&wt.path)?;
+            // `git status` may refresh the index, so stamp what it left behind.
+            let cache = metadata::PromptCache {
+                stamp: metadata::PromptStamp::read(&git_dir, &wt.commit),
+                written_at: metadata::now(),
+                ahead,
+                behind,
+                dirty,
+            };
+            metadata::write_prompt_cache(&git_dir, &cache);
+            cache
+        }
+    };
+    Ok(PromptStatus {
+        branch: wt.branch.clone(),
+        commit: wt.commit.clone(),
+        ahead: cache.ahead,
+        behind: cache.behind,
+        dirty: cache.dirty,
+    })
+}
+
+/// [`prompt_status`] of the worktree containing `path`, answered from the
+/// cache with a single `git rev-parse`. `None` on a cache miss, leaving the
+/// caller to list worktrees and compute the status.
+pub fn cached_prompt_status(path: &Path) -> Option<PromptStatus> {
+    let head = git::head_info(path).ok()?;
+    let stamp = metadata::PromptStamp::read(&head.git_dir, &head.commit);
+    let cache = metadata::read_prompt_cache(&head.git_dir, &stamp, PROMPT_CACHE_TTL)?;
+    Some(PromptStatus {
+        branch: head.branch,
+        commit: head.commit,
+        ahead: cache.ahead,
+        behind: cache.behind,
+        dirty: cache.dirty,
+    })
+}
+
+/// Remove a worktree and delete its local branch.
+pub fn remove(repo: &RepoRoot, branch: Option<&BranchName>, force: bool) -> Result<RemoveResult> {
+    let result = remove_with(&ProcessGit, repo, branch, force)?;
+    forget_removed(repo, &[&result.removed_path]);
+    Ok(result)
+}
+
+/// Forget what wt keeps about removed worktrees outside Git: their entries
+/// in the navigation history and, with `wt.tmux` set, their tmux sessions.
+/// Best-effort.
+///
+/// Runs after the `*_with` operations, which only touch the backend.
+fn forget_removed(repo: &RepoRoot, paths: &[&Path]) {
+    let _ = metadata::forget_visits(repo.as_ref(), paths);
+    if !cli_support::tmux_configured(repo.as_ref()) {
+        return;
+    }
+    for path in paths {
+        tmux::kill_session_for(path);
+    }
+}
+
+/// [`remove`] against an arbitrary [`GitBackend`].
+///
+/// Only the backend is touched; the navigation history and tmux sessions
+/// are left as is.
+pub fn remove_with(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    branch: Option<&BranchName>,
+    force: bool,
+) -> Result<RemoveResult> {
+    let worktrees = backend.list_worktrees(repo)?;
+
+    // Resolve which branch to remove.
+    let target_branch = match branch {
+        Some(b) => b.clone(),
+        None => resolve_branch_from_cwd(&worktrees)?,
+    };
+
+    // Find the worktree entry.
+    let wt = worktrees
+        .iter()
+        .find(|wt| wt.branch.as_deref() == Some(target_branch.as_str()))
         .ok_or_else(|| {
             AppError::usage(format!("no worktree found for branch '{target_branch}'"))
         })?;
@@ -317,9 +1637,10 @@ pub fn remove(repo: &RepoRoot, branch: Option<&BranchName>, force: bool) -> Resu
     let removed_path = wt.path.clone();
 
     // Remove worktree first, then branch.
-    git::remove_worktree(repo, &removed_path, force)?;
+    backend.remove_worktree(repo, &removed_path, force)?;
     // Branch deletion: best-effort — bubble warning instead of blocking.
-    let warning = git::delete_branch(repo, &target_branch, force)
+    let warning = backend
+        .delete_branch(repo, &target_branch, force)
         .err()
         .map(|e| format!("worktree removed but branch deletion failed: {e}"));
 
@@ -350,77 +1671,247 @@ pub enum IntegrationStatus {
     NotIntegrated,
     /// Worktree has no branch (detached HEAD).
     NoBranch,
+    /// Review or scratch worktree that is no longer needed.
+    Expired(ExpiryReason),
+}
+
+/// Why a review or scratch worktree is considered expired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpiryReason {
+    /// The pull-request ref no longer exists on the remote.
+    RefGone,
+    /// The worktree is older than the configured TTL.
+    TtlElapsed,
+    /// Scratch worktree swept by `prune --scratch`.
+    Scratch,
 }
 
 /// A worktree entry annotated with its integration status for prune.
 #[derive(Debug)]
 pub struct WorktreePruneEntry {
+    /// Branch of the worktree, or `None` when detached.
     pub branch: Option<String>,
+    /// Path of the worktree.
     pub path: std::path::PathBuf,
+    /// Whether the worktree can be pruned, and why.
     pub status: IntegrationStatus,
 }
 
 /// Result of a prune dry-run.
 #[derive(Debug)]
 pub struct PruneDryRun {
+    /// Mainline the branches were checked against.
     pub mainline: String,
+    /// Every non-main worktree with its status.
     pub entries: Vec<WorktreePruneEntry>,
 }
 
 /// An entry that was pruned (removed).
 #[derive(Debug)]
 pub struct PrunedEntry {
-    pub branch: String,
+    /// `None` for detached scratch worktrees.
+    pub branch: Option<String>,
+    /// Path of the removed worktree.
     pub path: std::path::PathBuf,
 }
 
 /// An entry that was skipped during pruning.
 #[derive(Debug)]
 pub struct SkippedEntry {
+    /// Branch of the worktree, or `None` when detached.
     pub branch: Option<String>,
+    /// Path of the worktree.
     pub path: std::path::PathBuf,
+    /// Why it was left in place.
     pub reason: String,
 }
 
 /// Result of a prune execution.
 #[derive(Debug)]
 pub struct PruneExecuteResult {
+    /// Mainline the branches were checked against.
     pub mainline: String,
+    /// Worktrees that were removed.
     pub pruned: Vec<PrunedEntry>,
+    /// Worktrees that were left in place.
     pub skipped: Vec<SkippedEntry>,
+    /// Non-fatal problems, e.g. a branch that could not be deleted.
     pub warnings: Vec<String>,
 }
 
 /// Classify the integration status of a branch against the mainline.
-fn classify_integration(repo: &RepoRoot, branch: &str, mainline: &str) -> IntegrationStatus {
+fn classify_integration(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    branch: &str,
+    mainline: &str,
+) -> IntegrationStatus {
     // 1. Ancestry check (merge / fast-forward)
-    if git::is_ancestor(repo, branch, mainline) {
+    if backend.is_ancestor(repo, branch, mainline) {
         return IntegrationStatus::Integrated(IntegrationMethod::Merged);
     }
 
     // 2. Patch-id check (rebase merge)
-    if git::cherry(repo, mainline, branch) {
+    if backend.cherry(repo, mainline, branch) {
         return IntegrationStatus::Integrated(IntegrationMethod::Rebase);
     }
 
     IntegrationStatus::NotIntegrated
 }
 
-/// Dry-run: scan worktrees and report integration status without removing anything.
-pub fn prune_dry_run(repo: &RepoRoot, mainline_override: Option<&str>) -> Result<PruneDryRun> {
-    let mainline = match mainline_override {
+/// Resolve the mainline from an optional `--mainline` override, falling back
+/// to auto-detection.
+fn resolve_mainline_override(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    mainline_override: Option<&str>,
+) -> Result<String> {
+    match mainline_override {
         Some(m) => {
-            if !git::rev_exists(repo, m) {
+            if !backend.rev_exists(repo, m) {
                 return Err(AppError::usage(format!(
                     "mainline branch '{m}' does not exist"
                 )));
             }
-            m.to_string()
+            Ok(m.to_string())
         }
-        None => git::resolve_mainline(repo)?,
+        None => backend.resolve_mainline(repo),
+    }
+}
+
+/// When review and scratch worktrees count as expired during prune.
+struct ExpiryPolicy {
+    review_ttl: Option<u64>,
+    scratch_ttl: Option<u64>,
+    /// `prune --scratch`: treat every scratch worktree as expired.
+    sweep_scratch: bool,
+    /// Ask the remote whether a review worktree's ref is gone. Off for the
+    /// overview, which must not touch the network.
+    check_remote: bool,
+}
+
+impl ExpiryPolicy {
+    fn load(backend: &dyn GitBackend, repo: &RepoRoot, sweep_scratch: bool) -> Result<Self> {
+        Ok(Self {
+            review_ttl: config_ttl(backend, repo, "wt.reviewTtl")?,
+            scratch_ttl: config_ttl(backend, repo, "wt.scratchTtl")?,
+            sweep_scratch,
+            check_remote: true,
+        })
+    }
+}
+
+/// Read a TTL config value (e.g. `wt.reviewTtl`), in seconds.
+fn config_ttl(backend: &dyn GitBackend, repo: &RepoRoot, key: &str) -> Result<Option<u64>> {
+    let Some(value) = backend.config_get(repo.as_ref(), key)? else {
+        return Ok(None);
     };
+    metadata::parse_ttl(&value).map(Some).ok_or_else(|| {
+        AppError::usage(format!(
+            "invalid {key} '{value}' (expected e.g. 72h, 14d, 2w)"
+        ))
+    })
+}
 
-    let worktrees = git::list_worktrees(repo)?;
+fn ttl_elapsed(meta: &WorktreeMeta, ttl: Option<u64>) -> bool {
+    ttl.is_some_and(|ttl| metadata::now().saturating_sub(meta.created_at) >= ttl)
+}
+
+/// Decide whether a scratch worktree has expired.
+fn scratch_expiry(meta: &WorktreeMeta, policy: &ExpiryPolicy) -> Option<ExpiryReason> {
+    if meta.kind != WorktreeKind::Scratch {
+        return None;
+    }
+    if policy.sweep_scratch {
+        return Some(ExpiryReason::Scratch);
+    }
+    ttl_elapsed(meta, policy.scratch_ttl).then_some(ExpiryReason::TtlElapsed)
+}
+
+/// Decide whether a review worktree has expired.
+///
+/// The TTL is checked first since it needs no network access. A remote that
+/// cannot be reached never counts as the ref being gone.
+fn review_expiry(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    meta: &WorktreeMeta,
+    policy: &ExpiryPolicy,
+) -> Option<ExpiryReason> {
+    if meta.kind != WorktreeKind::Review {
+        return None;
+    }
+    if ttl_elapsed(meta, policy.review_ttl) {
+        return Some(ExpiryReason::TtlElapsed);
+    }
+    if !policy.check_remote {
+        return None;
+    }
+    let (Some(remote), Some(remote_ref)) = (&meta.remote, &meta.remote_ref) else {
+        return None;
+    };
+    matches!(
+        backend.remote_ref_exists(repo, remote, remote_ref),
+        Ok(false)
+    )
+    .then_some(ExpiryReason::RefGone)
+}
+
+/// Classify a worktree for prune: integration first, then review or
+/// scratch expiry.
+fn classify_prune_entry(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    wt: &Worktree,
+    mainline: &str,
+    policy: &ExpiryPolicy,
+) -> IntegrationStatus {
+    let Some(branch) = &wt.branch else {
+        return metadata::read(&wt.path)
+            .and_then(|meta| scratch_expiry(&meta, policy))
+            .map_or(IntegrationStatus::NoBranch, IntegrationStatus::Expired);
+    };
+    let status = classify_integration(backend, repo, branch, mainline);
+    if status != IntegrationStatus::NotIntegrated {
+        return status;
+    }
+    metadata::read(&wt.path)
+        .and_then(|meta| review_expiry(backend, repo, &meta, policy))
+        .map_or(status, IntegrationStatus::Expired)
+}
+
+/// Dry-run: scan worktrees and report integration status without removing anything.
+///
+/// With `sweep_scratch`, every scratch worktree is reported as expired.
+pub fn prune_dry_run(
+    repo: &RepoRoot,
+    mainline_override: Option<&str>,
+    sweep_scratch: bool,
+) -> Result<PruneDryRun> {
+    prune_dry_run_with(&ProcessGit, repo, mainline_override, sweep_scratch)
+}
+
+/// [`prune_dry_run`] against an arbitrary [`GitBackend`].
+pub fn prune_dry_run_with(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    mainline_override: Option<&str>,
+    sweep_scratch: bool,
+) -> Result<PruneDryRun> {
+    let mainline = resolve_mainline_override(backend, repo, mainline_override)?;
+    let policy = ExpiryPolicy::load(backend, repo, sweep_scratch)?;
+    scan_prune(backend, repo, mainline, &policy)
+}
+
+/// Classify every linked worktree against `mainline` under `policy`.
+fn scan_prune(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    mainline: String,
+    policy: &ExpiryPolicy,
+) -> Result<PruneDryRun> {
+    let worktrees = backend.list_worktrees(repo)?;
     let mut entries = Vec::new();
 
     for wt in &worktrees {
@@ -428,10 +1919,7 @@ pub fn prune_dry_run(repo: &RepoRoot, mainline_override: Option<&str>) -> Result
             continue;
         }
 
-        let status = match &wt.branch {
-            Some(branch) => classify_integration(repo, branch, &mainline),
-            None => IntegrationStatus::NoBranch,
-        };
+        let status = classify_prune_entry(backend, repo, wt, &mainline, policy);
 
         entries.push(WorktreePruneEntry {
             branch: wt.branch.clone(),
@@ -450,164 +1938,1105 @@ struct PruneAccumulator {
     warnings: Vec<String>,
 }
 
-/// Try to remove an integrated worktree and its branch.
+/// Try to remove an integrated or expired worktree and its branch.
 ///
 /// When the branch was integrated via rebase (patch-id match), Git's own
 /// ancestry check (`git branch -d`) would refuse deletion because the
 /// original commits are not ancestors of mainline.  We auto-escalate to
 /// `-D` in that case since the cherry check already confirmed integration.
+/// Expired review branches are throwaway copies of a remote ref, so they
+/// are deleted with `-D` as well. Scratch worktrees have no branch to delete.
 fn prune_integrated_entry(
+    backend: &dyn GitBackend,
     repo: &RepoRoot,
     entry: WorktreePruneEntry,
     force: bool,
     acc: &mut PruneAccumulator,
 ) {
-    let branch_name = entry.branch.clone().expect("integrated implies branch");
+    let label = entry
+        .branch
+        .clone()
+        .unwrap_or_else(|| entry.path.display().to_string());
 
     let force_branch = force
         || matches!(
             entry.status,
             IntegrationStatus::Integrated(IntegrationMethod::Rebase)
+                | IntegrationStatus::Expired(_)
         );
 
-    if let Err(e) = git::remove_worktree(repo, &entry.path, force) {
-        acc.warnings.push(format!(
-            "failed to remove worktree for '{branch_name}': {e}"
-        ));
+    if let Err(e) = backend.remove_worktree(repo, &entry.path, force) {
+        acc.warnings
+            .push(format!("failed to remove worktree for '{label}': {e}"));
         acc.skipped.push(SkippedEntry {
-            branch: Some(branch_name),
+            branch: entry.branch,
             path: entry.path,
             reason: "removal_failed".to_string(),
         });
         return;
     }
 
-    let bn = BranchName::new(&branch_name);
-    if let Err(e) = git::delete_branch(repo, &bn, force_branch) {
+    let deleted = entry
+        .branch
+        .as_deref()
+        .map(|branch| backend.delete_branch(repo, &BranchName::new(branch), force_branch));
+    if let Some(Err(e)) = deleted {
         acc.warnings.push(format!(
-            "worktree removed but branch deletion failed for '{branch_name}': {e}"
+            "worktree removed but branch deletion failed for '{label}': {e}"
         ));
     }
     acc.pruned.push(PrunedEntry {
-        branch: branch_name,
+        branch: entry.branch,
         path: entry.path,
     });
 }
 
-/// Execute prune: remove integrated worktrees and their branches.
+/// Execute prune: remove integrated and expired worktrees and their branches.
 pub fn prune_execute(
     repo: &RepoRoot,
     mainline_override: Option<&str>,
     force: bool,
+    sweep_scratch: bool,
 ) -> Result<PruneExecuteResult> {
-    let dry_run = prune_dry_run(repo, mainline_override)?;
-    let mainline = dry_run.mainline;
+    let result = prune_execute_with(&ProcessGit, repo, mainline_override, force, sweep_scratch)?;
+    forget_pruned(repo, &result);
+    Ok(result)
+}
 
-    let mut acc = PruneAccumulator {
-        pruned: Vec::new(),
-        skipped: Vec::new(),
-        warnings: Vec::new(),
+/// [`prune_execute`] against an arbitrary [`GitBackend`].
+///
+/// Only the backend is touched; the navigation history and tmux sessions
+/// are left as is.
+pub fn prune_execute_with(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    mainline_override: Option<&str>,
+    force: bool,
+    sweep_scratch: bool,
+) -> Result<PruneExecuteResult> {
+    let dry_run = prune_dry_run_with(backend, repo, mainline_override, sweep_scratch)?;
+    Ok(execute_prune(backend, repo, dry_run, force))
+}
+
+/// Prune only the worktrees at `paths`: those integrated into the mainline
+/// or expired are removed with their branches, the others are skipped.
+pub fn prune_selected(
+    repo: &RepoRoot,
+    paths: &[PathBuf],
+    force: bool,
+) -> Result<PruneExecuteResult> {
+    let result = prune_selected_with(&ProcessGit, repo, paths, force)?;
+    forget_pruned(repo, &result);
+    Ok(result)
+}
+
+fn forget_pruned(repo: &RepoRoot, result: &PruneExecuteResult) {
+    let paths: Vec<&Path> = result.pruned.iter().map(|p| p.path.as_path()).collect();
+    forget_removed(repo, &paths);
+}
+
+/// [`prune_selected`] against an arbitrary [`GitBackend`].
+///
+/// Only the backend is touched; the navigation history and tmux sessions
+/// are left as is.
+pub fn prune_selected_with(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    paths: &[PathBuf],
+    force: bool,
+) -> Result<PruneExecuteResult> {
+    let mut dry_run = prune_dry_run_with(backend, repo, None, false)?;
+    dry_run.entries.retain(|entry| paths.contains(&entry.path));
+    Ok(execute_prune(backend, repo, dry_run, force))
+}
+
+fn execute_prune(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    dry_run: PruneDryRun,
+    force: bool,
+) -> PruneExecuteResult {
+    let mainline = dry_run.mainline;
+
+    let mut acc = PruneAccumulator {
+        pruned: Vec::new(),
+        skipped: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    for entry in dry_run.entries {
+        match entry.status {
+            IntegrationStatus::Integrated(_) | IntegrationStatus::Expired(_) => {
+                prune_integrated_entry(backend, repo, entry, force, &mut acc);
+            }
+            IntegrationStatus::NotIntegrated => {
+                acc.skipped.push(SkippedEntry {
+                    branch: entry.branch,
+                    path: entry.path,
+                    reason: "not_integrated".to_string(),
+                });
+            }
+            IntegrationStatus::NoBranch => {
+                acc.skipped.push(SkippedEntry {
+                    branch: None,
+                    path: entry.path,
+                    reason: "no_branch".to_string(),
+                });
+            }
+        }
+    }
+
+    PruneExecuteResult {
+        mainline,
+        pruned: acc.pruned,
+        skipped: acc.skipped,
+        warnings: acc.warnings,
+    }
+}
+
+/// Whether the worktree at `path` is locked (`git worktree lock`).
+pub fn is_locked(path: &Path) -> bool {
+    ProcessGit.is_locked(path)
+}
+
+/// Lock the linked worktree at `path`, so Git refuses to remove or prune it.
+pub fn lock(repo: &RepoRoot, path: &Path, reason: Option<&str>) -> Result<()> {
+    lock_with(&ProcessGit, repo, path, reason)
+}
+
+/// [`lock`] against an arbitrary [`GitBackend`].
+pub fn lock_with(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    path: &Path,
+    reason: Option<&str>,
+) -> Result<()> {
+    backend.lock_worktree(repo, path, reason)
+}
+
+/// Unlock the linked worktree at `path`.
+pub fn unlock(repo: &RepoRoot, path: &Path) -> Result<()> {
+    unlock_with(&ProcessGit, repo, path)
+}
+
+/// [`unlock`] against an arbitrary [`GitBackend`].
+pub fn unlock_with(backend: &dyn GitBackend, repo: &RepoRoot, path: &Path) -> Result<()> {
+    backend.unlock_worktree(repo, path)
+}
+
+/// Commit and diff stats of `wt` against `base`, or why they are unavailable.
+pub fn stats_status_with(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    base: &str,
+    wt: &Worktree,
+) -> WorktreeStatsStatus {
+    match &wt.branch {
+        Some(branch) => backend.worktree_stats(repo, base, branch).map_or_else(
+            |_| WorktreeStatsStatus::Unavailable {
+                base: base.to_string(),
+                reason: "git_error".to_string(),
+            },
+            WorktreeStatsStatus::Available,
+        ),
+        None => WorktreeStatsStatus::Unavailable {
+            base: base.to_string(),
+            reason: "no_branch".to_string(),
+        },
+    }
+}
+
+/// A worktree with everything a dashboard shows about it.
+#[derive(Debug)]
+pub struct OverviewEntry {
+    /// The worktree itself.
+    pub worktree: Worktree,
+    /// Stats against the mainline.
+    pub stats: WorktreeStatsStatus,
+    /// Integration status as `prune` sees it (`None` for the main worktree).
+    pub integration: Option<IntegrationStatus>,
+    /// Uncommitted changes to tracked files (`None` if `git status` failed).
+    pub dirty: Option<bool>,
+    /// Whether the worktree is locked.
+    pub locked: bool,
+}
+
+/// Result of [`overview`].
+#[derive(Debug)]
+pub struct Overview {
+    /// Mainline the stats and integration status are computed against.
+    pub mainline: String,
+    /// Every worktree, main first.
+    pub entries: Vec<OverviewEntry>,
+}
+
+/// Stats, integration status, dirty state and lock state of every worktree.
+///
+/// Review worktrees only expire by TTL here: the overview never asks the
+/// remote whether their ref is gone, so `prune` may still find more.
+pub fn overview(repo: &RepoRoot) -> Result<Overview> {
+    overview_with(&ProcessGit, repo)
+}
+
+/// [`overview`] against an arbitrary [`GitBackend`].
+pub fn overview_with(backend: &dyn GitBackend, repo: &RepoRoot) -> Result<Overview> {
+    let policy = ExpiryPolicy {
+        check_remote: false,
+        ..ExpiryPolicy::load(backend, repo, false)?
+    };
+    let PruneDryRun {
+        mainline,
+        entries: prune_entries,
+    } = scan_prune(backend, repo, backend.resolve_mainline(repo)?, &policy)?;
+    let mut integration: Vec<(PathBuf, IntegrationStatus)> = prune_entries
+        .into_iter()
+        .map(|entry| (entry.path, entry.status))
+        .collect();
+
+    let entries = backend
+        .list_worktrees(repo)?
+        .into_iter()
+        .map(|wt| OverviewEntry {
+            stats: stats_status_with(backend, repo, &mainline, &wt),
+            integration: integration
+                .iter()
+                .position(|(path, _)| *path == wt.path)
+                .map(|idx| integration.swap_remove(idx).1),
+            dirty: backend.is_dirty(&wt.path).ok(),
+            locked: !wt.is_main && backend.is_locked(&wt.path),
+            worktree: wt,
+        })
+        .collect();
+
+    Ok(Overview { mainline, entries })
+}
+
+/// How `sync` brings mainline changes into a worktree branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncStrategy {
+    /// `git rebase <mainline>` inside the worktree.
+    Rebase,
+    /// `git merge --no-edit <mainline>` inside the worktree.
+    Merge,
+}
+
+/// An entry whose branch was updated onto mainline.
+#[derive(Debug)]
+pub struct SyncedEntry {
+    /// Branch that was updated.
+    pub branch: String,
+    /// Path of its worktree.
+    pub path: std::path::PathBuf,
+}
+
+/// Result of a sync run.
+///
+/// Mirrors [`PruneExecuteResult`]: per-branch failures never abort the run,
+/// they are reported as skipped entries with a warning.
+#[derive(Debug)]
+pub struct SyncResult {
+    /// The revision branches were synced onto (e.g. `origin/main`).
+    pub mainline: String,
+    /// How mainline changes were brought in.
+    pub strategy: SyncStrategy,
+    /// Branches that were updated.
+    pub synced: Vec<SyncedEntry>,
+    /// Branches that were left alone.
+    pub skipped: Vec<SkippedEntry>,
+    /// Non-fatal problems encountered along the way.
+    pub warnings: Vec<String>,
+}
+
+/// Accumulator for sync results.
+struct SyncAccumulator {
+    synced: Vec<SyncedEntry>,
+    skipped: Vec<SkippedEntry>,
+    warnings: Vec<String>,
+}
+
+impl SyncAccumulator {
+    fn skip(&mut self, branch: Option<&str>, path: &Path, reason: &str) {
+        self.skipped.push(SkippedEntry {
+            branch: branch.map(str::to_string),
+            path: path.to_path_buf(),
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// Pick the revision to sync onto: the remote-tracking counterpart of the
+/// mainline when it exists (so a preceding fetch takes effect), otherwise
+/// the mainline itself.
+fn sync_upstream(repo: &RepoRoot, mainline: &str) -> String {
+    let remote_ref = format!("origin/{mainline}");
+    if !mainline.starts_with("origin/")
+        && git::rev_exists(repo, &format!("refs/remotes/{remote_ref}"))
+    {
+        return remote_ref;
+    }
+    mainline.to_string()
+}
+
+/// Rebase or merge a single worktree branch onto `upstream`.
+///
+/// A failed rebase/merge is aborted so the worktree is left exactly as it
+/// was; the failure is recorded without affecting other worktrees, as
+/// `conflict` when Git stopped on unmerged paths and `sync_failed` otherwise
+/// (e.g. a stale `index.lock`).
+fn sync_entry(
+    repo: &RepoRoot,
+    wt: &Worktree,
+    upstream: &str,
+    strategy: SyncStrategy,
+    acc: &mut SyncAccumulator,
+) {
+    let Some(branch) = wt.branch.as_deref() else {
+        acc.skip(None, &wt.path, "no_branch");
+        return;
+    };
+
+    match git::is_dirty(&wt.path) {
+        Ok(false) => {}
+        Ok(true) => {
+            acc.skip(Some(branch), &wt.path, "dirty");
+            return;
+        }
+        Err(e) => {
+            acc.warnings
+                .push(format!("failed to inspect worktree for '{branch}': {e}"));
+            acc.skip(Some(branch), &wt.path, "status_failed");
+            return;
+        }
+    }
+
+    if git::is_ancestor(repo, upstream, branch) {
+        acc.skip(Some(branch), &wt.path, "up_to_date");
+        return;
+    }
+
+    let outcome = match strategy {
+        SyncStrategy::Rebase => git::rebase(&wt.path, upstream),
+        SyncStrategy::Merge => git::merge_upstream(&wt.path, upstream),
+    };
+
+    let Err(e) = outcome else {
+        acc.synced.push(SyncedEntry {
+            branch: branch.to_string(),
+            path: wt.path.clone(),
+        });
+        return;
+    };
+
+    let conflicted = git::has_unmerged_paths(&wt.path);
+    match strategy {
+        SyncStrategy::Rebase => git::rebase_abort(&wt.path),
+        SyncStrategy::Merge => git::merge_abort(&wt.path),
+    }
+    let (what, reason) = if conflicted {
+        ("conflicted and was aborted", "conflict")
+    } else {
+        ("failed", "sync_failed")
+    };
+    acc.warnings
+        .push(format!("sync of '{branch}' onto {upstream} {what}: {e}"));
+    acc.skip(Some(branch), &wt.path, reason);
+}
+
+/// Sync worktree branches with the latest mainline.
+///
+/// 1. Fetch from `origin` (unless `fetch` is false or no `origin` exists)
+/// 2. Resolve the mainline (`--mainline` or auto-detected)
+/// 3. For each selected non-main worktree with a clean tree, rebase or merge
+///    onto mainline from inside that worktree
+///
+/// When `branches` is empty every non-main worktree is considered.
+pub fn sync(
+    repo: &RepoRoot,
+    branches: &[BranchName],
+    strategy: SyncStrategy,
+    mainline_override: Option<&str>,
+    fetch: bool,
+) -> Result<SyncResult> {
+    let mut acc = SyncAccumulator {
+        synced: Vec::new(),
+        skipped: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    // A failed fetch is not fatal: syncing onto the last-fetched state is
+    // still useful, so surface it as a warning.
+    let fetch_error = (fetch && git::remote_exists(repo, "origin"))
+        .then(|| git::fetch(repo, "origin").err())
+        .flatten();
+    if let Some(e) = fetch_error {
+        acc.warnings.push(format!("fetch from origin failed: {e}"));
+    }
+
+    let mainline = resolve_mainline_override(&ProcessGit, repo, mainline_override)?;
+    let upstream = sync_upstream(repo, &mainline);
+    let worktrees = git::list_worktrees(repo)?;
+
+    let unknown = branches.iter().find(|b| {
+        !worktrees
+            .iter()
+            .any(|wt| !wt.is_main && wt.branch.as_deref() == Some(b.as_str()))
+    });
+    if let Some(branch) = unknown {
+        return Err(AppError::usage(format!(
+            "no worktree found for branch '{branch}'"
+        )));
+    }
+
+    let selected = worktrees.iter().filter(|wt| {
+        !wt.is_main
+            && (branches.is_empty()
+                || branches
+                    .iter()
+                    .any(|b| wt.branch.as_deref() == Some(b.as_str())))
+    });
+
+    for wt in selected {
+        if wt.branch.as_deref() == Some(mainline.as_str()) {
+            acc.skip(Some(&mainline), &wt.path, "mainline");
+            continue;
+        }
+        sync_entry(repo, wt, &upstream, strategy, &mut acc);
+    }
+
+    Ok(SyncResult {
+        mainline: upstream,
+        strategy,
+        synced: acc.synced,
+        skipped: acc.skipped,
+        warnings: acc.warnings,
+    })
+}
+
+/// Run health diagnostics on the repository's worktree state.
+///
+/// Stale registrations are listed without pruning so they can be reported.
+pub fn doctor(repo: &RepoRoot) -> Result<Vec<Diagnostic>> {
+    let mut diags = toolchain_diagnostics(repo);
+
+    let wt_dir = repo.worktrees_dir();
+    let worktrees = git::list_registered_worktrees(repo)?;
+    if wt_dir.exists() {
+        diags.extend(managed_dir_diagnostics(repo, &wt_dir, &worktrees)?);
+    }
+
+    let (present, missing): (Vec<&Worktree>, Vec<&Worktree>) = worktrees
+        .iter()
+        .partition(|wt| wt.is_main || wt.path.exists());
+
+    for wt in missing {
+        diags.push(
+            Diagnostic::new(
+                DiagLevel::Warn,
+                DiagCode::MissingDirectory,
+                format!(
+                    "registered worktree directory is missing: {}",
+                    wt.path.display()
+                ),
+            )
+            .with_path(wt.path.clone())
+            .with_fix("git worktree prune".to_string())
+            .fixable(),
+        );
+    }
+
+    for wt in &present {
+        diags.extend(git_state_diagnostics(wt));
+    }
+
+    let linked: Vec<&Worktree> = present.into_iter().filter(|wt| !wt.is_main).collect();
+
+    for wt in &linked {
+        diags.extend(layout_diagnostic(&wt_dir, wt));
+    }
+
+    // Check each worktree has a valid branch.
+    for wt in &linked {
+        let intentional = || metadata::read(&wt.path).is_some_and(|m| m.kind.is_detached());
+        if wt.branch.is_some() || intentional() {
+            continue;
+        }
+        let diag = Diagnostic::new(
+            DiagLevel::Warn,
+            DiagCode::DetachedHead,
+            format!(
+                "worktree has no branch (detached HEAD): {}",
+                wt.path.display()
+            ),
+        )
+        .with_path(wt.path.clone());
+        diags.push(match reattach_branch(repo, &worktrees, wt) {
+            Some(branch) => diag
+                .with_fix(format!(
+                    "git -C {} switch {}",
+                    shell_quote(&wt.path.display().to_string()),
+                    shell_quote(&branch)
+                ))
+                .fixable(),
+            None => diag,
+        });
+    }
+
+    diags.extend(upstream_diagnostics(repo, &linked));
+
+    for wt in &linked {
+        diags.extend(
+            symlinks::check_links(repo, &wt.path)
+                .iter()
+                .map(|finding| link_diagnostic(wt, finding)),
+        );
+    }
+
+    if diags.is_empty() && !wt_dir.exists() {
+        diags.push(Diagnostic::new(
+            DiagLevel::Ok,
+            DiagCode::NoWorktrees,
+            "no .worktrees directory (no worktrees created yet)".to_string(),
+        ));
+    }
+    if diags.is_empty() {
+        diags.push(Diagnostic::new(
+            DiagLevel::Ok,
+            DiagCode::Healthy,
+            "all worktrees healthy".to_string(),
+        ));
+    }
+
+    Ok(diags)
+}
+
+/// Lock files younger than this are assumed to belong to a running Git
+/// process.
+const STALE_LOCK_AGE: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Checks on the Git installation and the remote setup.
+fn toolchain_diagnostics(repo: &RepoRoot) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+
+    let (min_major, min_minor) = git::MIN_GIT_VERSION;
+    if let Some((major, minor)) = git::version().filter(|v| *v < git::MIN_GIT_VERSION) {
+        diags.push(Diagnostic::new(
+            DiagLevel::Error,
+            DiagCode::GitTooOld,
+            format!(
+                "git {major}.{minor} is older than the supported minimum {min_major}.{min_minor}"
+            ),
+        ));
+    }
+
+    if git::remote_exists(repo, "origin") && !git::origin_head_resolves(repo) {
+        diags.push(
+            Diagnostic::new(
+                DiagLevel::Warn,
+                DiagCode::OriginHeadMissing,
+                "origin/HEAD is missing or unresolvable; mainline detection falls back to main/master"
+                    .to_string(),
+            )
+            .with_fix("git remote set-head origin --auto".to_string()),
+        );
+    }
+
+    diags
+}
+
+/// Checks on the managed `.worktrees/` directory itself.
+fn managed_dir_diagnostics(
+    repo: &RepoRoot,
+    wt_dir: &Path,
+    worktrees: &[Worktree],
+) -> Result<Vec<Diagnostic>> {
+    let mut diags = Vec::new();
+
+    if !git::is_ignored(repo.as_ref(), ".worktrees/") {
+        let exclude = git::git_path(repo.as_ref(), "info/exclude")?;
+        diags.push(
+            Diagnostic::new(
+                DiagLevel::Warn,
+                DiagCode::WorktreesNotIgnored,
+                ".worktrees/ is not ignored and shows up in `git status`".to_string(),
+            )
+            .with_fix(format!(
+                "echo .worktrees/ >> {}",
+                shell_quote(&exclude.display().to_string())
+            ))
+            .fixable(),
+        );
+    }
+
+    let managed_paths: Vec<_> = worktrees.iter().map(|wt| &wt.path).collect();
+
+    let orphaned = std::fs::read_dir(wt_dir)
+        .into_iter()
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|p| p.is_dir() && !managed_paths.contains(&p));
+
+    for orphan in orphaned {
+        let quoted = shell_quote(&orphan.display().to_string());
+        let diag = Diagnostic::new(
+            DiagLevel::Warn,
+            DiagCode::OrphanedDirectory,
+            format!(
+                "orphaned directory not tracked by git: {}",
+                orphan.display()
+            ),
+        );
+        // Only empty directories and worktrees that can be re-registered in
+        // place are fixed; anything else may be the user's work.
+        let diag = if is_empty_dir(&orphan) {
+            diag.with_fix(format!("rmdir {quoted}")).fixable()
+        } else if orphan_branch(repo, worktrees, &orphan).is_some() {
+            diag.with_fix("wt doctor --fix".to_string()).fixable()
+        } else {
+            diag.with_fix(format!("rm -rf {quoted}"))
+        };
+        diags.push(diag.with_path(orphan));
+    }
+
+    Ok(diags)
+}
+
+/// Stale `index.lock` files and interrupted merges, rebases, cherry-picks
+/// or reverts in a worktree (main included).
+fn git_state_diagnostics(wt: &Worktree) -> Vec<Diagnostic> {
+    let Ok(git_dir) = git::worktree_git_dir(&wt.path) else {
+        return Vec::new();
+    };
+    let quoted = shell_quote(&wt.path.display().to_string());
+    let mut diags = Vec::new();
+
+    let lock = git_dir.join("index.lock");
+    let age = std::fs::metadata(&lock)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok());
+    if age.is_some_and(|age| age >= STALE_LOCK_AGE) {
+        diags.push(
+            Diagnostic::new(
+                DiagLevel::Warn,
+                DiagCode::StaleIndexLock,
+                format!("stale index.lock in {}", wt.path.display()),
+            )
+            .with_path(wt.path.clone())
+            .with_fix(format!("rm {}", shell_quote(&lock.display().to_string()))),
+        );
+    }
+
+    let operation = [
+        ("MERGE_HEAD", "merge"),
+        ("rebase-merge", "rebase"),
+        ("rebase-apply", "rebase"),
+        ("CHERRY_PICK_HEAD", "cherry-pick"),
+        ("REVERT_HEAD", "revert"),
+    ]
+    .into_iter()
+    .find(|(marker, _)| git_dir.join(marker).exists());
+    if let Some((_, op)) = operation {
+        diags.push(
+            Diagnostic::new(
+                DiagLevel::Warn,
+                DiagCode::OperationInProgress,
+                format!("{op} in progress in {}", wt.path.display()),
+            )
+            .with_path(wt.path.clone())
+            .with_fix(format!("git -C {quoted} {op} --continue  # or --abort")),
+        );
+    }
+
+    diags
+}
+
+/// Flag linked worktrees that live outside `.worktrees/` or whose directory
+/// name does not match their branch.
+fn layout_diagnostic(wt_dir: &Path, wt: &Worktree) -> Option<Diagnostic> {
+    let quoted = shell_quote(&wt.path.display().to_string());
+    if wt.path.parent() != Some(wt_dir) {
+        return Some(
+            Diagnostic::new(
+                DiagLevel::Warn,
+                DiagCode::OutsideManagedRoot,
+                format!(
+                    "worktree outside {}: {}",
+                    wt_dir.display(),
+                    wt.path.display()
+                ),
+            )
+            .with_path(wt.path.clone())
+            .with_fix(format!("wt adopt {quoted}")),
+        );
+    }
+    let expected = BranchName::new(wt.branch.as_deref()?).to_dir_name();
+    if wt.path.file_name()? == expected.as_str() {
+        return None;
+    }
+    Some(
+        Diagnostic::new(
+            DiagLevel::Warn,
+            DiagCode::DirNameMismatch,
+            format!(
+                "worktree directory does not match its branch (expected {expected}): {}",
+                wt.path.display()
+            ),
+        )
+        .with_path(wt.path.clone())
+        .with_fix(format!("wt adopt {quoted}")),
+    )
+}
+
+/// Worktree branches whose upstream was deleted on the remote.
+fn upstream_diagnostics(repo: &RepoRoot, linked: &[&Worktree]) -> Vec<Diagnostic> {
+    let gone = git::gone_upstreams(repo).unwrap_or_default();
+    linked
+        .iter()
+        .filter_map(|wt| {
+            let branch = wt.branch.as_deref()?;
+            let (_, upstream) = gone.iter().find(|(b, _)| b == branch)?;
+            Some(
+                Diagnostic::new(
+                    DiagLevel::Warn,
+                    DiagCode::UpstreamGone,
+                    format!("upstream {upstream} of {branch} is gone"),
+                )
+                .with_path(wt.path.clone())
+                .with_fix(format!("wt remove {}", shell_quote(branch))),
+            )
+        })
+        .collect()
+}
+
+/// The only branch a detached worktree can be re-attached to: one whose tip
+/// matches `HEAD` and that is not checked out anywhere else.
+fn reattach_branch(repo: &RepoRoot, worktrees: &[Worktree], wt: &Worktree) -> Option<String> {
+    let head = git::head_commit(&wt.path).ok()?;
+    let candidates: Vec<String> = git::branches_at(repo, &head)
+        .ok()?
+        .into_iter()
+        .filter(|b| !worktrees.iter().any(|w| w.branch.as_deref() == Some(b)))
+        .collect();
+    match candidates.as_slice() {
+        [only] => Some(only.clone()),
+        _ => None,
+    }
+}
+
+/// Turn a shared-entry finding into a doctor diagnostic with a fix command.
+fn link_diagnostic(wt: &Worktree, finding: &symlinks::LinkFinding) -> Diagnostic {
+    use symlinks::LinkProblem;
+
+    let rel = finding.path.display();
+    let link = match &wt.branch {
+        Some(branch) => format!("wt link {}", shell_quote(branch)),
+        None => "wt link --all".to_string(),
+    };
+    let (code, what, fix) = match finding.problem {
+        LinkProblem::Missing => (DiagCode::LinkMissing, "shared entry missing", link),
+        LinkProblem::Dangling => (
+            DiagCode::LinkDangling,
+            "dangling shared link (target removed from main worktree)",
+            format!("{link} --prune"),
+        ),
+        LinkProblem::WrongTarget => (
+            DiagCode::LinkWrongTarget,
+            "shared link points to the wrong target",
+            link,
+        ),
+        LinkProblem::Replaced => (
+            DiagCode::LinkReplaced,
+            "shared entry replaced by a real file or directory",
+            format!(
+                "rm -rf {} && {link}",
+                shell_quote(&wt.path.join(&finding.path).display().to_string())
+            ),
+        ),
+        LinkProblem::Tracked => (
+            DiagCode::LinkTracked,
+            "shared link is tracked by git and would be committed",
+            format!(
+                "git -C {} rm --cached {}",
+                shell_quote(&wt.path.display().to_string()),
+                shell_quote(&rel.to_string())
+            ),
+        ),
     };
+    let diag = Diagnostic::new(
+        DiagLevel::Warn,
+        code,
+        format!("{what}: {rel} in {}", wt.path.display()),
+    )
+    .with_path(wt.path.clone())
+    .with_fix(fix);
+    // Missing and mis-targeted links are recreated by `wt link`; the others
+    // involve deleting or unstaging content the user may want to keep.
+    match code {
+        DiagCode::LinkMissing | DiagCode::LinkWrongTarget => diag.fixable(),
+        _ => diag,
+    }
+}
 
-    for entry in dry_run.entries {
-        match entry.status {
-            IntegrationStatus::Integrated(_) => {
-                prune_integrated_entry(repo, entry, force, &mut acc);
-            }
-            IntegrationStatus::NotIntegrated => {
-                acc.skipped.push(SkippedEntry {
-                    branch: entry.branch,
-                    path: entry.path,
-                    reason: "not_integrated".to_string(),
-                });
+/// Quote `value` for POSIX shells when it contains anything beyond a safe
+/// set of characters.
+fn shell_quote(value: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "/._-+=:@,".contains(c);
+    if !value.is_empty() && value.chars().all(safe) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Outcome of one remediation attempted by [`doctor_fix`].
+#[derive(Debug)]
+pub struct FixOutcome {
+    /// Finding the remediation addressed.
+    pub code: DiagCode,
+    /// What was (or would be) done, or why it failed.
+    pub message: String,
+    /// Whether the remediation succeeded.
+    pub ok: bool,
+}
+
+/// Repair every fixable finding reported by [`doctor`].
+///
+/// With `dry_run`, nothing is touched and the outcomes describe what would
+/// be done.
+pub fn doctor_fix(repo: &RepoRoot, dry_run: bool) -> Result<Vec<FixOutcome>> {
+    let diags = doctor(repo)?;
+    let worktrees = git::list_registered_worktrees(repo)?;
+    let mut outcomes = Vec::new();
+    let mut pruned = false;
+    let mut relinked: Vec<&Path> = Vec::new();
+
+    for diag in diags.iter().filter(|d| d.fixable) {
+        let path = diag.path.as_deref();
+        let outcome = match (diag.code, path) {
+            (DiagCode::WorktreesNotIgnored, _) => fix_ignore(repo, dry_run),
+            (DiagCode::OrphanedDirectory, Some(dir)) => fix_orphan(repo, &worktrees, dir, dry_run),
+            (DiagCode::MissingDirectory, _) if !pruned => {
+                pruned = true;
+                fix_prune(repo, dry_run)
             }
-            IntegrationStatus::NoBranch => {
-                acc.skipped.push(SkippedEntry {
-                    branch: None,
-                    path: entry.path,
-                    reason: "no_branch".to_string(),
-                });
+            (DiagCode::DetachedHead, Some(dir)) => fix_detached(repo, &worktrees, dir, dry_run),
+            (DiagCode::LinkMissing | DiagCode::LinkWrongTarget, Some(dir))
+                if !relinked.contains(&dir) =>
+            {
+                relinked.push(dir);
+                fix_links(repo, dir, dry_run)
             }
-        }
+            _ => continue,
+        };
+        outcomes.push(FixOutcome {
+            code: diag.code,
+            message: outcome.0,
+            ok: outcome.1,
+        });
     }
 
-    Ok(PruneExecuteResult {
-        mainline,
-        pruned: acc.pruned,
-        skipped: acc.skipped,
-        warnings: acc.warnings,
-    })
+    Ok(outcomes)
 }
 
-/// Run health diagnostics on the repository's worktree state.
-pub fn doctor(repo: &RepoRoot) -> Result<Vec<Diagnostic>> {
-    let mut diags = Vec::new();
+/// Pick the planned or completed phrasing of a fix.
+fn fix_verb(dry_run: bool, planned: &str, done: &str) -> String {
+    if dry_run { planned } else { done }.to_string()
+}
 
-    // Check .worktrees directory exists.
-    let wt_dir = repo.worktrees_dir();
-    if !wt_dir.exists() {
-        diags.push(Diagnostic {
-            level: DiagLevel::Ok,
-            message: "no .worktrees directory (no worktrees created yet)".to_string(),
-        });
-        return Ok(diags);
+fn fix_ignore(repo: &RepoRoot, dry_run: bool) -> (String, bool) {
+    let exclude = match git::git_path(repo.as_ref(), "info/exclude") {
+        Ok(path) => path,
+        Err(e) => return (format!("Could not locate info/exclude: {e}"), false),
+    };
+    let verb = fix_verb(dry_run, "Would add", "Added");
+    let message = format!("{verb} .worktrees/ to {}", exclude.display());
+    if dry_run {
+        return (message, true);
+    }
+    let mut content = std::fs::read_to_string(&exclude).unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
     }
+    content.push_str(".worktrees/\n");
+    let written = exclude
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&exclude, content));
+    match written {
+        Ok(()) => (message, true),
+        Err(e) => (
+            format!("Could not update {}: {e}", exclude.display()),
+            false,
+        ),
+    }
+}
 
-    // List worktrees and check for orphaned directories.
-    let worktrees = git::list_worktrees(repo)?;
+fn fix_orphan(
+    repo: &RepoRoot,
+    worktrees: &[Worktree],
+    dir: &Path,
+    dry_run: bool,
+) -> (String, bool) {
+    let Some(branch) = orphan_branch(repo, worktrees, dir) else {
+        return fix_empty_orphan(dir, dry_run);
+    };
+    let verb = fix_verb(dry_run, "Would re-register", "Re-registered");
+    let message = format!("{verb} orphaned worktree {} on {branch}", dir.display());
+    if dry_run {
+        return (message, true);
+    }
+    match reregister_orphan(repo, dir, &branch) {
+        Ok(()) => (message, true),
+        Err(e) => (
+            format!("Could not re-register {}: {e}", dir.display()),
+            false,
+        ),
+    }
+}
 
-    let managed_paths: Vec<_> = worktrees.iter().map(|wt| &wt.path).collect();
+/// Remove an orphaned directory; doctor only offers this when it is empty.
+fn fix_empty_orphan(dir: &Path, dry_run: bool) -> (String, bool) {
+    let verb = fix_verb(dry_run, "Would remove", "Removed");
+    let message = format!("{verb} orphaned directory {}", dir.display());
+    if dry_run {
+        return (message, true);
+    }
+    match std::fs::remove_dir(dir) {
+        Ok(()) => (message, true),
+        Err(e) => (format!("Could not remove {}: {e}", dir.display()), false),
+    }
+}
 
-    let orphaned = std::fs::read_dir(&wt_dir)
-        .into_iter()
-        .flat_map(|entries| entries.flatten())
-        .map(|entry| entry.path())
-        .filter(|p| p.is_dir() && !managed_paths.contains(&p));
+fn is_empty_dir(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none())
+}
 
-    for orphan in orphaned {
-        diags.push(Diagnostic {
-            level: DiagLevel::Warn,
-            message: format!(
-                "orphaned directory not tracked by git: {}",
-                orphan.display()
-            ),
+/// The branch an orphaned worktree directory with a `.git` file was created
+/// for: the one local branch without a worktree whose directory name it has.
+///
+/// `git worktree repair` cannot help once the registration was pruned, so
+/// the branch is recovered from the path convention instead.
+fn orphan_branch(repo: &RepoRoot, worktrees: &[Worktree], dir: &Path) -> Option<BranchName> {
+    if !dir.join(".git").is_file() {
+        return None;
+    }
+    let name = dir.file_name()?.to_str()?;
+    let mut candidates = git::ref_names(repo, &["refs/heads/"])
+        .ok()?
+        .into_iter()
+        .filter_map(|r| r.strip_prefix("refs/heads/").map(BranchName::new))
+        .filter(|b| b.to_dir_name() == name)
+        .filter(|b| {
+            !worktrees
+                .iter()
+                .any(|wt| wt.branch.as_deref() == Some(b.as_str()))
         });
+    let branch = candidates.next()?;
+    candidates.next().is_none().then_some(branch)
+}
+
+/// Register the orphaned worktree at `dir` again on `branch`, keeping its
+/// files: a fresh registration is created with the directory parked aside,
+/// its `.git` file moved into the orphan, and the index rebuilt from `HEAD`.
+fn reregister_orphan(repo: &RepoRoot, dir: &Path, branch: &BranchName) -> Result<()> {
+    let io_err = |e: std::io::Error| AppError::git(format!("{}: {e}", dir.display()));
+    let mut parked_name = dir.as_os_str().to_owned();
+    parked_name.push(".orphan");
+    let parked = PathBuf::from(parked_name);
+    if parked.exists() {
+        return Err(AppError::conflict(format!(
+            "{} already exists",
+            parked.display()
+        )));
     }
 
-    // Check each worktree has a valid branch.
-    for wt in &worktrees {
-        if wt.is_main {
-            continue;
-        }
-        if wt.branch.is_none() {
-            diags.push(Diagnostic {
-                level: DiagLevel::Warn,
-                message: format!(
-                    "worktree has no branch (detached HEAD): {}",
-                    wt.path.display()
-                ),
-            });
-        }
+    std::fs::rename(dir, &parked).map_err(io_err)?;
+    if let Err(e) = git::add_worktree_for_branch_no_checkout(repo, dir, branch) {
+        let _ = std::fs::remove_dir_all(dir);
+        std::fs::rename(&parked, dir).map_err(io_err)?;
+        return Err(e);
     }
+    std::fs::copy(dir.join(".git"), parked.join(".git")).map_err(io_err)?;
+    std::fs::remove_dir_all(dir).map_err(io_err)?;
+    std::fs::rename(&parked, dir).map_err(io_err)?;
+    git::reset_index(dir)
+}
 
-    if diags.is_empty() {
-        diags.push(Diagnostic {
-            level: DiagLevel::Ok,
-            message: "all worktrees healthy".to_string(),
-        });
+fn fix_prune(repo: &RepoRoot, dry_run: bool) -> (String, bool) {
+    let verb = fix_verb(dry_run, "Would prune", "Pruned");
+    let message = format!("{verb} registrations of missing worktree directories");
+    if dry_run {
+        return (message, true);
     }
+    match git::prune_worktrees(repo) {
+        Ok(()) => (message, true),
+        Err(e) => (format!("Could not prune worktrees: {e}"), false),
+    }
+}
 
-    Ok(diags)
+fn fix_detached(
+    repo: &RepoRoot,
+    worktrees: &[Worktree],
+    dir: &Path,
+    dry_run: bool,
+) -> (String, bool) {
+    let Some(wt) = worktrees.iter().find(|wt| wt.path == dir) else {
+        return (
+            format!("Worktree {} is no longer registered", dir.display()),
+            false,
+        );
+    };
+    let Some(branch) = reattach_branch(repo, worktrees, wt) else {
+        return (
+            format!("No unique branch to re-attach {} to", dir.display()),
+            false,
+        );
+    };
+    let verb = fix_verb(dry_run, "Would re-attach", "Re-attached");
+    let message = format!("{verb} {} to {branch}", dir.display());
+    if dry_run {
+        return (message, true);
+    }
+    match git::switch(dir, &branch) {
+        Ok(()) => (message, true),
+        Err(e) => (format!("Could not re-attach {}: {e}", dir.display()), false),
+    }
+}
+
+fn fix_links(repo: &RepoRoot, dir: &Path, dry_run: bool) -> (String, bool) {
+    let outcomes = symlinks::refresh_links(repo, dir, dry_run, false);
+    let failed = outcomes
+        .iter()
+        .filter(|o| matches!(o, symlinks::SymlinkOutcome::Skipped(..)))
+        .count();
+    let fixed = outcomes.len() - failed;
+    let verb = fix_verb(dry_run, "Would relink", "Relinked");
+    let mut message = format!(
+        "{verb} {fixed} shared entr{} in {}",
+        if fixed == 1 { "y" } else { "ies" },
+        dir.display()
+    );
+    if failed > 0 {
+        message.push_str(&format!(" ({failed} skipped)"));
+    }
+    (message, failed == 0)
 }
 
 /// Result of a successful `merge` operation.
+#[derive(Debug)]
 pub struct MergeResult {
+    /// Branch that was merged.
     pub branch: BranchName,
+    /// Branch it was merged into.
     pub mainline: String,
+    /// Root of the main worktree.
     pub repo_root: PathBuf,
+    /// Whether the merged worktree and branch were removed.
     pub cleaned_up: bool,
     /// Path of the removed worktree (only set when `cleaned_up` is true).
     pub removed_path: Option<PathBuf>,
+    /// Whether the mainline was pushed.
     pub pushed: bool,
     /// Non-fatal warnings (e.g. cleanup or push failure after merge).
     pub warnings: Vec<String>,
@@ -628,7 +3057,26 @@ pub fn merge(
     push: bool,
     no_cleanup: bool,
 ) -> Result<MergeResult> {
-    let worktrees = git::list_worktrees(repo)?;
+    let result = merge_with(&ProcessGit, repo, branch, into, push, no_cleanup)?;
+    if let Some(path) = &result.removed_path {
+        forget_removed(repo, &[path]);
+    }
+    Ok(result)
+}
+
+/// [`merge`] against an arbitrary [`GitBackend`].
+///
+/// Only the backend is touched; the navigation history and tmux sessions
+/// are left as is.
+pub fn merge_with(
+    backend: &dyn GitBackend,
+    repo: &RepoRoot,
+    branch: Option<&BranchName>,
+    into: Option<&str>,
+    push: bool,
+    no_cleanup: bool,
+) -> Result<MergeResult> {
+    let worktrees = backend.list_worktrees(repo)?;
 
     // Resolve which branch to merge (same cwd-inference as `remove`).
     let target_branch = match branch {
@@ -655,7 +3103,7 @@ pub fn merge(
     let mainline = into
         .map(str::to_string)
         .map(Ok)
-        .unwrap_or_else(|| git::resolve_mainline(repo))?;
+        .unwrap_or_else(|| backend.resolve_mainline(repo))?;
     if target_branch.as_str() == mainline {
         return Err(AppError::invariant(
             "refusing to merge a branch into itself".to_string(),
@@ -678,9 +3126,9 @@ pub fn merge(
     }
 
     // Attempt the merge from the main worktree's context.
-    if let Err(e) = git::merge_no_ff(repo, target_branch.as_str()) {
+    if let Err(e) = backend.merge_no_ff(repo, target_branch.as_str()) {
         // Abort to restore the main worktree to a clean state.
-        git::merge_abort(repo);
+        backend.merge_abort(repo.as_ref());
         return Err(AppError::conflict(format!(
             "merge conflicts with '{}' — merge aborted; use `git merge` directly to handle conflicts\n{e}",
             target_branch
@@ -695,7 +3143,7 @@ pub fn merge(
     let (cleaned_up, removed_path) = if no_cleanup {
         (false, None)
     } else {
-        match remove(repo, Some(&target_branch), false) {
+        match remove_with(backend, repo, Some(&target_branch), false) {
             Ok(result) => {
                 if let Some(w) = result.warning {
                     warnings.push(w);
@@ -711,7 +3159,7 @@ pub fn merge(
 
     // Push mainline to origin if requested.
     let pushed = if push {
-        match git::push(repo, &mainline) {
+        match backend.push(repo, &mainline) {
             Ok(()) => true,
             Err(e) => {
                 warnings.push(format!("merge succeeded but push failed: {e}"));
@@ -738,6 +3186,8 @@ mod tests {
     use std::path::PathBuf;
 
     use super::*;
+    use crate::backend::fake::FakeGit;
+    use crate::error::ExitCode;
 
     fn wt(path: &str, branch: Option<&str>, is_main: bool) -> Worktree {
         Worktree {
@@ -748,6 +3198,110 @@ mod tests {
         }
     }
 
+    fn prompt(branch: Option<&str>, dirty: bool) -> PromptStatus {
+        PromptStatus {
+            branch: branch.map(str::to_string),
+            commit: "deadbee".to_string(),
+            ahead: 2,
+            behind: 1,
+            dirty,
+        }
+    }
+
+    #[test]
+    fn prompt_render_fills_placeholders() {
+        assert_eq!(
+            prompt(Some("feature/x"), true).render(PROMPT_FORMAT),
+            "feature/x 2↑1↓ *"
+        );
+        assert_eq!(
+            prompt(Some("feature/x"), false).render("{branch}@{commit} {dirty}"),
+            "feature/x@deadbee"
+        );
+    }
+
+    #[test]
+    fn prompt_render_shows_commit_when_detached() {
+        assert_eq!(prompt(None, false).render(PROMPT_FORMAT), "deadbee 2↑1↓");
+    }
+
+    fn branches<'a>(matches: &[&'a Worktree]) -> Vec<&'a str> {
+        matches
+            .iter()
+            .filter_map(|wt| wt.branch.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn match_worktrees_prefers_exact_then_substring_then_fuzzy() {
+        let worktrees = [
+            wt("/repo", Some("main"), true),
+            wt("/repo/.worktrees/api--1a2b3c4d", Some("api"), false),
+            wt("/repo/.worktrees/api-v2--5e6f7a8b", Some("api-v2"), false),
+            wt(
+                "/repo/.worktrees/feature-auth--9c0d1e2f",
+                Some("feature/auth"),
+                false,
+            ),
+            wt("/repo/.worktrees/scratch--deadbeef", None, false),
+        ];
+
+        assert_eq!(branches(&match_worktrees(&worktrees, "api")), ["api"]);
+        assert_eq!(
+            branches(&match_worktrees(&worktrees, "api-v2--5e6f7a8b")),
+            ["api-v2"]
+        );
+        assert_eq!(
+            branches(&match_worktrees(
+                &worktrees,
+                ".worktrees/feature-auth--9c0d1e2f"
+            )),
+            ["feature/auth"]
+        );
+        assert_eq!(
+            branches(&match_worktrees(&worktrees, "ap")),
+            ["api", "api-v2"]
+        );
+        assert_eq!(
+            branches(&match_worktrees(&worktrees, "fauth")),
+            ["feature/auth"]
+        );
+        assert!(match_worktrees(&worktrees, "scratch").is_empty());
+        assert!(match_worktrees(&worktrees, "zzz").is_empty());
+    }
+
+    #[test]
+    fn match_worktrees_main_aliases_select_the_main_worktree() {
+        let worktrees = [
+            wt("/repo", None, true),
+            wt(
+                "/repo/.worktrees/main-ish--1a2b3c4d",
+                Some("main-ish"),
+                false,
+            ),
+        ];
+        for alias in MAIN_ALIASES {
+            let matches = match_worktrees(&worktrees, alias);
+            assert_eq!(matches.len(), 1);
+            assert!(matches[0].is_main);
+        }
+    }
+
+    #[test]
+    fn go_with_rejects_ambiguous_queries() {
+        let fake = FakeGit::new()
+            .with_worktree("feature/auth-login")
+            .with_worktree("feature/auth-refresh");
+        let err = go_with(&fake, &FakeGit::repo(), &BranchName::new("auth"))
+            .err()
+            .expect("ambiguous");
+        assert_eq!(err.code, ExitCode::Usage);
+        assert!(err.to_string().contains("feature/auth-login"));
+
+        let found = go_with(&fake, &FakeGit::repo(), &BranchName::new("refresh")).expect("unique");
+        assert_eq!(found.branch.as_str(), "feature/auth-refresh");
+    }
+
     #[test]
     fn diff_dirty_dry_run_supports_detached_worktree() {
         let worktree = wt("/repo/.worktrees/detached--abc12345", None, false);
@@ -815,4 +3369,332 @@ mod tests {
         let selected = worktree_for_cwd(&worktrees, &cwd);
         assert!(selected.is_none());
     }
+
+    #[test]
+    fn open_command_substitutes_or_appends_the_path() {
+        let path = Path::new("/repo/.worktrees/my feature--1a2b3c4d");
+        assert_eq!(
+            open_command("code -n {path}", path).expect("template"),
+            ["code", "-n", "/repo/.worktrees/my feature--1a2b3c4d"]
+        );
+        assert_eq!(
+            open_command("tmux new-window -c {path}", path).expect("template"),
+            [
+                "tmux",
+                "new-window",
+                "-c",
+                "/repo/.worktrees/my feature--1a2b3c4d"
+            ]
+        );
+        assert_eq!(
+            open_command("'/opt/My Editor/bin/ed' --wait", path).expect("editor"),
+            [
+                "/opt/My Editor/bin/ed",
+                "--wait",
+                "/repo/.worktrees/my feature--1a2b3c4d"
+            ]
+        );
+    }
+
+    #[test]
+    fn open_command_rejects_empty_and_unbalanced_templates() {
+        let path = Path::new("/repo");
+        assert_eq!(
+            open_command("  ", path).expect_err("empty").code,
+            ExitCode::Usage
+        );
+        assert_eq!(
+            open_command("code 'unterminated", path)
+                .expect_err("unbalanced")
+                .code,
+            ExitCode::Usage
+        );
+    }
+
+    #[test]
+    fn open_display_command_quotes_arguments() {
+        let result = OpenResult {
+            worktree_path: PathBuf::from("/repo/my dir"),
+            command: vec!["code".into(), "-n".into(), "/repo/my dir".into()],
+        };
+        assert_eq!(result.display_command(), "code -n '/repo/my dir'");
+    }
+
+    #[test]
+    fn shell_quote_leaves_plain_paths_alone() {
+        assert_eq!(
+            shell_quote("/repo/.worktrees/a--1"),
+            "/repo/.worktrees/a--1"
+        );
+        assert_eq!(shell_quote("my dir"), "'my dir'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    fn feat(name: &str) -> BranchName {
+        BranchName::new(name)
+    }
+
+    fn merge_fake(fake: &FakeGit, branch: &str, push: bool) -> Result<MergeResult> {
+        merge_with(
+            fake,
+            &FakeGit::repo(),
+            Some(&feat(branch)),
+            None,
+            push,
+            false,
+        )
+    }
+
+    fn reasons(result: &PruneExecuteResult) -> Vec<(Option<&str>, &str)> {
+        result
+            .skipped
+            .iter()
+            .map(|s| (s.branch.as_deref(), s.reason.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn merge_removes_worktree_then_branch() {
+        let fake = FakeGit::new().with_worktree("feat");
+        let result = merge_fake(&fake, "feat", false).expect("merge");
+
+        assert_eq!(result.mainline, "main");
+        assert!(result.cleaned_up);
+        assert_eq!(result.removed_path, Some(FakeGit::path_of("feat")));
+        assert!(!result.pushed);
+        assert!(result.warnings.is_empty());
+        assert_eq!(
+            fake.calls(),
+            [
+                "merge feat".to_string(),
+                format!("worktree remove {}", FakeGit::path_of("feat").display()),
+                "branch -d feat".to_string(),
+            ]
+        );
+        assert_eq!(fake.branches(), ["main"]);
+    }
+
+    #[test]
+    fn merge_no_cleanup_keeps_worktree() {
+        let fake = FakeGit::new().with_worktree("feat");
+        let result = merge_with(
+            &fake,
+            &FakeGit::repo(),
+            Some(&feat("feat")),
+            None,
+            false,
+            true,
+        )
+        .expect("merge");
+
+        assert!(!result.cleaned_up);
+        assert_eq!(result.removed_path, None);
+        assert_eq!(fake.calls(), ["merge feat"]);
+    }
+
+    #[test]
+    fn merge_conflict_aborts() {
+        let fake = FakeGit::new().with_worktree("feat").conflicting("feat");
+        let err = merge_fake(&fake, "feat", true).expect_err("conflict");
+
+        assert_eq!(err.code, ExitCode::Conflict);
+        assert!(err.message.contains("merge aborted"), "{}", err.message);
+        assert_eq!(fake.calls(), ["merge feat", "merge --abort"]);
+    }
+
+    #[test]
+    fn merge_refuses_when_main_is_off_mainline() {
+        let fake = FakeGit::new().with_worktree("feat").main_on("other");
+        let err = merge_fake(&fake, "feat", false).expect_err("wrong branch");
+
+        assert_eq!(err.code, ExitCode::Invariant);
+        assert!(err.message.contains("on 'other', expected 'main'"));
+        assert!(fake.calls().is_empty());
+    }
+
+    #[test]
+    fn merge_into_checks_target_branch() {
+        let fake = FakeGit::new().with_worktree("feat").main_on("release");
+        let result = merge_with(
+            &fake,
+            &FakeGit::repo(),
+            Some(&feat("feat")),
+            Some("release"),
+            true,
+            true,
+        )
+        .expect("merge");
+
+        assert_eq!(result.mainline, "release");
+        assert!(result.pushed);
+        assert_eq!(fake.calls(), ["merge feat", "push release"]);
+    }
+
+    #[test]
+    fn merge_refuses_main_worktree_and_self_merge() {
+        let fake = FakeGit::new().with_worktree("feat");
+        let err = merge_fake(&fake, "main", false).expect_err("main worktree");
+        assert_eq!(err.code, ExitCode::Invariant);
+
+        let err = merge_with(
+            &fake,
+            &FakeGit::repo(),
+            Some(&feat("feat")),
+            Some("feat"),
+            false,
+            false,
+        )
+        .expect_err("self merge");
+        assert_eq!(err.code, ExitCode::Invariant);
+        assert!(fake.calls().is_empty());
+    }
+
+    #[test]
+    fn merge_unknown_branch_is_usage_error() {
+        let fake = FakeGit::new();
+        let err = merge_fake(&fake, "nope", false).expect_err("unknown");
+        assert_eq!(err.code, ExitCode::Usage);
+    }
+
+    #[test]
+    fn merge_downgrades_push_and_cleanup_failures() {
+        let fake = FakeGit::new()
+            .with_worktree("feat")
+            .failing_remove("feat")
+            .failing_push();
+        let result = merge_fake(&fake, "feat", true).expect("merge");
+
+        assert!(!result.cleaned_up);
+        assert!(!result.pushed);
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[0].starts_with("merge succeeded but cleanup failed"));
+        assert!(result.warnings[1].starts_with("merge succeeded but push failed"));
+    }
+
+    #[test]
+    fn merge_reports_branch_delete_failure() {
+        let fake = FakeGit::new().with_worktree("feat").failing_delete("feat");
+        let result = merge_fake(&fake, "feat", false).expect("merge");
+
+        assert!(result.cleaned_up);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("branch deletion failed"));
+    }
+
+    #[test]
+    fn prune_deletes_by_integration_method() {
+        let fake = FakeGit::new()
+            .with_worktree("merged")
+            .with_worktree("rebased")
+            .with_worktree("open")
+            .merged("merged")
+            .rebased("rebased");
+        let result =
+            prune_execute_with(&fake, &FakeGit::repo(), None, false, false).expect("prune");
+
+        let pruned: Vec<_> = result.pruned.iter().map(|p| p.branch.as_deref()).collect();
+        assert_eq!(pruned, [Some("merged"), Some("rebased")]);
+        assert_eq!(reasons(&result), [(Some("open"), "not_integrated")]);
+        assert!(result.warnings.is_empty());
+        let deletes: Vec<_> = fake
+            .calls()
+            .into_iter()
+            .filter(|c| c.starts_with("branch"))
+            .collect();
+        assert_eq!(deletes, ["branch -d merged", "branch -D rebased"]);
+        assert_eq!(fake.branches(), ["main", "open"]);
+    }
+
+    #[test]
+    fn prune_skips_detached_and_failed_removals() {
+        let fake = FakeGit::new()
+            .with_worktree("stuck")
+            .with_worktree("undeletable")
+            .with_detached("/repo/.worktrees/detached")
+            .merged("stuck")
+            .merged("undeletable")
+            .failing_remove("stuck")
+            .failing_delete("undeletable");
+        let result =
+            prune_execute_with(&fake, &FakeGit::repo(), None, false, false).expect("prune");
+
+        assert_eq!(
+            reasons(&result),
+            [(Some("stuck"), "removal_failed"), (None, "no_branch")]
+        );
+        assert_eq!(result.pruned.len(), 1);
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[0].starts_with("failed to remove worktree for 'stuck'"));
+        assert!(result.warnings[1].contains("branch deletion failed for 'undeletable'"));
+    }
+
+    #[test]
+    fn prune_force_deletes_with_capital_d() {
+        let fake = FakeGit::new().with_worktree("feat").merged("feat");
+        prune_execute_with(&fake, &FakeGit::repo(), None, true, false).expect("prune");
+
+        assert_eq!(
+            fake.calls(),
+            [
+                format!(
+                    "worktree remove --force {}",
+                    FakeGit::path_of("feat").display()
+                ),
+                "branch -D feat".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn prune_rejects_bad_mainline_and_ttl() {
+        let fake = FakeGit::new().with_worktree("feat");
+        let err = prune_execute_with(&fake, &FakeGit::repo(), Some("nope"), false, false)
+            .expect_err("bad mainline");
+        assert_eq!(err.code, ExitCode::Usage);
+
+        let fake = FakeGit::new().with_config("wt.reviewTtl", "soon");
+        let err = prune_dry_run_with(&fake, &FakeGit::repo(), None, false).expect_err("bad ttl");
+        assert_eq!(err.code, ExitCode::Usage);
+        assert!(err.message.contains("wt.reviewTtl"));
+    }
+
+    #[test]
+    fn overview_reads_dirty_and_lock_state_from_the_backend() {
+        let fake = FakeGit::new()
+            .with_worktree("feat")
+            .with_worktree("wip")
+            .dirty("wip")
+            .locked("feat");
+        let overview = overview_with(&fake, &FakeGit::repo()).expect("overview");
+
+        let states: Vec<_> = overview
+            .entries
+            .iter()
+            .map(|e| (e.worktree.branch.as_deref(), e.dirty, e.locked))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                (Some("main"), Some(false), false),
+                (Some("feat"), Some(false), true),
+                (Some("wip"), Some(true), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn lock_and_unlock_go_through_the_backend() {
+        let fake = FakeGit::new().with_worktree("feat");
+        let path = FakeGit::path_of("feat");
+        lock_with(&fake, &FakeGit::repo(), &path, Some("review")).expect("lock");
+        assert!(fake.is_locked(&path));
+        lock_with(&fake, &FakeGit::repo(), &path, None).expect_err("locked twice");
+        unlock_with(&fake, &FakeGit::repo(), &path).expect("unlock");
+        assert!(!fake.is_locked(&path));
+
+        let lock = format!("worktree lock {}", path.display());
+        let unlock = format!("worktree unlock {}", path.display());
+        assert_eq!(fake.calls(), [lock.clone(), lock, unlock]);
+    }
 }
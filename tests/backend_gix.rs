@@ -0,0 +1,262 @@
+//! Differential tests: the gitoxide backend must answer read-only queries
+//! exactly like the `git` subprocess backend.
+#![cfg(feature = "gix")]
+
+mod fixtures;
+
+use std::path::PathBuf;
+
+use wt_core::backend::{GitBackend, GixBackend, ProcessGit};
+use wt_core::domain::{BranchName, RepoRoot};
+use wt_core::worktree::{self, AddOptions};
+
+fn root(path: &std::path::Path) -> RepoRoot {
+    worktree::repo_root(path).expect("repo root")
+}
+
+fn add(root: &RepoRoot, branch: &str) -> PathBuf {
+    worktree::add(root, &BranchName::new(branch), &AddOptions::default())
+        .expect("add")
+        .worktree_path
+}
+
+fn listing(
+    backend: &dyn GitBackend,
+    root: &RepoRoot,
+) -> Vec<(PathBuf, Option<String>, String, bool)> {
+    let mut listed: Vec<_> = backend
+        .list_worktrees(root)
+        .expect("list")
+        .into_iter()
+        .map(|wt| (wt.path, wt.branch, wt.commit, wt.is_main))
+        .collect();
+    // Git reports linked worktrees in directory order; only main is pinned.
+    listed[1..].sort();
+    listed
+}
+
+fn assert_same_listing(root: &RepoRoot) {
+    let native = GixBackend::open(root).expect("open");
+    assert_eq!(listing(&native, root), listing(&ProcessGit, root));
+}
+
+#[test]
+fn worktree_listings_match() {
+    let repo = fixtures::TestRepo::new();
+    let root = root(&repo.path());
+    assert_same_listing(&root);
+
+    let feature = add(&root, "feature/a");
+    add(&root, "feature/b");
+    fixtures::commit_file(&feature, "a.txt", "a\n", "add a");
+    fixtures::run_git(
+        &["worktree", "add", "--detach", ".worktrees/detached"],
+        &repo.path(),
+    );
+    assert_same_listing(&root);
+}
+
+#[test]
+fn listings_skip_deleted_worktrees_unless_locked() {
+    let repo = fixtures::TestRepo::new();
+    let root = root(&repo.path());
+    let gone = add(&root, "feature/gone");
+    let locked = add(&root, "feature/locked");
+    fixtures::run_git(
+        &["worktree", "lock", &locked.display().to_string()],
+        &repo.path(),
+    );
+    std::fs::remove_dir_all(&gone).expect("remove gone");
+    std::fs::remove_dir_all(&locked).expect("remove locked");
+
+    let native = GixBackend::open(&root).expect("open");
+    let listed = listing(&native, &root);
+    assert_eq!(listed, listing(&ProcessGit, &root));
+    assert!(listed.iter().any(|wt| wt.0 == locked));
+    assert!(!listed.iter().any(|wt| wt.0 == gone));
+}
+
+#[test]
+fn refs_and_mainline_match() {
+    let repo = fixtures::ClonedTestRepo::new();
+    let root = root(&repo.path());
+    add(&root, "feature/x");
+    let native = GixBackend::open(&root).expect("open");
+
+    assert_eq!(
+        native.resolve_mainline(&root).expect("native mainline"),
+        ProcessGit
+            .resolve_mainline(&root)
+            .expect("process mainline")
+    );
+    for rev in [
+        "HEAD",
+        "main",
+        "origin/main",
+        "refs/heads/feature/x",
+        "nope",
+        "HEAD~5",
+    ] {
+        assert_eq!(
+            native.rev_exists(&root, rev),
+            ProcessGit.rev_exists(&root, rev),
+            "rev_exists({rev})"
+        );
+    }
+    for branch in ["main", "feature/x", "feature/y"] {
+        let branch = BranchName::new(branch);
+        assert_eq!(
+            native.branch_exists(&root, &branch),
+            ProcessGit.branch_exists(&root, &branch),
+            "branch_exists({branch})"
+        );
+    }
+}
+
+#[test]
+fn mainline_falls_back_without_origin() {
+    let repo = fixtures::TestRepo::new();
+    let root = root(&repo.path());
+    fixtures::run_git(&["branch", "-m", "trunk"], &repo.path());
+    let native = GixBackend::open(&root).expect("open");
+
+    assert_eq!(native.resolve_mainline(&root).expect("native"), "trunk");
+    assert_eq!(
+        ProcessGit.resolve_mainline(&root).expect("process"),
+        "trunk"
+    );
+}
+
+#[test]
+fn ancestry_and_stats_match() {
+    let repo = fixtures::TestRepo::new();
+    let root = root(&repo.path());
+    let merged = add(&root, "feature/merged");
+    let ahead = add(&root, "feature/ahead");
+    fixtures::commit_file(&merged, "m.txt", "m\n", "merged work");
+    fixtures::run_git(
+        &["merge", "--no-ff", "-m", "merge", "feature/merged"],
+        &repo.path(),
+    );
+    fixtures::commit_file(&ahead, "a.txt", "a\n", "ahead 1");
+    fixtures::commit_file(&ahead, "b.txt", "b\nb\n", "ahead 2");
+    fixtures::commit_file(&repo.path(), "main.txt", "main\n", "main moves on");
+    let native = GixBackend::open(&root).expect("open");
+
+    for branch in ["feature/merged", "feature/ahead", "main", "missing"] {
+        assert_eq!(
+            native.is_ancestor(&root, branch, "main"),
+            ProcessGit.is_ancestor(&root, branch, "main"),
+            "is_ancestor({branch})"
+        );
+    }
+    for branch in ["feature/merged", "feature/ahead"] {
+        let n = native
+            .worktree_stats(&root, "main", branch)
+            .expect("native");
+        let p = ProcessGit
+            .worktree_stats(&root, "main", branch)
+            .expect("process");
+        assert_eq!(
+            (
+                n.commits_ahead,
+                n.commits_behind,
+                n.files_changed,
+                n.insertions
+            ),
+            (
+                p.commits_ahead,
+                p.commits_behind,
+                p.files_changed,
+                p.insertions
+            ),
+            "stats({branch})"
+        );
+    }
+}
+
+#[test]
+fn ahead_behind_match_across_merges() {
+    let repo = fixtures::TestRepo::new();
+    let root = root(&repo.path());
+    let main = repo.path();
+    let early = add(&root, "feature/early");
+    for i in 0..8 {
+        fixtures::commit_file(&main, &format!("m{i}.txt"), "m\n", "main work");
+    }
+    let synced = add(&root, "feature/synced");
+    fixtures::commit_file(&synced, "s.txt", "s\n", "synced work");
+    fixtures::commit_file(&early, "e.txt", "e\n", "early work");
+    fixtures::run_git(&["merge", "-q", "--no-edit", "main"], &early);
+    fixtures::commit_file(&early, "e2.txt", "e\n", "early after sync");
+    for i in 0..3 {
+        fixtures::commit_file(&main, &format!("n{i}.txt"), "n\n", "main moves on");
+    }
+    fixtures::run_git(&["merge", "-q", "--no-edit", "feature/synced"], &main);
+    fixtures::run_git(&["merge", "-q", "--no-edit", "main"], &synced);
+    let native = GixBackend::open(&root).expect("open");
+
+    for branch in ["feature/early", "feature/synced", "main"] {
+        let n = native
+            .worktree_stats(&root, "main", branch)
+            .expect("native");
+        let p = ProcessGit
+            .worktree_stats(&root, "main", branch)
+            .expect("process");
+        assert_eq!(
+            (n.commits_ahead, n.commits_behind),
+            (p.commits_ahead, p.commits_behind),
+            "stats({branch})"
+        );
+    }
+}
+
+#[test]
+fn config_reads_match_per_worktree() {
+    let repo = fixtures::TestRepo::new();
+    let root = root(&repo.path());
+    let sparse = add(&root, "feature/sparse");
+    let plain = add(&root, "feature/plain");
+    fixtures::run_git(&["sparse-checkout", "set", "--cone", "docs"], &repo.path());
+    fixtures::run_git(&["sparse-checkout", "set", "--cone", "src"], &sparse);
+    fixtures::run_git(&["config", "wt.tmux", "true"], &repo.path());
+    fixtures::run_git(&["config", "--worktree", "wt.tmux", "false"], &plain);
+    let native = GixBackend::open(&root).expect("open");
+
+    for path in [repo.path(), sparse, plain] {
+        for key in ["core.sparseCheckout", "wt.tmux", "wt.unset"] {
+            assert_eq!(
+                native.config_get(&path, key).expect("native"),
+                ProcessGit.config_get(&path, key).expect("process"),
+                "config_get({}, {key})",
+                path.display()
+            );
+        }
+    }
+}
+
+/// `list` and `go` must not spawn `git` with the native backend: the
+/// subprocess count, not wall time, is what keeps them within budget.
+#[test]
+fn list_and_go_run_no_git_processes() {
+    let repo = fixtures::TestRepo::new();
+    let root = root(&repo.path());
+    add(&root, "feature/a");
+    add(&root, "feature/b");
+
+    for args in [&["list"][..], &["list", "--json"], &["go", "feature/a"]] {
+        let trace = tempfile::NamedTempFile::new().expect("trace file");
+        assert_cmd::Command::new(assert_cmd::cargo_bin!("wt-core"))
+            .args(args)
+            .current_dir(repo.path())
+            .env("GIT_TRACE", trace.path())
+            .assert()
+            .success();
+        let trace = std::fs::read_to_string(trace.path()).expect("read trace");
+        let runs: Vec<&str> = trace
+            .lines()
+            .filter(|l| l.contains("trace: built-in: git"))
+            .collect();
+        assert!(runs.is_empty(), "{args:?} ran git: {runs:#?}");
+    }
+}
@@ -0,0 +1,145 @@
+#!/usr/bin/env pwsh
+# Integration tests for the PowerShell binding.
+# Requires wt-core on PATH.
+
+$ErrorActionPreference = 'Stop'
+
+. (Join-Path $PSScriptRoot '../../bindings/powershell/wt.ps1')
+
+$Work = Join-Path ([System.IO.Path]::GetTempPath()) ([System.IO.Path]::GetRandomFileName())
+New-Item -ItemType Directory -Path $Work | Out-Null
+
+function Pass([string]$Message) { Write-Host "  ✓ $Message" }
+function Fail([string]$Message) {
+    Write-Host "  ✗ $Message"
+    Set-Location /
+    Remove-Item -Recurse -Force $Work
+    exit 1
+}
+function Current { (Resolve-Path .).ProviderPath }
+
+# ── Setup ────────────────────────────────────────────────────────────
+git init (Join-Path $Work 'repo') *> $null
+Set-Location (Join-Path $Work 'repo')
+$RepoPath = Current
+git config user.name  "test"
+git config user.email "test@test.com"
+git commit --allow-empty -m "initial" *> $null
+
+Write-Host "Running powershell binding tests..."
+
+# ── wt add ───────────────────────────────────────────────────────────
+wt add feat-one *> $null
+if ((Current) -like '*.worktrees*feat-one*') {
+    Pass "wt add: cd into new worktree"
+} else {
+    Fail "wt add: expected cwd inside .worktrees/…feat-one…, got $(Current)"
+}
+
+$WtPath = Current
+
+# ── wt list ──────────────────────────────────────────────────────────
+$output = (wt list 2>&1) -join "`n"
+if ($output -like '*feat-one*') {
+    Pass "wt list: output contains branch name"
+} else {
+    Fail "wt list: 'feat-one' not found in output"
+}
+
+# ── wt go ────────────────────────────────────────────────────────────
+Set-Location $RepoPath
+wt go feat-one *> $null
+if ((Current) -eq $WtPath) {
+    Pass "wt go: cd into existing worktree"
+} else {
+    Fail "wt go: expected $WtPath, got $(Current)"
+}
+
+# ── JSON events ──────────────────────────────────────────────────────
+Set-Location $RepoPath
+$json = (wt go feat-one --json) -join "`n"
+if ($json -like '*"event":"switch"*') {
+    Pass "wt go --json: JSON passed through"
+} else {
+    Fail "wt go --json: expected a switch event, got $json"
+}
+if ((Current) -eq $WtPath) {
+    Pass "wt go --json: followed switch event"
+} else {
+    Fail "wt go --json: expected $WtPath, got $(Current)"
+}
+
+# ── help passthrough safety ─────────────────────────────────────────
+$addHelp = (wt add --help 2>&1) -join "`n"
+if ($addHelp -like '*Usage: wt-core add*') {
+    Pass "wt add --help: passthrough to core help"
+} else {
+    Fail "wt add --help: expected core help output"
+}
+if ((Current) -eq $WtPath) {
+    Pass "wt add --help: cwd unchanged"
+} else {
+    Fail "wt add --help: cwd changed unexpectedly"
+}
+
+$goHelp = (wt go --help 2>&1) -join "`n"
+if ($goHelp -like '*Usage: wt-core go*') {
+    Pass "wt go --help: passthrough to core help"
+} else {
+    Fail "wt go --help: expected core help output"
+}
+if ((Current) -eq $WtPath) {
+    Pass "wt go --help: cwd unchanged"
+} else {
+    Fail "wt go --help: cwd changed unexpectedly"
+}
+
+$rmHelp = (wt remove --help 2>&1) -join "`n"
+if ($rmHelp -like '*Usage: wt-core remove*') {
+    Pass "wt remove --help: passthrough to core help"
+} else {
+    Fail "wt remove --help: expected core help output"
+}
+if (Test-Path $WtPath) {
+    Pass "wt remove --help: worktree not removed"
+} else {
+    Fail "wt remove --help: worktree was removed unexpectedly"
+}
+if ((Current) -eq $WtPath) {
+    Pass "wt remove --help: cwd unchanged"
+} else {
+    Fail "wt remove --help: cwd changed unexpectedly"
+}
+
+# ── wt remove (from inside worktree) ────────────────────────────────
+wt remove feat-one *> $null
+if ((Current) -eq $RepoPath) {
+    Pass "wt remove: cd back to repo root"
+} else {
+    Fail "wt remove: expected $RepoPath, got $(Current)"
+}
+
+if (-not (Test-Path $WtPath)) {
+    Pass "wt remove: worktree directory deleted"
+} else {
+    Fail "wt remove: $WtPath still exists"
+}
+
+# ── wt remove --json (reset event) ──────────────────────────────────
+wt add feat-two *> $null
+$WtTwo = Current
+$json = (wt remove feat-two --json) -join "`n"
+if ($json -like '*"event":"reset"*' -and (Current) -eq $RepoPath) {
+    Pass "wt remove --json: followed reset event"
+} else {
+    Fail "wt remove --json: expected $RepoPath, got $(Current)"
+}
+if (-not (Test-Path $WtTwo)) {
+    Pass "wt remove --json: worktree directory deleted"
+} else {
+    Fail "wt remove --json: $WtTwo still exists"
+}
+
+Set-Location /
+Remove-Item -Recurse -Force $Work
+Write-Host "All powershell binding tests passed."
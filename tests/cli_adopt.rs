@@ -0,0 +1,246 @@
+mod fixtures;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+use fixtures::{find_worktree_dir, run_git};
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+/// Create a worktree for a new `branch` with plain `git worktree add`.
+fn git_worktree_add(repo: &Path, dir: &Path, branch: &str) {
+    run_git(
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            branch,
+            &dir.display().to_string(),
+        ],
+        repo,
+    );
+}
+
+fn outside_dir(name: &str) -> (TempDir, PathBuf) {
+    let tmp = TempDir::new().expect("tempdir");
+    let dir = tmp.path().canonicalize().expect("canonicalize").join(name);
+    (tmp, dir)
+}
+
+fn adopt_json(repo: &Path, args: &[&str]) -> serde_json::Value {
+    let repo_str = repo.display().to_string();
+    let mut full = vec!["adopt"];
+    full.extend_from_slice(args);
+    full.extend(["--repo", &repo_str, "--json"]);
+    let output = wt_core()
+        .args(&full)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+fn is_symlink(path: &Path) -> bool {
+    path.symlink_metadata()
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+#[test]
+fn adopt_moves_worktree_and_applies_symlinks() {
+    let repo = fixtures::TestRepo::new();
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    fs::create_dir_all(repo.path().join(".wt")).expect("mkdir");
+    fs::write(repo.path().join(".wt/symlinks"), "node_modules\n").expect("write");
+    let (_tmp, outside) = outside_dir("foo");
+    git_worktree_add(&repo.path(), &outside, "feat/foo");
+
+    let json = adopt_json(&repo.path(), &[&outside.display().to_string()]);
+
+    let entry = &json["worktrees"][0];
+    assert_eq!(entry["branch"], "feat/foo");
+    assert_eq!(entry["status"], "moved");
+    assert_eq!(entry["from"], outside.display().to_string());
+    let to = find_worktree_dir(&repo.path(), "feat-foo");
+    assert_eq!(entry["to"], to.display().to_string());
+    assert_eq!(entry["links"][0]["path"], "node_modules");
+    assert_eq!(entry["links"][0]["action"], "created");
+    assert!(!outside.exists());
+    assert!(to.join("README.md").exists());
+    assert!(is_symlink(&to.join("node_modules")));
+}
+
+#[test]
+fn adopt_dry_run_leaves_worktree_in_place() {
+    let repo = fixtures::TestRepo::new();
+    let (_tmp, outside) = outside_dir("preview");
+    git_worktree_add(&repo.path(), &outside, "feat/preview");
+
+    let json = adopt_json(&repo.path(), &["--all", "--dry-run"]);
+
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(json["worktrees"][0]["status"], "moved");
+    assert!(outside.exists());
+    assert!(!repo.path().join(".worktrees").exists());
+}
+
+#[test]
+fn adopt_all_relocates_only_unmanaged_worktrees() {
+    let repo = fixtures::TestRepo::new();
+    wt_core()
+        .args([
+            "add",
+            "feat/managed",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .success();
+    wt_core()
+        .args([
+            "add",
+            "--detach",
+            "HEAD",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .success();
+    let renamed = repo.path().join(".worktrees/custom-name");
+    git_worktree_add(&repo.path(), &renamed, "feat/renamed");
+    let (_tmp, outside) = outside_dir("outside");
+    git_worktree_add(&repo.path(), &outside, "feat/outside");
+
+    let json = adopt_json(&repo.path(), &["--all"]);
+
+    let mut branches: Vec<_> = json["worktrees"]
+        .as_array()
+        .expect("worktrees")
+        .iter()
+        .map(|w| w["branch"].as_str().expect("branch").to_string())
+        .collect();
+    branches.sort();
+    assert_eq!(branches, ["feat/outside", "feat/renamed"]);
+    assert!(!renamed.exists() && !outside.exists());
+    assert!(find_worktree_dir(&repo.path(), "feat-renamed").exists());
+
+    let json = adopt_json(&repo.path(), &["--all"]);
+    assert_eq!(json["worktrees"], serde_json::json!([]));
+}
+
+#[test]
+fn adopt_all_reports_failures_and_continues() {
+    let repo = fixtures::TestRepo::new();
+    let (_tmp, outside) = outside_dir("blocked");
+    git_worktree_add(&repo.path(), &outside, "feat/blocked");
+    let (_tmp2, other) = outside_dir("other");
+    git_worktree_add(&repo.path(), &other, "feat/other");
+    let json = adopt_json(&repo.path(), &["--all", "--dry-run"]);
+    let blocked_to = json["worktrees"]
+        .as_array()
+        .expect("worktrees")
+        .iter()
+        .find(|w| w["branch"] == "feat/blocked")
+        .map(|w| PathBuf::from(w["to"].as_str().expect("to")))
+        .expect("blocked entry");
+    fs::create_dir_all(&blocked_to).expect("mkdir");
+
+    let json = adopt_json(&repo.path(), &["--all"]);
+
+    assert_eq!(json["ok"], false);
+    let entries = json["worktrees"].as_array().expect("worktrees");
+    let blocked = entries
+        .iter()
+        .find(|w| w["branch"] == "feat/blocked")
+        .expect("blocked");
+    assert_eq!(blocked["status"], "failed");
+    assert!(blocked["reason"]
+        .as_str()
+        .expect("reason")
+        .contains("already exists"));
+    assert!(outside.exists());
+    assert!(!other.exists(), "other worktree still adopted");
+}
+
+#[test]
+fn adopt_defaults_to_current_worktree() {
+    let repo = fixtures::TestRepo::new();
+    let (_tmp, outside) = outside_dir("cwd");
+    git_worktree_add(&repo.path(), &outside, "feat/cwd");
+
+    wt_core()
+        .args(["adopt", "--dry-run"])
+        .current_dir(&outside)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feat/cwd"))
+        .stdout(predicate::str::contains(format!(
+            "Would move {} -> ",
+            outside.display()
+        )))
+        .stdout(predicate::str::contains("1 worktree would be adopted."));
+}
+
+#[test]
+fn adopt_reports_managed_worktree_unchanged() {
+    let repo = fixtures::TestRepo::new();
+    wt_core()
+        .args([
+            "add",
+            "feat/home",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .success();
+    let wt = find_worktree_dir(&repo.path(), "feat-home");
+
+    let json = adopt_json(&repo.path(), &[&wt.display().to_string()]);
+
+    assert_eq!(json["worktrees"][0]["status"], "unchanged");
+    assert_eq!(json["worktrees"][0]["to"], wt.display().to_string());
+}
+
+#[test]
+fn adopt_refuses_main_worktree() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args(["adopt"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("main worktree cannot be adopted"));
+}
+
+#[test]
+fn adopt_single_target_conflict_is_an_error() {
+    let repo = fixtures::TestRepo::new();
+    let (_tmp, outside) = outside_dir("taken");
+    git_worktree_add(&repo.path(), &outside, "feat/taken");
+    let json = adopt_json(&repo.path(), &[&outside.display().to_string(), "--dry-run"]);
+    let to = PathBuf::from(json["worktrees"][0]["to"].as_str().expect("to"));
+    fs::create_dir_all(&to).expect("mkdir");
+
+    wt_core()
+        .args([
+            "adopt",
+            &outside.display().to_string(),
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains("already exists"));
+}
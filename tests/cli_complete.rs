@@ -0,0 +1,134 @@
+mod fixtures;
+
+use std::path::Path;
+
+use assert_cmd::Command;
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+fn candidates(repo: &Path, context: &str) -> Vec<String> {
+    let output = wt_core()
+        .args(["complete", context, "--repo", &repo.display().to_string()])
+        .assert()
+        .success();
+    String::from_utf8_lossy(&output.get_output().stdout)
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn add(repo: &Path, branch: &str) {
+    wt_core()
+        .args(["add", branch, "--repo", &repo.display().to_string()])
+        .assert()
+        .success();
+}
+
+#[test]
+fn complete_worktrees_lists_checked_out_branches() {
+    let repo = fixtures::TestRepo::new();
+    add(&repo.path(), "feature/b");
+    add(&repo.path(), "feature/a");
+    fixtures::run_git(&["branch", "idle"], &repo.path());
+
+    assert_eq!(
+        candidates(&repo.path(), "worktrees"),
+        ["feature/a", "feature/b", "main"]
+    );
+}
+
+#[test]
+fn complete_branches_skips_branches_with_worktrees() {
+    let repo = fixtures::ClonedTestRepo::new();
+    add(&repo.path(), "feature/taken");
+    fixtures::run_git(&["branch", "local-only"], &repo.path());
+    fixtures::run_git(&["push", "origin", "local-only:remote-only"], &repo.path());
+    fixtures::run_git(&["fetch", "origin"], &repo.path());
+
+    assert_eq!(
+        candidates(&repo.path(), "branches"),
+        ["local-only", "remote-only"]
+    );
+}
+
+#[test]
+fn complete_revisions_lists_branches_remotes_and_tags() {
+    let repo = fixtures::ClonedTestRepo::new();
+    fixtures::run_git(&["tag", "v1.0"], &repo.path());
+
+    let revisions = candidates(&repo.path(), "revisions");
+
+    assert_eq!(revisions, ["main", "origin/main", "v1.0"]);
+}
+
+#[test]
+fn complete_outside_repo_prints_nothing() {
+    let dir = tempfile::TempDir::new().expect("tempdir");
+
+    wt_core()
+        .args(["complete", "worktrees"])
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("");
+}
+
+#[test]
+fn complete_is_hidden_from_help() {
+    let output = wt_core().arg("--help").assert().success();
+    let help = String::from_utf8_lossy(&output.get_output().stdout).into_owned();
+
+    assert!(!help.contains("complete"));
+}
+
+/// Drive the Bash completion function the way `complete -F` would.
+#[test]
+fn bash_binding_completes_live_candidates() {
+    if std::process::Command::new("bash")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        return;
+    }
+    let repo = fixtures::TestRepo::new();
+    add(&repo.path(), "feature/wt");
+    fixtures::run_git(&["branch", "idle"], &repo.path());
+    fixtures::run_git(&["tag", "v1.0"], &repo.path());
+    let init = wt_core().args(["init", "bash"]).assert().success();
+    let script = repo.dir.path().join("wt-init.bash");
+    std::fs::write(&script, &init.get_output().stdout).expect("write init script");
+    let bin_dir = Path::new(assert_cmd::cargo_bin!("wt-core"))
+        .parent()
+        .expect("bin dir")
+        .to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let run = |words: &str| -> String {
+        let output = std::process::Command::new("bash")
+            .arg("-c")
+            .arg(format!(
+                "source '{}'; COMP_WORDS=({words}); COMP_CWORD=$((${{#COMP_WORDS[@]}}-1)); \
+                 _wt_complete wt \"${{COMP_WORDS[COMP_CWORD]}}\" \"${{COMP_WORDS[COMP_CWORD-1]}}\"; \
+                 printf '%s\\n' \"${{COMPREPLY[@]}}\"",
+                script.display()
+            ))
+            .current_dir(repo.path())
+            .env("PATH", &path)
+            .output()
+            .expect("run bash");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    assert_eq!(run("wt go ''"), "feature/wt\nmain");
+    assert_eq!(run("wt add ''"), "idle");
+    assert_eq!(run("wt add --base v"), "v1.0");
+    assert_eq!(run("wt go --js"), "--json");
+}
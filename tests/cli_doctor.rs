@@ -0,0 +1,528 @@
+mod fixtures;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command as StdCommand;
+use std::time::{Duration, SystemTime};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use fixtures::{commit_file, run_git};
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+fn write_config(repo: &Path, content: &str) {
+    fs::create_dir_all(repo.join(".wt")).expect("mkdir .wt");
+    fs::write(repo.join(".wt/symlinks"), content).expect("write config");
+}
+
+fn add_worktree(repo: &Path, branch: &str) -> PathBuf {
+    let output = wt_core()
+        .args([
+            "add",
+            branch,
+            "--repo",
+            &repo.display().to_string(),
+            "--print-cd-path",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    PathBuf::from(String::from_utf8(output).expect("utf8").trim())
+}
+
+fn doctor_json(repo: &Path, args: &[&str]) -> serde_json::Value {
+    let repo_str = repo.display().to_string();
+    let mut full = vec!["doctor"];
+    full.extend_from_slice(args);
+    full.extend(["--repo", &repo_str, "--json"]);
+    let output = wt_core()
+        .args(&full)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+/// Private git directory of a linked worktree.
+fn git_dir(wt: &Path) -> PathBuf {
+    let gitfile = fs::read_to_string(wt.join(".git")).expect("read .git");
+    PathBuf::from(gitfile.trim_start_matches("gitdir: ").trim())
+}
+
+/// Warning codes reported by doctor.
+fn doctor_codes(repo: &Path) -> Vec<String> {
+    doctor_json(repo, &[])["diagnostics"]
+        .as_array()
+        .expect("diagnostics")
+        .iter()
+        .filter(|d| d["level"] != "ok")
+        .map(|d| d["code"].as_str().expect("code").to_string())
+        .collect()
+}
+
+/// Doctor diagnostics as `(message, fix)` pairs, skipping the summary line
+/// and the `.worktrees/` ignore check that fires in every fixture repo.
+fn doctor_findings(repo: &Path) -> Vec<(String, Option<String>)> {
+    doctor_json(repo, &[])["diagnostics"]
+        .as_array()
+        .expect("diagnostics")
+        .iter()
+        .filter(|d| d["level"] != "ok" && d["code"] != "worktrees_not_ignored")
+        .map(|d| {
+            (
+                d["message"].as_str().expect("message").to_string(),
+                d["fix"].as_str().map(str::to_string),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn doctor_reports_missing_shared_entry() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feat/late");
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n");
+
+    let findings = doctor_findings(&repo.path());
+
+    assert_eq!(findings.len(), 1, "{findings:?}");
+    assert!(findings[0]
+        .0
+        .starts_with("shared entry missing: node_modules"));
+    assert_eq!(findings[0].1.as_deref(), Some("wt link feat/late"));
+}
+
+#[test]
+fn doctor_reports_dangling_link() {
+    let repo = fixtures::TestRepo::new();
+    fs::write(repo.path().join(".env"), "A=1").expect("write");
+    write_config(&repo.path(), ".env\n");
+    add_worktree(&repo.path(), "feat/dangling");
+    fs::remove_file(repo.path().join(".env")).expect("rm");
+
+    let findings = doctor_findings(&repo.path());
+
+    assert_eq!(findings.len(), 1, "{findings:?}");
+    assert!(findings[0].0.starts_with("dangling shared link"));
+    assert_eq!(
+        findings[0].1.as_deref(),
+        Some("wt link feat/dangling --prune")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn doctor_reports_wrong_target_and_replaced_entries() {
+    let repo = fixtures::TestRepo::new();
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    fs::create_dir(repo.path().join(".venv")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n.venv\n");
+    let wt = add_worktree(&repo.path(), "feat/drift");
+
+    fs::remove_file(wt.join("node_modules")).expect("rm");
+    std::os::unix::fs::symlink("/tmp", wt.join("node_modules")).expect("symlink");
+    fs::remove_file(wt.join(".venv")).expect("rm");
+    fs::create_dir(wt.join(".venv")).expect("mkdir");
+
+    let findings = doctor_findings(&repo.path());
+
+    assert_eq!(findings.len(), 2, "{findings:?}");
+    assert!(findings[0]
+        .0
+        .starts_with("shared link points to the wrong target"));
+    assert_eq!(findings[0].1.as_deref(), Some("wt link feat/drift"));
+    assert!(findings[1].0.starts_with("shared entry replaced"));
+    assert_eq!(
+        findings[1].1.as_deref(),
+        Some(
+            format!(
+                "rm -rf {} && wt link feat/drift",
+                wt.join(".venv").display()
+            )
+            .as_str()
+        )
+    );
+}
+
+#[test]
+fn doctor_reports_tracked_shared_link() {
+    let repo = fixtures::TestRepo::new();
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n");
+    let wt = add_worktree(&repo.path(), "feat/tracked");
+    run_git(&["add", "-f", "node_modules"], &wt);
+
+    let findings = doctor_findings(&repo.path());
+
+    assert_eq!(findings.len(), 1, "{findings:?}");
+    assert!(findings[0].0.starts_with("shared link is tracked by git"));
+    assert_eq!(
+        findings[0].1.as_deref(),
+        Some(format!("git -C {} rm --cached node_modules", wt.display()).as_str())
+    );
+}
+
+#[test]
+fn doctor_human_prints_fix_command() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feat/human");
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n");
+
+    wt_core()
+        .args(["doctor", "--repo", &repo.path().display().to_string()])
+        .assert()
+        .stdout(predicate::str::contains(
+            "⚠ shared entry missing: node_modules",
+        ))
+        .stdout(predicate::str::contains("    fix: wt link feat/human"));
+}
+
+#[test]
+fn doctor_quiet_when_links_are_healthy() {
+    let repo = fixtures::TestRepo::new();
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n");
+    add_worktree(&repo.path(), "feat/healthy");
+
+    assert!(doctor_findings(&repo.path()).is_empty());
+}
+
+#[test]
+fn doctor_reports_codes_and_fixable_flags() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feat/codes");
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n");
+
+    let json = doctor_json(&repo.path(), &[]);
+
+    let diags = json["diagnostics"].as_array().expect("diagnostics");
+    assert_eq!(diags[0]["code"], "worktrees_not_ignored");
+    assert_eq!(diags[0]["fixable"], true);
+    assert_eq!(diags[1]["code"], "link_missing");
+    assert_eq!(diags[1]["fixable"], true);
+    assert!(json.get("fixes").is_none());
+}
+
+#[test]
+fn doctor_fix_dry_run_changes_nothing() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feat/preview");
+    let orphan = repo.path().join(".worktrees/leftover");
+    fs::create_dir_all(&orphan).expect("mkdir");
+
+    let json = doctor_json(&repo.path(), &["--fix", "--dry-run"]);
+
+    assert_eq!(json["dry_run"], true);
+    let fixes = json["fixes"].as_array().expect("fixes");
+    assert_eq!(fixes.len(), 2, "{fixes:?}");
+    assert_eq!(fixes[0]["code"], "worktrees_not_ignored");
+    assert_eq!(fixes[1]["code"], "orphaned_directory");
+    assert!(fixes[1]["message"]
+        .as_str()
+        .expect("message")
+        .starts_with("Would remove orphaned directory"));
+    assert!(orphan.exists());
+    assert_eq!(
+        doctor_codes(&repo.path()),
+        ["worktrees_not_ignored", "orphaned_directory"]
+    );
+}
+
+#[test]
+fn doctor_fix_repairs_safe_findings() {
+    let repo = fixtures::TestRepo::new();
+    let linked = add_worktree(&repo.path(), "feat/links");
+    let detached = add_worktree(&repo.path(), "feat/detached");
+    let gone = add_worktree(&repo.path(), "feat/gone");
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n");
+    let orphan = repo.path().join(".worktrees/leftover");
+    fs::create_dir_all(&orphan).expect("mkdir");
+    run_git(&["checkout", "-q", "--detach"], &detached);
+    fs::remove_dir_all(&gone).expect("rm");
+
+    assert_eq!(
+        doctor_codes(&repo.path()),
+        [
+            "worktrees_not_ignored",
+            "orphaned_directory",
+            "missing_directory",
+            "detached_head",
+            "link_missing",
+            "link_missing",
+        ]
+    );
+
+    let json = doctor_json(&repo.path(), &["--fix"]);
+
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["dry_run"], false);
+    let fixes = json["fixes"].as_array().expect("fixes");
+    assert!(fixes.iter().all(|f| f["ok"] == true), "{fixes:?}");
+    assert!(!orphan.exists());
+    assert!(linked.join("node_modules").exists());
+    let exclude = fs::read_to_string(repo.path().join(".git/info/exclude")).expect("exclude");
+    assert!(exclude.lines().any(|l| l == ".worktrees/"));
+    let head = fs::read_to_string(git_dir(&detached).join("HEAD")).expect("read HEAD");
+    assert_eq!(head.trim(), "ref: refs/heads/feat/detached");
+    assert!(!gone.exists());
+    assert!(doctor_codes(&repo.path()).is_empty());
+}
+
+#[test]
+fn doctor_fix_reregisters_orphan_whose_registration_was_pruned() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add_worktree(&repo.path(), "feat/orphan");
+    commit_file(&wt, "kept.txt", "committed\n", "add kept");
+    fs::write(wt.join("kept.txt"), "edited\n").expect("write");
+    fs::write(wt.join("notes.txt"), "untracked\n").expect("write");
+    fs::remove_dir_all(git_dir(&wt)).expect("rm admin dir");
+    run_git(&["worktree", "prune"], &repo.path());
+
+    let json = doctor_json(&repo.path(), &[]);
+    let orphan = json["diagnostics"]
+        .as_array()
+        .expect("diagnostics")
+        .iter()
+        .find(|d| d["code"] == "orphaned_directory")
+        .expect("orphan diagnostic")
+        .clone();
+    assert_eq!(orphan["fixable"], true);
+
+    let json = doctor_json(&repo.path(), &["--fix"]);
+
+    let fixes = json["fixes"].as_array().expect("fixes");
+    let fix = fixes
+        .iter()
+        .find(|f| f["code"] == "orphaned_directory")
+        .expect("orphan fix");
+    assert_eq!(fix["ok"], true, "{fix:?}");
+    assert_eq!(
+        fs::read_to_string(wt.join("kept.txt")).expect("read"),
+        "edited\n"
+    );
+    assert!(wt.join("notes.txt").exists());
+    let status = StdCommand::new("git")
+        .args(["status", "--porcelain", "--branch"])
+        .current_dir(&wt)
+        .output()
+        .expect("git status");
+    let status = String::from_utf8(status.stdout).expect("utf8");
+    assert_eq!(
+        status.lines().collect::<Vec<_>>(),
+        ["## feat/orphan", " M kept.txt", "?? notes.txt"]
+    );
+    assert!(!doctor_codes(&repo.path()).contains(&"orphaned_directory".to_string()));
+}
+
+#[test]
+fn doctor_fix_leaves_non_empty_orphan() {
+    let repo = fixtures::TestRepo::new();
+    let orphan = repo.path().join(".worktrees/leftover");
+    fs::create_dir_all(&orphan).expect("mkdir");
+    fs::write(orphan.join("work.txt"), "keep me\n").expect("write");
+
+    let json = doctor_json(&repo.path(), &["--fix"]);
+
+    let fixes = json["fixes"].as_array().expect("fixes");
+    assert!(fixes.iter().all(|f| f["code"] != "orphaned_directory"));
+    assert!(orphan.join("work.txt").exists());
+    let findings = doctor_findings(&repo.path());
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].0.starts_with("orphaned directory"));
+}
+
+#[test]
+fn doctor_fix_leaves_ambiguous_detached_head() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add_worktree(&repo.path(), "feat/one");
+    run_git(&["branch", "feat/two"], &wt);
+    run_git(&["checkout", "-q", "--detach"], &wt);
+
+    let json = doctor_json(&repo.path(), &[]);
+    let detached = json["diagnostics"]
+        .as_array()
+        .expect("diagnostics")
+        .iter()
+        .find(|d| d["code"] == "detached_head")
+        .expect("detached diagnostic")
+        .clone();
+    assert_eq!(detached["fixable"], false);
+    assert!(detached.get("fix").is_none());
+
+    wt_core()
+        .args([
+            "doctor",
+            "--fix",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Added .worktrees/ to"))
+        .stdout(predicate::str::contains("⚠ worktree has no branch"));
+}
+
+#[test]
+fn doctor_dry_run_requires_fix() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args([
+            "doctor",
+            "--dry-run",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .failure();
+}
+
+/// Warning codes other than the `.worktrees/` ignore check.
+fn repo_codes(repo: &Path) -> Vec<String> {
+    doctor_codes(repo)
+        .into_iter()
+        .filter(|code| code != "worktrees_not_ignored")
+        .collect()
+}
+
+#[test]
+fn doctor_reports_directory_layout_problems() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add_worktree(&repo.path(), "feat/renamed");
+    let expected = wt
+        .file_name()
+        .expect("dir name")
+        .to_string_lossy()
+        .to_string();
+    let renamed = repo.path().join(".worktrees/renamed");
+    run_git(
+        &[
+            "worktree",
+            "move",
+            &wt.display().to_string(),
+            &renamed.display().to_string(),
+        ],
+        &repo.path(),
+    );
+    let elsewhere = tempfile::TempDir::new().expect("tempdir");
+    let outside = elsewhere.path().canonicalize().expect("canon").join("out");
+    run_git(
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "feat/outside",
+            &outside.display().to_string(),
+        ],
+        &repo.path(),
+    );
+
+    let json = doctor_json(&repo.path(), &[]);
+
+    let find = |code: &str| {
+        json["diagnostics"]
+            .as_array()
+            .expect("diagnostics")
+            .iter()
+            .find(|d| d["code"] == code)
+            .unwrap_or_else(|| panic!("no {code} diagnostic in {json}"))
+            .clone()
+    };
+    assert_eq!(repo_codes(&repo.path()).len(), 2);
+    let mismatch = find("dir_name_mismatch");
+    assert_eq!(mismatch["path"], renamed.display().to_string());
+    assert!(mismatch["message"]
+        .as_str()
+        .expect("message")
+        .contains(&format!("(expected {expected})")));
+    assert_eq!(mismatch["fix"], format!("wt adopt {}", renamed.display()));
+    let outside_diag = find("outside_managed_root");
+    assert_eq!(
+        outside_diag["fix"],
+        format!("wt adopt {}", outside.display())
+    );
+}
+
+#[test]
+fn doctor_reports_stale_index_lock_only() {
+    let repo = fixtures::TestRepo::new();
+    let stale = add_worktree(&repo.path(), "feat/stale");
+    let fresh = add_worktree(&repo.path(), "feat/fresh");
+    let lock = git_dir(&stale).join("index.lock");
+    let file = fs::File::create(&lock).expect("create lock");
+    file.set_modified(SystemTime::now() - Duration::from_secs(300))
+        .expect("set mtime");
+    fs::write(git_dir(&fresh).join("index.lock"), "").expect("create lock");
+
+    let findings = doctor_findings(&repo.path());
+
+    assert_eq!(findings.len(), 1, "{findings:?}");
+    assert!(findings[0].0.starts_with("stale index.lock in"));
+    assert_eq!(findings[0].1, Some(format!("rm {}", lock.display())));
+}
+
+#[test]
+fn doctor_reports_merge_in_progress() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add_worktree(&repo.path(), "feat/conflict");
+    commit_file(&repo.path(), "shared.txt", "main\n", "main side");
+    commit_file(&wt, "shared.txt", "branch\n", "branch side");
+    let merge = StdCommand::new("git")
+        .args(["merge", "-q", "main"])
+        .current_dir(&wt)
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_WORK_TREE")
+        .output()
+        .expect("run merge");
+    assert!(!merge.status.success(), "merge should conflict");
+
+    assert_eq!(repo_codes(&repo.path()), ["operation_in_progress"]);
+    let findings = doctor_findings(&repo.path());
+    assert!(findings[0].0.starts_with("merge in progress in"));
+}
+
+#[test]
+fn doctor_reports_gone_upstream() {
+    let repo = fixtures::ClonedTestRepo::new();
+    let wt = add_worktree(&repo.path(), "feat/shipped");
+    run_git(&["push", "-q", "-u", "origin", "feat/shipped"], &wt);
+    run_git(&["push", "-q", "origin", "--delete", "feat/shipped"], &wt);
+
+    let findings = doctor_findings(&repo.path());
+
+    assert_eq!(findings.len(), 1, "{findings:?}");
+    assert_eq!(
+        findings[0].0,
+        "upstream origin/feat/shipped of feat/shipped is gone"
+    );
+    assert_eq!(findings[0].1.as_deref(), Some("wt remove feat/shipped"));
+}
+
+#[test]
+fn doctor_reports_missing_origin_head() {
+    let repo = fixtures::ClonedTestRepo::new();
+    assert!(repo_codes(&repo.path()).is_empty());
+
+    run_git(&["remote", "set-head", "origin", "--delete"], &repo.path());
+
+    let findings = doctor_findings(&repo.path());
+    assert_eq!(findings.len(), 1, "{findings:?}");
+    assert!(findings[0].0.starts_with("origin/HEAD is missing"));
+    assert_eq!(
+        findings[0].1.as_deref(),
+        Some("git remote set-head origin --auto")
+    );
+}
@@ -150,3 +150,16 @@ fn no_subcommand_shows_help() {
         .failure()
         .stderr(predicate::str::contains("Usage"));
 }
+
+#[test]
+fn ui_non_tty_errors() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args(["ui", "--repo", &repo.path().display().to_string()])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("wt ui requires a terminal"));
+}
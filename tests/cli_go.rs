@@ -333,3 +333,185 @@ fn go_no_worktrees_to_select_errors() {
         .code(1)
         .stderr(predicate::str::contains("no worktrees to select"));
 }
+
+fn add_worktrees(repo: &std::path::Path, branches: &[&str]) {
+    for branch in branches {
+        wt_core()
+            .args(["add", branch, "--repo", &repo.display().to_string()])
+            .assert()
+            .success();
+    }
+}
+
+fn go_cd_path(repo: &std::path::Path, query: &str) -> String {
+    let output = wt_core()
+        .args([
+            "go",
+            query,
+            "--repo",
+            &repo.display().to_string(),
+            "--print-cd-path",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8(output)
+        .expect("invalid utf8")
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn go_accepts_unique_substring_and_fuzzy_queries() {
+    let repo = fixtures::TestRepo::new();
+    add_worktrees(&repo.path(), &["feature/auth-refresh", "feature/billing"]);
+
+    assert!(go_cd_path(&repo.path(), "auth").contains("feature-auth-refresh"));
+    assert!(go_cd_path(&repo.path(), "bllng").contains("feature-billing"));
+}
+
+#[test]
+fn go_accepts_directory_name_and_path_fragment() {
+    let repo = fixtures::TestRepo::new();
+    add_worktrees(&repo.path(), &["feature/slug"]);
+    let path = go_cd_path(&repo.path(), "feature/slug");
+    let dir = std::path::Path::new(&path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .expect("dir name")
+        .to_string();
+
+    assert_eq!(go_cd_path(&repo.path(), &dir), path);
+    assert_eq!(go_cd_path(&repo.path(), &format!(".worktrees/{dir}")), path);
+    assert_eq!(go_cd_path(&repo.path(), &path), path);
+}
+
+#[test]
+fn go_exact_branch_wins_over_substring_matches() {
+    let repo = fixtures::TestRepo::new();
+    add_worktrees(&repo.path(), &["api", "api-v2"]);
+
+    assert!(!go_cd_path(&repo.path(), "api").contains("api-v2"));
+    assert!(go_cd_path(&repo.path(), "api-v").contains("api-v2"));
+}
+
+#[test]
+fn go_ambiguous_query_lists_candidates_without_tty() {
+    let repo = fixtures::TestRepo::new();
+    add_worktrees(
+        &repo.path(),
+        &["feature/auth-login", "feature/auth-refresh"],
+    );
+
+    for extra in [None, Some("--json")] {
+        let mut cmd = wt_core();
+        cmd.args(["go", "auth", "--repo", &repo.path().display().to_string()]);
+        cmd.args(extra);
+        cmd.assert()
+            .failure()
+            .code(1)
+            .stderr(predicate::str::contains("'auth' matches 2 worktrees"))
+            .stderr(predicate::str::contains("feature/auth-login"))
+            .stderr(predicate::str::contains("feature/auth-refresh"));
+    }
+}
+
+fn go_from(cwd: &std::path::Path, args: &[&str]) -> String {
+    let output = wt_core()
+        .arg("go")
+        .args(args)
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8(output)
+        .expect("invalid utf8")
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn go_main_aliases_resolve_the_main_worktree() {
+    let repo = fixtures::TestRepo::new();
+    add_worktrees(&repo.path(), &["feature/alias"]);
+    let wt = std::path::PathBuf::from(go_cd_path(&repo.path(), "feature/alias"));
+    let root = repo.path().display().to_string();
+
+    assert_eq!(go_from(&wt, &["@main", "--print-cd-path"]), root);
+    assert_eq!(go_from(&wt, &["^", "--print-cd-path"]), root);
+}
+
+#[test]
+fn go_keep_subdir_lands_in_the_matching_directory() {
+    let repo = fixtures::TestRepo::new();
+    add_worktrees(&repo.path(), &["feature/sub"]);
+    let wt = std::path::PathBuf::from(go_cd_path(&repo.path(), "feature/sub"));
+    std::fs::create_dir_all(repo.path().join("src/deep")).expect("mkdir");
+    std::fs::create_dir_all(wt.join("src/deep")).expect("mkdir");
+    std::fs::create_dir_all(repo.path().join("only-main")).expect("mkdir");
+
+    let from = repo.path().join("src/deep");
+    assert_eq!(
+        go_from(&from, &["feature/sub", "--keep-subdir", "--print-cd-path"]),
+        wt.join("src/deep").display().to_string()
+    );
+    assert_eq!(
+        go_from(&from, &["feature/sub", "--print-cd-path"]),
+        wt.display().to_string()
+    );
+
+    // A directory missing from the target falls back to its root.
+    assert_eq!(
+        go_from(
+            &repo.path().join("only-main"),
+            &["feature/sub", "--keep-subdir", "--print-cd-path"]
+        ),
+        wt.display().to_string()
+    );
+}
+
+#[test]
+fn go_keep_subdir_config_and_override() {
+    let repo = fixtures::TestRepo::new();
+    add_worktrees(&repo.path(), &["feature/cfg"]);
+    let wt = std::path::PathBuf::from(go_cd_path(&repo.path(), "feature/cfg"));
+    std::fs::create_dir_all(wt.join("docs")).expect("mkdir");
+    fixtures::run_git(&["config", "wt.keepSubdir", "true"], &repo.path());
+
+    let from = wt.join("docs");
+    assert_eq!(
+        go_from(&from, &["^", "--print-cd-path"]),
+        repo.path().display().to_string(),
+        "docs/ does not exist in the main worktree"
+    );
+    std::fs::create_dir_all(repo.path().join("docs")).expect("mkdir");
+    assert_eq!(
+        go_from(&from, &["^", "--print-cd-path"]),
+        repo.path().join("docs").display().to_string()
+    );
+    assert_eq!(
+        go_from(&from, &["^", "--no-keep-subdir", "--print-cd-path"]),
+        repo.path().display().to_string()
+    );
+}
+
+#[test]
+fn go_json_reports_cd_path_separately_from_worktree_path() {
+    let repo = fixtures::TestRepo::new();
+    add_worktrees(&repo.path(), &["feature/json"]);
+    let wt = std::path::PathBuf::from(go_cd_path(&repo.path(), "feature/json"));
+    std::fs::create_dir_all(repo.path().join("src")).expect("mkdir");
+    std::fs::create_dir_all(wt.join("src")).expect("mkdir");
+
+    let stdout = go_from(
+        &repo.path().join("src"),
+        &["feature/json", "--keep-subdir", "--json"],
+    );
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid json");
+    assert_eq!(json["worktree_path"], wt.display().to_string());
+    assert_eq!(json["cd_path"], wt.join("src").display().to_string());
+}
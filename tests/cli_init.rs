@@ -42,16 +42,73 @@ fn init_nu_emits_binding() {
         .stdout(predicate::str::contains("export def \"wt list\""));
 }
 
+#[test]
+fn init_powershell_emits_binding() {
+    for shell in ["powershell", "pwsh"] {
+        wt_core()
+            .args(["init", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("function wt {"))
+            .stdout(predicate::str::contains("--print-cd-path"));
+    }
+}
+
+#[test]
+fn init_elvish_emits_binding() {
+    wt_core()
+        .args(["init", "elvish"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fn wt {|@args|"));
+}
+
+#[test]
+fn init_xonsh_emits_binding() {
+    wt_core()
+        .args(["init", "xonsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("aliases[\"wt\"] = _wt"));
+}
+
 #[test]
 fn init_unknown_shell_fails() {
     wt_core()
-        .args(["init", "powershell"])
+        .args(["init", "tcsh"])
         .assert()
         .failure()
         .code(2)
-        .stderr(predicate::str::contains("invalid value 'powershell'"))
+        .stderr(predicate::str::contains("invalid value 'tcsh'"))
         .stderr(predicate::str::contains("bash"))
         .stderr(predicate::str::contains("zsh"))
         .stderr(predicate::str::contains("fish"))
-        .stderr(predicate::str::contains("nu"));
+        .stderr(predicate::str::contains("nu"))
+        .stderr(predicate::str::contains("powershell"))
+        .stderr(predicate::str::contains("elvish"))
+        .stderr(predicate::str::contains("xonsh"));
+}
+
+#[test]
+fn init_emits_generated_completions() {
+    for (shell, marker) in [
+        ("bash", "complete -F _wt_complete"),
+        ("zsh", "compdef _wt_dynamic wt"),
+        ("fish", "complete -c wt -n \"__fish_wt_needs_command\""),
+        ("nu", "export extern \"wt-core go\""),
+        (
+            "powershell",
+            "Register-ArgumentCompleter -Native -CommandName 'wt'",
+        ),
+        ("elvish", "set edit:completion:arg-completer[wt]"),
+    ] {
+        let output = wt_core().args(["init", shell]).assert().success();
+        let stdout = String::from_utf8_lossy(&output.get_output().stdout).into_owned();
+        assert!(stdout.contains(marker), "{shell}: missing {marker}");
+        assert!(stdout.contains("move-changes"), "{shell}: no subcommands");
+        assert!(
+            !stdout.contains("Print completion candidates"),
+            "{shell}: hidden `complete` subcommand leaked"
+        );
+    }
 }
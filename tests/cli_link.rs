@@ -0,0 +1,207 @@
+mod fixtures;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+fn write_config(repo: &Path, content: &str) {
+    fs::create_dir_all(repo.join(".wt")).expect("mkdir .wt");
+    fs::write(repo.join(".wt/symlinks"), content).expect("write config");
+}
+
+fn add_worktree(repo: &Path, branch: &str) -> PathBuf {
+    let output = wt_core()
+        .args([
+            "add",
+            branch,
+            "--repo",
+            &repo.display().to_string(),
+            "--print-cd-path",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    PathBuf::from(String::from_utf8(output).expect("utf8").trim())
+}
+
+fn link_json(repo: &Path, args: &[&str]) -> serde_json::Value {
+    let repo_str = repo.display().to_string();
+    let mut full = vec!["link"];
+    full.extend_from_slice(args);
+    full.extend(["--repo", &repo_str, "--json"]);
+    let output = wt_core()
+        .args(&full)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+fn is_symlink(path: &Path) -> bool {
+    path.symlink_metadata()
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+#[test]
+fn link_adds_entries_configured_after_add() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add_worktree(&repo.path(), "feat/late");
+
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    fs::write(repo.path().join(".env"), "A=1").expect("write");
+    write_config(&repo.path(), "node_modules\ncopy: .env\n");
+
+    let json = link_json(&repo.path(), &["feat/late"]);
+
+    assert_eq!(json["dry_run"], false);
+    let entry = &json["worktrees"][0];
+    assert_eq!(entry["branch"], "feat/late");
+    assert_eq!(
+        entry["links"],
+        serde_json::json!([
+            {"path": "node_modules", "action": "created", "mode": "symlink"},
+            {"path": ".env", "action": "created", "mode": "copy"},
+        ])
+    );
+    assert!(is_symlink(&wt.join("node_modules")));
+    assert!(wt.join(".env").is_file() && !is_symlink(&wt.join(".env")));
+
+    // A second run has nothing left to do.
+    let json = link_json(&repo.path(), &["feat/late"]);
+    assert_eq!(json["worktrees"][0]["links"], serde_json::json!([]));
+}
+
+#[cfg(unix)]
+#[test]
+fn link_repairs_wrong_target_and_keeps_real_directories() {
+    let repo = fixtures::TestRepo::new();
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    fs::create_dir(repo.path().join(".venv")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n.venv\n");
+    let wt = add_worktree(&repo.path(), "feat/repair");
+
+    fs::remove_file(wt.join("node_modules")).expect("rm");
+    std::os::unix::fs::symlink("/nonexistent", wt.join("node_modules")).expect("symlink");
+    fs::remove_file(wt.join(".venv")).expect("rm");
+    fs::create_dir(wt.join(".venv")).expect("mkdir");
+
+    let json = link_json(&repo.path(), &["feat/repair"]);
+
+    let links = &json["worktrees"][0]["links"];
+    assert_eq!(links[0]["path"], "node_modules");
+    assert_eq!(links[0]["action"], "repaired");
+    assert_eq!(links[1]["path"], ".venv");
+    assert_eq!(links[1]["action"], "skipped");
+    assert_eq!(links[1]["reason"], "exists and is not a symlink");
+    assert!(wt.join("node_modules").exists(), "link resolves again");
+    assert!(!is_symlink(&wt.join(".venv")));
+}
+
+#[test]
+fn link_prune_removes_links_no_longer_configured() {
+    let repo = fixtures::TestRepo::new();
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    fs::write(repo.path().join(".env"), "A=1").expect("write");
+    write_config(&repo.path(), "node_modules\n.env\n");
+    let wt = add_worktree(&repo.path(), "feat/prune");
+
+    write_config(&repo.path(), "node_modules\n");
+
+    let json = link_json(&repo.path(), &["--all", "--prune", "--dry-run"]);
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(
+        json["worktrees"][0]["links"],
+        serde_json::json!([{"path": ".env", "action": "removed"}])
+    );
+    assert!(is_symlink(&wt.join(".env")), "dry run leaves the link");
+
+    let json = link_json(&repo.path(), &["--all", "--prune"]);
+    assert_eq!(json["worktrees"][0]["links"][0]["action"], "removed");
+    assert!(!is_symlink(&wt.join(".env")));
+    assert!(is_symlink(&wt.join("node_modules")));
+    assert!(repo.path().join(".env").exists(), "original untouched");
+}
+
+#[test]
+fn link_prune_finds_links_under_untracked_directories() {
+    let repo = fixtures::TestRepo::new();
+    fs::create_dir_all(repo.path().join("config/local")).expect("mkdir");
+    fs::write(repo.path().join("config/local/.env"), "A=1").expect("write");
+    write_config(&repo.path(), "config/local/.env\n");
+    let wt = add_worktree(&repo.path(), "feat/nested");
+    assert!(is_symlink(&wt.join("config/local/.env")));
+
+    write_config(&repo.path(), "");
+
+    let json = link_json(&repo.path(), &["--all", "--prune"]);
+    assert_eq!(
+        json["worktrees"][0]["links"],
+        serde_json::json!([{"path": "config/local/.env", "action": "removed"}])
+    );
+    assert!(!is_symlink(&wt.join("config/local/.env")));
+}
+
+#[test]
+fn link_defaults_to_current_worktree() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add_worktree(&repo.path(), "feat/cwd");
+    add_worktree(&repo.path(), "feat/other");
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n");
+
+    wt_core()
+        .args(["link", "--dry-run"])
+        .current_dir(&wt)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feat/cwd"))
+        .stdout(predicate::str::contains("Would create node_modules"))
+        .stdout(predicate::str::contains("feat/other").not())
+        .stdout(predicate::str::contains("1 change would be made."));
+
+    assert!(!wt.join("node_modules").exists());
+}
+
+#[test]
+fn link_refuses_main_worktree() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args(["link"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("main worktree is the link source"));
+}
+
+#[test]
+fn link_human_reports_up_to_date() {
+    let repo = fixtures::TestRepo::new();
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    write_config(&repo.path(), "node_modules\n");
+    add_worktree(&repo.path(), "feat/fresh");
+
+    wt_core()
+        .args([
+            "link",
+            "feat/fresh",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Up to date"))
+        .stdout(predicate::str::contains("0 changes made."));
+}
@@ -0,0 +1,235 @@
+mod fixtures;
+
+use std::path::Path;
+use std::process::Command as StdCommand;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use fixtures::{commit_file, find_worktree_dir, run_git};
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+/// Environment variables cleared for raw git commands in tests.
+const GIT_ENV_OVERRIDES: &[&str] = &[
+    "GIT_DIR",
+    "GIT_WORK_TREE",
+    "GIT_INDEX_FILE",
+    "GIT_OBJECT_DIRECTORY",
+    "GIT_ALTERNATE_OBJECT_DIRECTORIES",
+    "GIT_PREFIX",
+];
+
+fn git_stdout(args: &[&str], cwd: &Path) -> String {
+    let mut cmd = StdCommand::new("git");
+    cmd.args(args).current_dir(cwd);
+    for var in GIT_ENV_OVERRIDES {
+        cmd.env_remove(var);
+    }
+    let output = cmd.output().expect("failed to run git");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn add_worktree(repo: &Path, branch: &str) {
+    wt_core()
+        .args(["add", branch, "--repo", &repo.display().to_string()])
+        .assert()
+        .success();
+}
+
+fn move_changes_json(cwd: &Path, extra_args: &[&str]) -> serde_json::Value {
+    let mut args = vec!["move-changes", "--json"];
+    args.extend_from_slice(extra_args);
+    let output = wt_core()
+        .args(&args)
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+fn json_files(json: &serde_json::Value) -> Vec<&str> {
+    let mut files: Vec<&str> = json["files"]
+        .as_array()
+        .expect("files array")
+        .iter()
+        .filter_map(|f| f.as_str())
+        .collect();
+    files.sort_unstable();
+    files
+}
+
+#[test]
+fn move_changes_moves_staged_and_unstaged_edits() {
+    let repo = fixtures::TestRepo::new();
+    commit_file(&repo.path(), "staged.txt", "original\n", "add staged.txt");
+    add_worktree(&repo.path(), "feature/target");
+
+    std::fs::write(repo.path().join("README.md"), "unstaged edit\n").expect("write");
+    std::fs::write(repo.path().join("staged.txt"), "staged edit\n").expect("write");
+    run_git(&["add", "staged.txt"], &repo.path());
+
+    let json = move_changes_json(&repo.path(), &["--to", "feature/target"]);
+
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["branch"], "feature/target");
+    assert_eq!(json_files(&json), vec!["README.md", "staged.txt"]);
+
+    // Source is clean, target has the changes with staging preserved.
+    assert!(git_stdout(&["status", "--porcelain", "-uno"], &repo.path()).is_empty());
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-target");
+    assert_eq!(
+        std::fs::read_to_string(wt_dir.join("README.md")).expect("read"),
+        "unstaged edit\n"
+    );
+    let staged = git_stdout(&["diff", "--cached", "--name-only"], &wt_dir);
+    assert_eq!(staged, "staged.txt");
+
+    // The temporary stash is dropped after a successful move.
+    assert!(git_stdout(&["stash", "list"], &repo.path()).is_empty());
+}
+
+#[test]
+fn move_changes_leaves_untracked_files_unless_requested() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/untracked");
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-untracked");
+
+    std::fs::write(repo.path().join("README.md"), "edit\n").expect("write");
+    std::fs::write(repo.path().join("new.txt"), "new\n").expect("write");
+
+    let json = move_changes_json(&repo.path(), &["--to", "feature/untracked"]);
+    assert_eq!(json_files(&json), vec!["README.md"]);
+    assert!(repo.path().join("new.txt").exists());
+    assert!(!wt_dir.join("new.txt").exists());
+
+    run_git(&["reset", "--hard"], &wt_dir);
+    let json = move_changes_json(
+        &repo.path(),
+        &["--to", "feature/untracked", "--include-untracked"],
+    );
+    assert_eq!(json_files(&json), vec!["new.txt"]);
+    assert!(!repo.path().join("new.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(wt_dir.join("new.txt")).expect("read"),
+        "new\n"
+    );
+}
+
+#[test]
+fn move_changes_conflict_keeps_named_stash_and_source_changes() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/diverged");
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-diverged");
+    commit_file(&wt_dir, "README.md", "branch side\n", "branch edit");
+
+    std::fs::write(repo.path().join("README.md"), "main side\n").expect("write");
+
+    wt_core()
+        .args(["move-changes", "--to", "feature/diverged"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains("wt move-changes:"))
+        .stderr(predicate::str::contains("stash@{0}"));
+
+    // Source keeps its edit, target is untouched, the stash is kept.
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("README.md")).expect("read"),
+        "main side\n"
+    );
+    assert!(git_stdout(&["status", "--porcelain"], &wt_dir).is_empty());
+    let stashes = git_stdout(&["stash", "list"], &repo.path());
+    assert!(
+        stashes.contains("wt move-changes:"),
+        "stash list: {stashes}"
+    );
+}
+
+#[test]
+fn move_changes_refuses_dirty_target() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/busy");
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-busy");
+    std::fs::write(wt_dir.join("README.md"), "target edit\n").expect("write");
+    std::fs::write(repo.path().join("README.md"), "source edit\n").expect("write");
+
+    wt_core()
+        .args(["move-changes", "--to", "feature/busy"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains("has uncommitted changes"));
+
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("README.md")).expect("read"),
+        "source edit\n"
+    );
+    assert!(git_stdout(&["stash", "list"], &repo.path()).is_empty());
+}
+
+#[test]
+fn move_changes_without_changes_is_usage_error() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/empty");
+
+    wt_core()
+        .args(["move-changes", "--to", "feature/empty"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("no uncommitted changes to move"));
+}
+
+#[test]
+fn add_carry_moves_changes_into_new_worktree() {
+    let repo = fixtures::TestRepo::new();
+    std::fs::write(repo.path().join("README.md"), "carried edit\n").expect("write");
+    std::fs::write(repo.path().join("notes.txt"), "notes\n").expect("write");
+
+    let output = wt_core()
+        .args([
+            "add",
+            "feature/carried",
+            "--carry",
+            "--include-untracked",
+            "--json",
+        ])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("invalid json");
+
+    assert_eq!(json["event"], "switch");
+    assert_eq!(json_files(&json), vec!["README.md", "notes.txt"]);
+    assert!(git_stdout(&["status", "--porcelain", "-uno"], &repo.path()).is_empty());
+
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-carried");
+    assert_eq!(
+        std::fs::read_to_string(wt_dir.join("README.md")).expect("read"),
+        "carried edit\n"
+    );
+    assert!(wt_dir.join("notes.txt").exists());
+}
+
+#[test]
+fn add_include_untracked_requires_carry() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args(["add", "feature/x", "--include-untracked"])
+        .current_dir(repo.path())
+        .assert()
+        .failure();
+}
@@ -0,0 +1,175 @@
+mod fixtures;
+
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn wt_core() -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo_bin!("wt-core"));
+    cmd.env_remove("VISUAL").env_remove("EDITOR");
+    cmd
+}
+
+fn add(repo: &Path, branch: &str) -> PathBuf {
+    let output = wt_core()
+        .args(["add", branch, "--print-cd-path", "--repo"])
+        .arg(repo)
+        .assert()
+        .success();
+    PathBuf::from(String::from_utf8_lossy(&output.get_output().stdout).trim())
+}
+
+fn stdout(cmd: &mut Command) -> String {
+    let output = cmd.assert().success();
+    String::from_utf8_lossy(&output.get_output().stdout)
+        .trim()
+        .to_string()
+}
+
+/// Prints the worktree root on the editor's stdout, which `wt` forwards to stderr.
+const SHOW_TOPLEVEL: &str = "git -C {path} rev-parse --show-toplevel";
+
+#[test]
+fn open_dry_run_prefers_visual_over_editor() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add(&repo.path(), "feature/open");
+
+    let mut cmd = wt_core();
+    cmd.args(["open", "feature/open", "--dry-run"])
+        .current_dir(repo.path())
+        .env("VISUAL", "code -n")
+        .env("EDITOR", "vim");
+    assert_eq!(stdout(&mut cmd), format!("code -n {}", wt.display()));
+
+    let mut cmd = wt_core();
+    cmd.args(["open", "feature/open", "--print-command"])
+        .current_dir(repo.path())
+        .env("EDITOR", "vim");
+    assert_eq!(stdout(&mut cmd), format!("vim {}", wt.display()));
+}
+
+#[test]
+fn open_config_template_wins_over_env_and_editor_flag_wins_over_config() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add(&repo.path(), "feature/tmux");
+    fixtures::run_git(
+        &["config", "wt.openCommand", "tmux new-window -c {path}"],
+        &repo.path(),
+    );
+
+    let mut cmd = wt_core();
+    cmd.args(["open", "--dry-run"])
+        .current_dir(&wt)
+        .env("EDITOR", "vim");
+    assert_eq!(
+        stdout(&mut cmd),
+        format!("tmux new-window -c {}", wt.display())
+    );
+
+    let mut cmd = wt_core();
+    cmd.args(["open", "--dry-run", "--editor", "idea {path}"])
+        .current_dir(&wt);
+    assert_eq!(stdout(&mut cmd), format!("idea {}", wt.display()));
+}
+
+#[test]
+fn open_defaults_to_the_current_worktree() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add(&repo.path(), "feature/here");
+    std::fs::create_dir(wt.join("sub")).expect("mkdir");
+
+    let mut cmd = wt_core();
+    cmd.args(["open", "--dry-run", "--editor", "ed"])
+        .current_dir(wt.join("sub"));
+    assert_eq!(stdout(&mut cmd), format!("ed {}", wt.display()));
+
+    let mut cmd = wt_core();
+    cmd.args(["open", "--dry-run", "--editor", "ed"])
+        .current_dir(repo.path());
+    assert_eq!(stdout(&mut cmd), format!("ed {}", repo.path().display()));
+}
+
+#[test]
+fn open_without_an_editor_fails() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args(["open", "--dry-run"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("no editor configured"));
+
+    wt_core()
+        .args(["open", "--editor", " "])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("--editor must not be empty"));
+}
+
+#[test]
+fn open_runs_the_command_and_reports_failures() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add(&repo.path(), "feature/run");
+
+    wt_core()
+        .args(["open", "feature/run", "--editor", SHOW_TOPLEVEL])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Opened"))
+        .stderr(predicate::str::contains(wt.display().to_string()));
+
+    wt_core()
+        .args(["open", "feature/run", "--editor", "git no-such-subcommand"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("'git' failed"));
+}
+
+#[test]
+fn add_open_keeps_stdout_for_the_shell_wrapper() {
+    let repo = fixtures::TestRepo::new();
+
+    let output = wt_core()
+        .args([
+            "add",
+            "feature/opened",
+            "--open",
+            "--editor",
+            SHOW_TOPLEVEL,
+            "--print-cd-path",
+        ])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    let out = output.get_output();
+    let path = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    assert!(path.contains("feature-opened"));
+    assert!(String::from_utf8_lossy(&out.stderr).contains(&path));
+}
+
+#[test]
+fn add_open_failure_only_warns() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args(["add", "feature/warned", "--open", "--print-cd-path"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature-warned"))
+        .stderr(predicate::str::contains("warning: no editor configured"));
+
+    wt_core()
+        .args(["add", "feature/no-open", "--editor", "vim"])
+        .current_dir(repo.path())
+        .assert()
+        .failure();
+}
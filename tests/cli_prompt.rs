@@ -0,0 +1,108 @@
+mod fixtures;
+
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+fn add(repo: &Path, branch: &str) -> PathBuf {
+    let output = wt_core()
+        .args(["add", branch, "--print-cd-path", "--repo"])
+        .arg(repo)
+        .assert()
+        .success();
+    PathBuf::from(String::from_utf8_lossy(&output.get_output().stdout).trim())
+}
+
+fn prompt(cwd: &Path, args: &[&str]) -> String {
+    let output = wt_core()
+        .arg("prompt")
+        .args(args)
+        .current_dir(cwd)
+        .assert()
+        .success();
+    String::from_utf8_lossy(&output.get_output().stdout).into_owned()
+}
+
+#[test]
+fn prompt_prints_nothing_outside_a_repository() {
+    let dir = tempfile::TempDir::new().expect("tempdir");
+    assert_eq!(prompt(dir.path(), &[]), "");
+}
+
+#[test]
+fn prompt_shows_branch_and_counts_against_mainline() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add(&repo.path(), "feature/prompt");
+    fixtures::commit_file(&wt, "a.txt", "a\n", "ahead 1");
+    fixtures::commit_file(&wt, "b.txt", "b\n", "ahead 2");
+    fixtures::commit_file(&repo.path(), "main.txt", "m\n", "main moves on");
+
+    assert_eq!(prompt(&wt, &[]), "feature/prompt 2↑1↓\n");
+    assert_eq!(
+        prompt(&wt, &["--format", "[{branch}]"]),
+        "[feature/prompt]\n"
+    );
+    assert_eq!(prompt(&repo.path(), &[]), "main 0↑0↓\n");
+}
+
+#[test]
+fn prompt_resolves_from_a_subdirectory_and_marks_dirty() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add(&repo.path(), "feature/dirty");
+    std::fs::create_dir(wt.join("sub")).expect("mkdir");
+    std::fs::write(wt.join("README.md"), "changed\n").expect("write");
+
+    assert_eq!(
+        prompt(&wt.join("sub"), &["--format", "{branch}{dirty}"]),
+        "feature/dirty*\n"
+    );
+}
+
+#[test]
+fn prompt_reuses_cached_stats_until_head_or_index_changes() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add(&repo.path(), "feature/cached");
+    let format = ["--format", "{ahead}{dirty}"];
+    assert_eq!(prompt(&wt, &format), "0\n");
+
+    // Editing a file touches neither HEAD nor the index: the cache still applies.
+    std::fs::write(wt.join("README.md"), "changed\n").expect("write");
+    assert_eq!(prompt(&wt, &format), "0\n");
+    assert_eq!(
+        prompt(&wt, &["--no-cache", "--format", "{ahead}{dirty}"]),
+        "0*\n"
+    );
+
+    // A commit moves the branch and rewrites the index.
+    fixtures::run_git(&["commit", "-am", "edit"], &wt);
+    assert_eq!(prompt(&wt, &format), "1\n");
+}
+
+#[test]
+fn prompt_cache_hit_runs_git_once() {
+    let repo = fixtures::TestRepo::new();
+    let wt = add(&repo.path(), "feature/fast");
+    assert_eq!(prompt(&wt, &[]), "feature/fast 0↑0↓\n");
+
+    let trace = tempfile::NamedTempFile::new().expect("trace file");
+    let output = wt_core()
+        .arg("prompt")
+        .current_dir(&wt)
+        .env("GIT_TRACE", trace.path())
+        .assert()
+        .success();
+    assert_eq!(
+        String::from_utf8_lossy(&output.get_output().stdout),
+        "feature/fast 0↑0↓\n"
+    );
+    let trace = std::fs::read_to_string(trace.path()).expect("read trace");
+    let runs: Vec<&str> = trace
+        .lines()
+        .filter(|l| l.contains("trace: built-in: git"))
+        .collect();
+    assert_eq!(runs.len(), 1, "{runs:#?}");
+}
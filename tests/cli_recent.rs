@@ -0,0 +1,172 @@
+mod fixtures;
+
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use fixtures::{commit_file, run_git};
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+fn add(cwd: &Path, branch: &str) -> PathBuf {
+    let output = wt_core()
+        .args(["add", branch, "--print-cd-path"])
+        .current_dir(cwd)
+        .assert()
+        .success();
+    PathBuf::from(String::from_utf8_lossy(&output.get_output().stdout).trim())
+}
+
+fn go(cwd: &Path, query: &str) -> PathBuf {
+    let output = wt_core()
+        .args(["go", query, "--print-cd-path"])
+        .current_dir(cwd)
+        .assert()
+        .success();
+    PathBuf::from(String::from_utf8_lossy(&output.get_output().stdout).trim())
+}
+
+fn recent_paths(cwd: &Path) -> Vec<String> {
+    let output = wt_core()
+        .args(["recent", "--json"])
+        .current_dir(cwd)
+        .assert()
+        .success();
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.get_output().stdout).expect("valid json");
+    json["worktrees"]
+        .as_array()
+        .expect("worktrees array")
+        .iter()
+        .map(|wt| wt["path"].as_str().expect("path").to_string())
+        .collect()
+}
+
+fn history_file(repo: &Path) -> String {
+    std::fs::read_to_string(repo.join(".git/wt-recent.json")).unwrap_or_default()
+}
+
+#[test]
+fn go_dash_returns_to_the_previous_worktree() {
+    let repo = fixtures::TestRepo::new();
+    let one = add(&repo.path(), "feature/one");
+    let two = add(&repo.path(), "feature/two");
+
+    // `add` recorded the move from the main worktree into `two`.
+    assert_eq!(go(&two, "-"), repo.path());
+    assert_eq!(go(&repo.path(), "-"), two);
+
+    go(&two, "feature/one");
+    assert_eq!(go(&one, "-"), two);
+    assert_eq!(go(&two, "-"), one);
+}
+
+#[test]
+fn go_dash_without_history_fails() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args(["go", "-"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("no previously visited worktree"));
+}
+
+#[test]
+fn recent_lists_visits_most_recent_first() {
+    let repo = fixtures::TestRepo::new();
+    let one = add(&repo.path(), "feature/one");
+    let two = add(&repo.path(), "feature/two");
+    go(&two, "feature/one");
+
+    let expected = [&one, &two, &repo.path()].map(|p| p.display().to_string());
+    assert_eq!(recent_paths(&one), expected);
+
+    let output = wt_core()
+        .args(["recent", "--json"])
+        .current_dir(&one)
+        .assert()
+        .success();
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.get_output().stdout).expect("valid json");
+    assert_eq!(json["worktrees"][0]["branch"], "feature/one");
+    assert_eq!(json["worktrees"][0]["is_current"], true);
+    assert_eq!(json["worktrees"][1]["is_current"], false);
+
+    wt_core()
+        .arg("recent")
+        .current_dir(&one)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/one"))
+        .stdout(predicate::str::contains("← here"));
+}
+
+#[test]
+fn recent_is_empty_without_visits() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .arg("recent")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No recently visited worktrees"));
+}
+
+#[test]
+fn remove_forgets_the_worktree() {
+    let repo = fixtures::TestRepo::new();
+    let gone = add(&repo.path(), "feature/gone");
+    add(&repo.path(), "feature/kept");
+    assert!(history_file(&repo.path()).contains(&gone.display().to_string()));
+
+    wt_core()
+        .args(["remove", "feature/gone"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+
+    assert!(!history_file(&repo.path()).contains(&gone.display().to_string()));
+    assert!(history_file(&repo.path()).contains("feature-kept"));
+}
+
+#[test]
+fn prune_forgets_pruned_worktrees() {
+    let repo = fixtures::TestRepo::new();
+    let merged = add(&repo.path(), "feature/merged");
+    commit_file(&merged, "feat.txt", "feature work", "add feature");
+    run_git(&["merge", "feature/merged"], &repo.path());
+    assert!(history_file(&repo.path()).contains(&merged.display().to_string()));
+
+    wt_core()
+        .args(["prune", "--execute"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+
+    assert!(!merged.exists());
+    assert!(!history_file(&repo.path()).contains(&merged.display().to_string()));
+}
+
+#[test]
+fn merge_forgets_the_cleaned_up_worktree() {
+    let repo = fixtures::TestRepo::new();
+    let merged = add(&repo.path(), "feature/merged");
+    commit_file(&merged, "feat.txt", "feature work", "add feature");
+    assert!(history_file(&repo.path()).contains(&merged.display().to_string()));
+
+    wt_core()
+        .args(["merge", "feature/merged"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+
+    assert!(!merged.exists());
+    assert!(!history_file(&repo.path()).contains(&merged.display().to_string()));
+}
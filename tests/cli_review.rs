@@ -0,0 +1,228 @@
+mod fixtures;
+
+use std::path::Path;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use fixtures::{commit_file, find_worktree_dir, run_git};
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+/// Push a commit adding `file` to `<ref_prefix>/<n>/head` on origin,
+/// leaving the clone's own branches untouched.
+fn publish_pr(repo: &Path, ref_template: &str, n: u64, file: &str) {
+    let remote_ref = ref_template.replace("{n}", &n.to_string());
+    run_git(&["checkout", "-q", "-b", "contrib"], repo);
+    commit_file(repo, file, "contribution\n", "contributor change");
+    run_git(
+        &["push", "-q", "origin", &format!("contrib:{remote_ref}")],
+        repo,
+    );
+    run_git(&["checkout", "-q", "main"], repo);
+    run_git(&["branch", "-q", "-D", "contrib"], repo);
+}
+
+fn json_output(repo: &Path, args: &[&str]) -> serde_json::Value {
+    let repo_str = repo.display().to_string();
+    let mut full = args.to_vec();
+    full.extend(["--repo", &repo_str, "--json"]);
+    let output = wt_core()
+        .args(&full)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+fn prune_entry<'a>(json: &'a serde_json::Value, branch: &str) -> &'a serde_json::Value {
+    json["worktrees"]
+        .as_array()
+        .expect("worktrees array")
+        .iter()
+        .find(|e| e["branch"] == branch)
+        .unwrap_or_else(|| panic!("branch '{branch}' not listed: {json}"))
+}
+
+#[test]
+fn add_pr_creates_review_worktree_from_pull_ref() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+    publish_pr(&repo, "refs/pull/{n}/head", 7, "pr7.txt");
+
+    let json = json_output(&repo, &["add", "--pr", "7"]);
+
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["event"], "switch");
+    assert_eq!(json["branch"], "pr/7");
+    let wt_dir = find_worktree_dir(&repo, "pr-7");
+    assert!(wt_dir.join("pr7.txt").exists());
+}
+
+#[test]
+fn review_command_is_alias_for_add_pr() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+    publish_pr(&repo, "refs/pull/{n}/head", 12, "pr12.txt");
+
+    wt_core()
+        .args(["review", "12", "--repo", &repo.display().to_string()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Created review worktree for PR #12 on branch 'pr/12'",
+        ));
+
+    assert!(find_worktree_dir(&repo, "pr-12").join("pr12.txt").exists());
+}
+
+#[test]
+fn add_pr_uses_configured_refspec_template() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+    publish_pr(&repo, "refs/merge-requests/{n}/head", 3, "mr3.txt");
+    run_git(
+        &["config", "wt.prRefspec", "refs/merge-requests/{n}/head"],
+        &repo,
+    );
+
+    let json = json_output(&repo, &["review", "3"]);
+
+    assert_eq!(json["branch"], "pr/3");
+    assert!(find_worktree_dir(&repo, "pr-3").join("mr3.txt").exists());
+}
+
+#[test]
+fn add_pr_missing_ref_is_git_error() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+
+    wt_core()
+        .args(["add", "--pr", "99", "--repo", &repo.display().to_string()])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "could not fetch 'refs/pull/99/head' from 'origin'",
+        ));
+
+    assert!(!repo.join(".worktrees").exists());
+}
+
+#[test]
+fn add_pr_conflicts_with_branch_argument() {
+    let cloned = fixtures::ClonedTestRepo::new();
+
+    wt_core()
+        .args([
+            "add",
+            "feature/x",
+            "--pr",
+            "1",
+            "--repo",
+            &cloned.path().display().to_string(),
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn prune_removes_review_worktree_once_ref_is_gone() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+    publish_pr(&repo, "refs/pull/{n}/head", 5, "pr5.txt");
+    json_output(&repo, &["review", "5"]);
+
+    let json = json_output(&repo, &["prune"]);
+    assert_eq!(prune_entry(&json, "pr/5")["status"], "not_integrated");
+
+    run_git(&["push", "-q", "origin", ":refs/pull/5/head"], &repo);
+
+    let json = json_output(&repo, &["prune"]);
+    let entry = prune_entry(&json, "pr/5");
+    assert_eq!(entry["status"], "expired");
+    assert_eq!(entry["reason"], "ref_gone");
+    assert_eq!(json["prunable"], 1);
+
+    let json = json_output(&repo, &["prune", "--execute"]);
+    assert_eq!(json["pruned"][0]["branch"], "pr/5");
+    assert!(!repo
+        .join(".worktrees")
+        .read_dir()
+        .expect("dir")
+        .any(|_| true));
+
+    let branches = std::process::Command::new("git")
+        .args(["branch", "--list", "pr/5"])
+        .current_dir(&repo)
+        .output()
+        .expect("git branch");
+    assert!(branches.stdout.is_empty(), "pr/5 branch should be deleted");
+}
+
+#[test]
+fn overview_does_not_ask_the_remote_about_review_refs() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+    publish_pr(&repo, "refs/pull/{n}/head", 6, "pr6.txt");
+    json_output(&repo, &["review", "6"]);
+    run_git(&["push", "-q", "origin", ":refs/pull/6/head"], &repo);
+
+    let root = wt_core::worktree::repo_root(&repo).expect("repo root");
+    let overview = wt_core::worktree::overview(&root).expect("overview");
+    let entry = overview
+        .entries
+        .iter()
+        .find(|e| e.worktree.branch.as_deref() == Some("pr/6"))
+        .expect("pr/6 entry");
+    assert_eq!(
+        entry.integration,
+        Some(wt_core::worktree::IntegrationStatus::NotIntegrated)
+    );
+}
+
+#[test]
+fn prune_expires_review_worktrees_after_ttl() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+    publish_pr(&repo, "refs/pull/{n}/head", 8, "pr8.txt");
+    json_output(&repo, &["review", "8"]);
+
+    // An ordinary unmerged worktree is never expired by the review TTL.
+    json_output(&repo, &["add", "feature/keep"]);
+    commit_file(
+        &find_worktree_dir(&repo, "feature-keep"),
+        "keep.txt",
+        "keep\n",
+        "keep work",
+    );
+
+    run_git(&["config", "wt.reviewTtl", "0s"], &repo);
+
+    let json = json_output(&repo, &["prune"]);
+    let entry = prune_entry(&json, "pr/8");
+    assert_eq!(entry["status"], "expired");
+    assert_eq!(entry["reason"], "ttl_elapsed");
+    assert_eq!(
+        prune_entry(&json, "feature/keep")["status"],
+        "not_integrated"
+    );
+}
+
+#[test]
+fn prune_rejects_invalid_review_ttl() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+    run_git(&["config", "wt.reviewTtl", "soon"], &repo);
+
+    wt_core()
+        .args(["prune", "--repo", &repo.display().to_string()])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("invalid wt.reviewTtl 'soon'"));
+}
@@ -0,0 +1,176 @@
+mod fixtures;
+
+use std::path::Path;
+use std::process::Command as StdCommand;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use fixtures::{commit_file, find_worktree_dir, run_git};
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+fn git_stdout(args: &[&str], cwd: &Path) -> String {
+    let output = StdCommand::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_WORK_TREE")
+        .output()
+        .expect("failed to run git");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn json_output(repo: &Path, args: &[&str]) -> serde_json::Value {
+    let repo_str = repo.display().to_string();
+    let mut full = args.to_vec();
+    full.extend(["--repo", &repo_str, "--json"]);
+    let output = wt_core()
+        .args(&full)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+fn doctor_messages(repo: &Path) -> Vec<String> {
+    json_output(repo, &["doctor"])["diagnostics"]
+        .as_array()
+        .expect("diagnostics")
+        .iter()
+        .filter_map(|d| d["message"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[test]
+fn add_detach_checks_out_revision_without_branch() {
+    let repo = fixtures::TestRepo::new();
+    run_git(&["tag", "v1.0"], &repo.path());
+    let tagged = git_stdout(&["rev-parse", "v1.0"], &repo.path());
+    commit_file(&repo.path(), "later.txt", "later", "after tag");
+
+    let json = json_output(&repo.path(), &["add", "--detach", "v1.0"]);
+
+    assert_eq!(json["event"], "switch");
+    assert_eq!(json["commit"], tagged.as_str());
+    assert!(json.get("branch").is_none());
+
+    let wt_dir = find_worktree_dir(&repo.path(), "v1-0");
+    assert_eq!(git_stdout(&["rev-parse", "HEAD"], &wt_dir), tagged);
+    assert!(git_stdout(&["symbolic-ref", "-q", "HEAD"], &wt_dir).is_empty());
+    assert!(!wt_dir.join("later.txt").exists());
+}
+
+#[test]
+fn add_detach_unknown_revision_is_git_error() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args([
+            "add",
+            "--detach",
+            "no-such-rev",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("revision 'no-such-rev' not found"));
+}
+
+#[test]
+fn scratch_creates_auto_named_detached_worktrees() {
+    let repo = fixtures::TestRepo::new();
+
+    let first = json_output(&repo.path(), &["scratch"]);
+    let second = json_output(&repo.path(), &["scratch", "HEAD"]);
+
+    let first_path = first["worktree_path"].as_str().expect("path");
+    let second_path = second["worktree_path"].as_str().expect("path");
+    assert!(first_path.contains("scratch-1--"), "{first_path}");
+    assert!(second_path.contains("scratch-2--"), "{second_path}");
+    assert!(git_stdout(&["symbolic-ref", "-q", "HEAD"], Path::new(first_path)).is_empty());
+}
+
+#[test]
+fn doctor_only_warns_on_unintentional_detached_worktrees() {
+    let repo = fixtures::TestRepo::new();
+    json_output(&repo.path(), &["scratch"]);
+    json_output(&repo.path(), &["add", "--detach", "HEAD"]);
+
+    let messages = doctor_messages(&repo.path());
+    assert!(
+        !messages.iter().any(|m| m.contains("detached HEAD")),
+        "{messages:?}"
+    );
+
+    let manual = repo.path().join(".worktrees").join("manual");
+    run_git(
+        &[
+            "worktree",
+            "add",
+            "--detach",
+            &manual.display().to_string(),
+            "HEAD",
+        ],
+        &repo.path(),
+    );
+
+    let messages = doctor_messages(&repo.path());
+    let detached: Vec<_> = messages
+        .iter()
+        .filter(|m| m.contains("detached HEAD"))
+        .collect();
+    assert_eq!(detached.len(), 1, "{messages:?}");
+    assert!(detached[0].contains("manual"));
+}
+
+#[test]
+fn prune_scratch_removes_only_scratch_worktrees() {
+    let repo = fixtures::TestRepo::new();
+    let scratch = json_output(&repo.path(), &["scratch"]);
+    json_output(&repo.path(), &["add", "--detach", "HEAD"]);
+
+    let json = json_output(&repo.path(), &["prune"]);
+    assert_eq!(json["prunable"], 0);
+
+    let json = json_output(&repo.path(), &["prune", "--scratch"]);
+    assert_eq!(json["prunable"], 1);
+
+    let json = json_output(&repo.path(), &["prune", "--scratch", "--execute"]);
+    let pruned = json["pruned"].as_array().expect("pruned");
+    assert_eq!(pruned.len(), 1);
+    assert!(pruned[0]["branch"].is_null());
+    assert_eq!(pruned[0]["path"], scratch["worktree_path"]);
+    assert!(!Path::new(scratch["worktree_path"].as_str().expect("path")).exists());
+    assert_eq!(json["skipped"][0]["reason"], "no_branch");
+}
+
+#[test]
+fn prune_expires_scratch_worktrees_after_ttl() {
+    let repo = fixtures::TestRepo::new();
+    json_output(&repo.path(), &["scratch"]);
+    run_git(&["config", "wt.scratchTtl", "0"], &repo.path());
+
+    let json = json_output(&repo.path(), &["prune"]);
+    let entry = &json["worktrees"][0];
+    assert_eq!(entry["status"], "expired");
+    assert_eq!(entry["reason"], "ttl_elapsed");
+
+    wt_core()
+        .args([
+            "prune",
+            "--execute",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(detached)"))
+        .stdout(predicate::str::contains("Pruned 1 worktree."));
+}
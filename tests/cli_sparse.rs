@@ -0,0 +1,155 @@
+mod fixtures;
+
+use std::path::Path;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use fixtures::{commit_file, find_worktree_dir};
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+/// A repo with `web/`, `api/` and `docs/` directories plus a root file.
+fn monorepo() -> fixtures::TestRepo {
+    let repo = fixtures::TestRepo::new();
+    for dir in ["web", "api", "docs"] {
+        std::fs::create_dir_all(repo.path().join(dir)).expect("mkdir");
+        commit_file(
+            &repo.path(),
+            &format!("{dir}/index.txt"),
+            dir,
+            &format!("add {dir}"),
+        );
+    }
+    repo
+}
+
+fn json_output(repo: &Path, args: &[&str]) -> serde_json::Value {
+    let repo_str = repo.display().to_string();
+    let mut full = args.to_vec();
+    full.extend(["--repo", &repo_str, "--json"]);
+    let output = wt_core()
+        .args(&full)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+#[test]
+fn add_sparse_checks_out_only_requested_directories() {
+    let repo = monorepo();
+
+    let json = json_output(&repo.path(), &["add", "feature/web", "--sparse", "web"]);
+
+    assert_eq!(json["sparse"], serde_json::json!(["web"]));
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-web");
+    assert!(wt_dir.join("web/index.txt").exists());
+    assert!(
+        wt_dir.join("README.md").exists(),
+        "cone mode keeps root files"
+    );
+    assert!(!wt_dir.join("api").exists());
+    assert!(!wt_dir.join("docs").exists());
+
+    // The main worktree stays a full checkout.
+    assert!(repo.path().join("api/index.txt").exists());
+}
+
+#[test]
+fn add_uses_sparse_config_with_branch_prefix_sections() {
+    let repo = monorepo();
+    std::fs::create_dir_all(repo.path().join(".wt")).expect("mkdir");
+    std::fs::write(
+        repo.path().join(".wt/sparse"),
+        "docs\n\n[feature/api-]\napi\n",
+    )
+    .expect("write");
+
+    json_output(&repo.path(), &["add", "feature/api-auth"]);
+    let api_dir = find_worktree_dir(&repo.path(), "feature-api-auth");
+    assert!(api_dir.join("api/index.txt").exists());
+    assert!(api_dir.join("docs/index.txt").exists());
+    assert!(!api_dir.join("web").exists());
+
+    json_output(&repo.path(), &["add", "bugfix/x"]);
+    let bugfix_dir = find_worktree_dir(&repo.path(), "bugfix-x");
+    assert!(bugfix_dir.join("docs/index.txt").exists());
+    assert!(!bugfix_dir.join("api").exists());
+}
+
+#[test]
+fn sparse_set_add_and_list_adjust_worktree() {
+    let repo = monorepo();
+    json_output(&repo.path(), &["add", "feature/full"]);
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-full");
+
+    let json = json_output(&repo.path(), &["sparse", "list", "feature/full"]);
+    assert_eq!(json["sparse"], false);
+
+    wt_core()
+        .args([
+            "sparse",
+            "add",
+            "feature/full",
+            "web",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("use `wt sparse set`"));
+
+    let json = json_output(&repo.path(), &["sparse", "set", "feature/full", "web"]);
+    assert_eq!(json["sparse"], true);
+    assert_eq!(json["patterns"], serde_json::json!(["web"]));
+    assert!(!wt_dir.join("api").exists());
+
+    let json = json_output(&repo.path(), &["sparse", "add", "feature/full", "api"]);
+    assert_eq!(json["patterns"], serde_json::json!(["api", "web"]));
+    assert!(wt_dir.join("api/index.txt").exists());
+
+    wt_core()
+        .args([
+            "sparse",
+            "list",
+            "feature/full",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  api\n  web\n"));
+}
+
+#[test]
+fn list_shows_sparse_worktrees() {
+    let repo = monorepo();
+    json_output(&repo.path(), &["add", "feature/sparse", "--sparse", "docs"]);
+    json_output(&repo.path(), &["add", "feature/dense"]);
+
+    let json = json_output(&repo.path(), &["list"]);
+    let sparse_of = |branch: &str| {
+        json["worktrees"]
+            .as_array()
+            .expect("worktrees")
+            .iter()
+            .find(|w| w["branch"] == branch)
+            .map(|w| w["sparse"].clone())
+            .expect("listed")
+    };
+    assert_eq!(sparse_of("feature/sparse"), true);
+    assert_eq!(sparse_of("feature/dense"), false);
+    assert_eq!(sparse_of("main"), false);
+
+    wt_core()
+        .args(["list", "--repo", &repo.path().display().to_string()])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"feature/sparse\s+\S+ \[sparse\]").expect("regex"));
+}
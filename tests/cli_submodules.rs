@@ -0,0 +1,184 @@
+mod fixtures;
+
+use std::path::Path;
+use std::process::Command as StdCommand;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use fixtures::{commit_file, find_worktree_dir, run_git};
+
+/// Local submodule URLs need the `file` transport, which Git disables for
+/// submodules by default.
+fn wt_core() -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo_bin!("wt-core"));
+    cmd.env("GIT_CONFIG_COUNT", "1")
+        .env("GIT_CONFIG_KEY_0", "protocol.file.allow")
+        .env("GIT_CONFIG_VALUE_0", "always");
+    cmd
+}
+
+/// A repo with a submodule at `libs/lib`, initialized in the main worktree.
+///
+/// Returns the superproject and the submodule's source repo.
+fn repo_with_submodule() -> (fixtures::TestRepo, fixtures::TestRepo) {
+    let lib = fixtures::TestRepo::new();
+    commit_file(&lib.path(), "lib.txt", "library\n", "add lib");
+
+    let repo = fixtures::TestRepo::new();
+    run_git(
+        &[
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            "-q",
+            &lib.path().display().to_string(),
+            "libs/lib",
+        ],
+        &repo.path(),
+    );
+    run_git(&["commit", "-q", "-m", "add submodule"], &repo.path());
+    (repo, lib)
+}
+
+fn json_output(repo: &Path, args: &[&str]) -> serde_json::Value {
+    let repo_str = repo.display().to_string();
+    let mut full = args.to_vec();
+    full.extend(["--repo", &repo_str, "--json"]);
+    let output = wt_core()
+        .args(&full)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+fn lfs_installed() -> bool {
+    StdCommand::new("git")
+        .args(["lfs", "version"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[test]
+fn add_initializes_submodules_from_local_objects() {
+    let (repo, _lib) = repo_with_submodule();
+
+    let json = json_output(&repo.path(), &["add", "feature/sub"]);
+
+    let step = &json["steps"][0];
+    assert_eq!(step["step"], "submodules");
+    assert_eq!(step["status"], "ok");
+    assert!(
+        step["detail"]
+            .as_str()
+            .expect("detail")
+            .contains("1 from local objects"),
+        "{step}"
+    );
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-sub");
+    assert!(wt_dir.join("libs/lib/lib.txt").exists());
+}
+
+#[test]
+fn add_without_submodules_reports_nothing() {
+    let repo = fixtures::TestRepo::new();
+
+    let json = json_output(&repo.path(), &["add", "feature/plain"]);
+
+    assert!(json.get("steps").is_none(), "{json}");
+}
+
+#[test]
+fn add_no_submodules_skips_initialization() {
+    let (repo, _lib) = repo_with_submodule();
+
+    let json = json_output(&repo.path(), &["add", "feature/bare", "--no-submodules"]);
+
+    assert_eq!(json["steps"][0]["status"], "skipped");
+    assert!(json.get("warnings").is_none(), "{json}");
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-bare");
+    assert!(!wt_dir.join("libs/lib/lib.txt").exists());
+}
+
+#[test]
+fn add_detach_initializes_submodules() {
+    let (repo, _lib) = repo_with_submodule();
+
+    let json = json_output(&repo.path(), &["add", "--detach", "HEAD"]);
+
+    assert_eq!(json["steps"][0]["status"], "ok");
+    let wt_dir = Path::new(json["worktree_path"].as_str().expect("path"));
+    assert!(wt_dir.join("libs/lib/lib.txt").exists());
+}
+
+#[test]
+fn scratch_no_submodules_skips_initialization() {
+    let (repo, _lib) = repo_with_submodule();
+
+    let json = json_output(&repo.path(), &["scratch", "--no-submodules"]);
+
+    assert_eq!(json["steps"][0]["status"], "skipped");
+    let wt_dir = Path::new(json["worktree_path"].as_str().expect("path"));
+    assert!(!wt_dir.join("libs/lib/lib.txt").exists());
+}
+
+#[test]
+fn submodule_failure_keeps_worktree_and_warns() {
+    let (repo, lib) = repo_with_submodule();
+    run_git(&["config", "wt.submoduleReference", "false"], &repo.path());
+    drop(lib);
+
+    let json = json_output(&repo.path(), &["add", "feature/broken"]);
+
+    assert_eq!(json["steps"][0]["status"], "failed");
+    let warning = json["warnings"][0].as_str().expect("warning");
+    assert!(
+        warning.contains("submodules were not initialized"),
+        "{warning}"
+    );
+    assert!(find_worktree_dir(&repo.path(), "feature-broken").exists());
+
+    wt_core()
+        .args([
+            "add",
+            "feature/broken-human",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "warning: submodules were not initialized",
+        ));
+}
+
+#[test]
+fn add_reports_lfs_checkout_step() {
+    let repo = fixtures::TestRepo::new();
+    commit_file(
+        &repo.path(),
+        ".gitattributes",
+        "*.bin filter=lfs diff=lfs merge=lfs -text\n",
+        "track binaries with LFS",
+    );
+
+    let json = json_output(&repo.path(), &["add", "feature/lfs"]);
+
+    let step = &json["steps"][0];
+    assert_eq!(step["step"], "lfs");
+    if lfs_installed() {
+        assert_eq!(step["status"], "ok");
+    } else {
+        assert_eq!(step["status"], "skipped");
+        assert_eq!(step["detail"], "git-lfs is not installed");
+        assert!(json["warnings"][0]
+            .as_str()
+            .expect("warning")
+            .contains("LFS files were left as pointers"));
+    }
+}
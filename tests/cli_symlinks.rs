@@ -186,6 +186,91 @@ fn add_json_includes_symlinks_array() {
     assert_eq!(symlinks[0], "node_modules");
 }
 
+#[test]
+fn add_expands_recursive_globs_with_exclusions() {
+    let repo = fixtures::TestRepo::new();
+    let repo_str = repo.path().display().to_string();
+
+    for dir in [
+        "apps/web/node_modules",
+        "apps/api/node_modules",
+        "legacy/node_modules",
+    ] {
+        fs::create_dir_all(repo.path().join(dir)).expect("mkdir");
+    }
+    fs::create_dir(repo.path().join(".wt")).expect("mkdir .wt");
+    fs::write(
+        repo.path().join(".wt/symlinks"),
+        "**/node_modules\n!legacy/**\n",
+    )
+    .expect("write config");
+
+    // An existing worktree must not be picked up by the second `add`.
+    wt_core()
+        .args(["add", "feat/first", "--repo", &repo_str])
+        .assert()
+        .success();
+
+    let output = wt_core()
+        .args(["add", "feat/recursive", "--repo", &repo_str, "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("invalid json");
+    assert_eq!(
+        json["symlinks"],
+        serde_json::json!(["apps/api/node_modules", "apps/web/node_modules"])
+    );
+}
+
+#[test]
+fn add_reports_link_mode_per_entry() {
+    let repo = fixtures::TestRepo::new();
+    let repo_str = repo.path().display().to_string();
+
+    fs::write(repo.path().join(".env.local"), "SECRET=x").expect("write");
+    fs::create_dir(repo.path().join("node_modules")).expect("mkdir");
+    fs::create_dir(repo.path().join(".wt")).expect("mkdir .wt");
+    fs::write(
+        repo.path().join(".wt/symlinks"),
+        "copy: .env.local\nnode_modules\n",
+    )
+    .expect("write config");
+
+    let output = wt_core()
+        .args(["add", "feat/modes", "--repo", &repo_str, "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("invalid json");
+    assert_eq!(
+        json["links"],
+        serde_json::json!([
+            {"path": ".env.local", "mode": "copy"},
+            {"path": "node_modules", "mode": "symlink"},
+        ])
+    );
+    let wt = std::path::Path::new(json["worktree_path"].as_str().expect("path"));
+    assert!(!wt
+        .join(".env.local")
+        .symlink_metadata()
+        .expect("stat")
+        .is_symlink());
+
+    wt_core()
+        .args(["add", "feat/modes-human", "--repo", &repo_str])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Copied .env.local"))
+        .stdout(predicate::str::contains("Symlinked node_modules"));
+}
+
 #[test]
 fn add_human_reports_symlinks() {
     let repo = fixtures::TestRepo::new();
@@ -0,0 +1,262 @@
+mod fixtures;
+
+use std::path::Path;
+use std::process::Command as StdCommand;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use fixtures::{commit_file, find_worktree_dir, run_git};
+
+fn wt_core() -> Command {
+    Command::new(assert_cmd::cargo_bin!("wt-core"))
+}
+
+/// Environment variables cleared for raw git commands in tests.
+const GIT_ENV_OVERRIDES: &[&str] = &[
+    "GIT_DIR",
+    "GIT_WORK_TREE",
+    "GIT_INDEX_FILE",
+    "GIT_OBJECT_DIRECTORY",
+    "GIT_ALTERNATE_OBJECT_DIRECTORIES",
+    "GIT_PREFIX",
+];
+
+fn git_stdout(args: &[&str], cwd: &Path) -> String {
+    let mut cmd = StdCommand::new("git");
+    cmd.args(args).current_dir(cwd);
+    for var in GIT_ENV_OVERRIDES {
+        cmd.env_remove(var);
+    }
+    let output = cmd.output().expect("failed to run git");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn is_ancestor(cwd: &Path, ancestor: &str, rev: &str) -> bool {
+    let mut cmd = StdCommand::new("git");
+    cmd.args(["merge-base", "--is-ancestor", ancestor, rev])
+        .current_dir(cwd);
+    for var in GIT_ENV_OVERRIDES {
+        cmd.env_remove(var);
+    }
+    cmd.status().expect("failed to run git").success()
+}
+
+fn add_worktree(repo: &Path, branch: &str) {
+    wt_core()
+        .args(["add", branch, "--repo", &repo.display().to_string()])
+        .assert()
+        .success();
+}
+
+fn sync_json(repo: &Path, extra_args: &[&str]) -> serde_json::Value {
+    let repo_str = repo.display().to_string();
+    let mut args = vec!["sync", "--repo", &repo_str, "--json"];
+    args.extend_from_slice(extra_args);
+    let output = wt_core()
+        .args(&args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).expect("invalid json")
+}
+
+fn skip_reason<'a>(json: &'a serde_json::Value, branch: &str) -> &'a str {
+    json["skipped"]
+        .as_array()
+        .expect("skipped array")
+        .iter()
+        .find(|e| e["branch"] == branch)
+        .and_then(|e| e["reason"].as_str())
+        .unwrap_or_else(|| panic!("branch '{branch}' not skipped: {json}"))
+}
+
+#[test]
+fn sync_rebases_branch_onto_mainline() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/behind");
+
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-behind");
+    commit_file(&wt_dir, "feature.txt", "feature", "feature work");
+    commit_file(&repo.path(), "main.txt", "main", "mainline work");
+
+    let json = sync_json(&repo.path(), &[]);
+
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["mainline"], "main");
+    assert_eq!(json["strategy"], "rebase");
+    assert_eq!(json["synced"][0]["branch"], "feature/behind");
+    assert!(is_ancestor(&repo.path(), "main", "feature/behind"));
+
+    // Rebase keeps history linear: no merge commit on the branch.
+    let merges = git_stdout(&["rev-list", "--merges", "main..feature/behind"], &wt_dir);
+    assert!(merges.is_empty(), "rebase should not create merges");
+}
+
+#[test]
+fn sync_merge_strategy_creates_merge_commit() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/merge-sync");
+
+    let wt_dir = find_worktree_dir(&repo.path(), "feature-merge-sync");
+    commit_file(&wt_dir, "feature.txt", "feature", "feature work");
+    commit_file(&repo.path(), "main.txt", "main", "mainline work");
+
+    let json = sync_json(&repo.path(), &["--merge"]);
+
+    assert_eq!(json["strategy"], "merge");
+    assert_eq!(json["synced"][0]["branch"], "feature/merge-sync");
+    let merges = git_stdout(
+        &["rev-list", "--merges", "main..feature/merge-sync"],
+        &wt_dir,
+    );
+    assert!(!merges.is_empty(), "merge strategy should create a merge");
+}
+
+#[test]
+fn sync_skips_dirty_and_up_to_date_worktrees() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/dirty");
+    add_worktree(&repo.path(), "feature/current");
+
+    commit_file(&repo.path(), "main.txt", "main", "mainline work");
+
+    let dirty_dir = find_worktree_dir(&repo.path(), "feature-dirty");
+    std::fs::write(dirty_dir.join("README.md"), "local edit\n").expect("write");
+
+    let current_dir = find_worktree_dir(&repo.path(), "feature-current");
+    run_git(&["merge", "--ff-only", "main"], &current_dir);
+
+    let json = sync_json(&repo.path(), &[]);
+
+    assert_eq!(json["synced"].as_array().expect("synced").len(), 0);
+    assert_eq!(skip_reason(&json, "feature/dirty"), "dirty");
+    assert_eq!(skip_reason(&json, "feature/current"), "up_to_date");
+    assert!(!is_ancestor(&repo.path(), "main", "feature/dirty"));
+}
+
+#[test]
+fn sync_conflict_is_aborted_without_touching_other_worktrees() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/clash");
+    add_worktree(&repo.path(), "feature/fine");
+
+    let clash_dir = find_worktree_dir(&repo.path(), "feature-clash");
+    commit_file(&clash_dir, "shared.txt", "branch side\n", "branch edit");
+    let fine_dir = find_worktree_dir(&repo.path(), "feature-fine");
+    commit_file(&fine_dir, "fine.txt", "fine\n", "fine edit");
+    commit_file(&repo.path(), "shared.txt", "main side\n", "main edit");
+
+    let json = sync_json(&repo.path(), &[]);
+
+    assert_eq!(skip_reason(&json, "feature/clash"), "conflict");
+    assert_eq!(json["synced"][0]["branch"], "feature/fine");
+    assert!(!json["warnings"].as_array().expect("warnings").is_empty());
+
+    // The conflicting worktree is restored: no rebase in progress, clean tree.
+    let status = git_stdout(&["status", "--porcelain"], &clash_dir);
+    assert!(status.is_empty(), "worktree should be clean: {status}");
+    let git_dir = git_stdout(&["rev-parse", "--git-dir"], &clash_dir);
+    assert!(!Path::new(&git_dir).join("rebase-merge").exists());
+    assert_eq!(
+        std::fs::read_to_string(clash_dir.join("shared.txt")).expect("read"),
+        "branch side\n"
+    );
+}
+
+#[test]
+fn sync_failure_without_conflicts_is_not_reported_as_conflict() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/locked");
+    let locked_dir = find_worktree_dir(&repo.path(), "feature-locked");
+    commit_file(&locked_dir, "locked.txt", "locked\n", "branch edit");
+    commit_file(&repo.path(), "main.txt", "main\n", "main edit");
+    let git_dir = git_stdout(&["rev-parse", "--absolute-git-dir"], &locked_dir);
+    std::fs::write(Path::new(&git_dir).join("index.lock"), "").expect("write index.lock");
+
+    let json = sync_json(&repo.path(), &[]);
+
+    assert_eq!(skip_reason(&json, "feature/locked"), "sync_failed");
+    let warning = json["warnings"][0].as_str().expect("warning");
+    assert!(warning.contains("failed"), "{warning}");
+    assert!(!warning.contains("conflict"), "{warning}");
+}
+
+#[test]
+fn sync_only_named_branches() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/one");
+    add_worktree(&repo.path(), "feature/two");
+    commit_file(&repo.path(), "main.txt", "main", "mainline work");
+
+    let json = sync_json(&repo.path(), &["feature/one"]);
+
+    let synced = json["synced"].as_array().expect("synced");
+    assert_eq!(synced.len(), 1);
+    assert_eq!(synced[0]["branch"], "feature/one");
+    assert!(!is_ancestor(&repo.path(), "main", "feature/two"));
+}
+
+#[test]
+fn sync_unknown_branch_is_usage_error() {
+    let repo = fixtures::TestRepo::new();
+
+    wt_core()
+        .args([
+            "sync",
+            "feature/missing",
+            "--repo",
+            &repo.path().display().to_string(),
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "no worktree found for branch 'feature/missing'",
+        ));
+}
+
+#[test]
+fn sync_fetches_and_uses_remote_mainline() {
+    let cloned = fixtures::ClonedTestRepo::new();
+    let repo = cloned.path();
+    add_worktree(&repo, "feature/remote");
+
+    // Another contributor pushes to origin/main.
+    let other = tempfile::TempDir::new().expect("tempdir");
+    run_git(
+        &[
+            "clone",
+            &cloned.origin_path().display().to_string(),
+            &other.path().display().to_string(),
+        ],
+        other.path(),
+    );
+    run_git(&["config", "user.email", "test@test.com"], other.path());
+    run_git(&["config", "user.name", "Test"], other.path());
+    commit_file(other.path(), "upstream.txt", "upstream", "upstream work");
+    run_git(&["push", "origin", "main"], other.path());
+
+    let json = sync_json(&repo, &[]);
+
+    assert_eq!(json["mainline"], "origin/main");
+    assert_eq!(json["synced"][0]["branch"], "feature/remote");
+    assert!(is_ancestor(&repo, "origin/main", "feature/remote"));
+}
+
+#[test]
+fn sync_human_output_summarizes() {
+    let repo = fixtures::TestRepo::new();
+    add_worktree(&repo.path(), "feature/human");
+    commit_file(&repo.path(), "main.txt", "main", "mainline work");
+
+    wt_core()
+        .args(["sync", "--repo", &repo.path().display().to_string()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Mainline: main"))
+        .stdout(predicate::str::contains("Rebased feature/human"))
+        .stdout(predicate::str::contains("Synced 1 worktree."));
+}
@@ -0,0 +1,303 @@
+mod fixtures;
+
+use std::path::{Path, PathBuf};
+use std::process::Command as StdCommand;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+use fixtures::{commit_file, run_git};
+
+/// A tmux server on a private socket (`TMUX_TMPDIR`), killed on drop so
+/// tests never see or touch the developer's own sessions.
+struct TmuxServer {
+    dir: TempDir,
+}
+
+impl TmuxServer {
+    /// `None` when tmux is not installed.
+    fn start() -> Option<Self> {
+        let installed = StdCommand::new("tmux")
+            .arg("-V")
+            .output()
+            .is_ok_and(|o| o.status.success());
+        if !installed {
+            eprintln!("tmux is not installed; skipping");
+            return None;
+        }
+        Some(Self {
+            dir: TempDir::new().expect("tempdir"),
+        })
+    }
+
+    fn wt_core(&self) -> Command {
+        let mut cmd = Command::new(assert_cmd::cargo_bin!("wt-core"));
+        cmd.env("TMUX_TMPDIR", self.dir.path()).env_remove("TMUX");
+        cmd
+    }
+
+    fn tmux(&self, args: &[&str]) -> String {
+        let output = StdCommand::new("tmux")
+            .args(args)
+            .env("TMUX_TMPDIR", self.dir.path())
+            .env_remove("TMUX")
+            .output()
+            .expect("failed to run tmux");
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    fn sessions(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tmux(&["list-sessions", "-F", "#{session_name}"])
+            .lines()
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn add(&self, repo: &Path, args: &[&str]) -> PathBuf {
+        let output = self
+            .wt_core()
+            .arg("add")
+            .args(args)
+            .arg("--print-cd-path")
+            .current_dir(repo)
+            .assert()
+            .success();
+        PathBuf::from(String::from_utf8_lossy(&output.get_output().stdout).trim())
+    }
+}
+
+impl Drop for TmuxServer {
+    fn drop(&mut self) {
+        self.tmux(&["kill-server"]);
+    }
+}
+
+#[test]
+fn add_tmux_creates_a_session_named_after_the_branch_slug() {
+    let Some(server) = TmuxServer::start() else {
+        return;
+    };
+    let repo = fixtures::TestRepo::new();
+    let wt = server.add(&repo.path(), &["feature/auth", "--tmux"]);
+
+    assert_eq!(server.sessions(), ["feature-auth"]);
+    assert_eq!(
+        server
+            .tmux(&[
+                "list-panes",
+                "-t",
+                "=feature-auth:",
+                "-F",
+                "#{pane_start_path}"
+            ])
+            .trim(),
+        wt.display().to_string()
+    );
+
+    run_git(&["config", "wt.tmux", "true"], &repo.path());
+    let output = server
+        .wt_core()
+        .args(["list", "--json"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.get_output().stdout).expect("valid json");
+    assert!(json["worktrees"][0].get("tmux_session").is_none());
+    assert_eq!(json["worktrees"][1]["tmux_session"], "feature-auth");
+
+    server
+        .wt_core()
+        .arg("list")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[tmux: feature-auth]"));
+}
+
+#[test]
+fn go_reuses_the_session_and_follows_the_config() {
+    let Some(server) = TmuxServer::start() else {
+        return;
+    };
+    let repo = fixtures::TestRepo::new();
+    server.add(&repo.path(), &["feature/one"]);
+    server.add(&repo.path(), &["feature/two"]);
+    assert!(server.sessions().is_empty());
+
+    run_git(&["config", "wt.tmux", "true"], &repo.path());
+    let go = |branch: &str, extra: &[&str]| {
+        server
+            .wt_core()
+            .args(["go", branch, "--print-cd-path"])
+            .args(extra)
+            .current_dir(repo.path())
+            .assert()
+            .success()
+    };
+
+    go("feature/one", &[]).stderr(predicate::str::contains(
+        "Created tmux session 'feature-one'",
+    ));
+    go("feature/one", &[]).stderr(predicate::str::contains("Found tmux session 'feature-one'"));
+    go("feature/two", &["--no-tmux"]).stderr(predicate::str::contains("tmux").not());
+    assert_eq!(server.sessions(), ["feature-one"]);
+}
+
+#[test]
+fn new_sessions_get_the_windows_of_the_layout_file() {
+    let Some(server) = TmuxServer::start() else {
+        return;
+    };
+    let repo = fixtures::TestRepo::new();
+    std::fs::create_dir(repo.path().join(".wt")).expect("mkdir");
+    commit_file(
+        &repo.path(),
+        ".wt/tmux",
+        "# window command\neditor\nserver echo serving\nlogs\n",
+        "add tmux layout",
+    );
+    commit_file(&repo.path(), "alt-layout", "only\n", "add alt layout");
+
+    server.add(&repo.path(), &["feature/layout", "--tmux"]);
+    assert_eq!(
+        server.tmux(&[
+            "list-windows",
+            "-t",
+            "=feature-layout",
+            "-F",
+            "#{window_name}"
+        ]),
+        "editor\nserver\nlogs\n"
+    );
+
+    run_git(&["config", "wt.tmuxLayout", "alt-layout"], &repo.path());
+    server.add(&repo.path(), &["feature/alt", "--tmux"]);
+    assert_eq!(
+        server.tmux(&["list-windows", "-t", "=feature-alt", "-F", "#{window_name}"]),
+        "only\n"
+    );
+}
+
+#[test]
+fn a_taken_name_falls_back_to_the_directory_name_and_is_never_killed() {
+    let Some(server) = TmuxServer::start() else {
+        return;
+    };
+    let repo = fixtures::TestRepo::new();
+    run_git(&["config", "wt.tmux", "true"], &repo.path());
+    server.tmux(&["new-session", "-d", "-s", "feature-auth"]);
+
+    let wt = server.add(&repo.path(), &["feature/auth", "--tmux"]);
+    let dir = wt
+        .file_name()
+        .and_then(|n| n.to_str())
+        .expect("dir name")
+        .to_string();
+    assert_eq!(server.sessions(), ["feature-auth".to_string(), dir]);
+
+    server
+        .wt_core()
+        .args(["remove", "feature/auth"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    assert_eq!(server.sessions(), ["feature-auth"]);
+}
+
+#[test]
+fn remove_merge_and_prune_kill_the_worktree_session() {
+    let Some(server) = TmuxServer::start() else {
+        return;
+    };
+    let repo = fixtures::TestRepo::new();
+    run_git(&["config", "wt.tmux", "true"], &repo.path());
+    server.add(&repo.path(), &["feature/removed", "--tmux"]);
+    let merged = server.add(&repo.path(), &["feature/merged", "--tmux"]);
+    let pruned = server.add(&repo.path(), &["feature/pruned", "--tmux"]);
+    let kept = server.add(&repo.path(), &["feature/kept", "--tmux"]);
+    commit_file(&kept, "kept.txt", "kept\n", "unmerged work");
+    commit_file(&merged, "merged.txt", "merged\n", "merged work");
+    commit_file(&pruned, "pruned.txt", "pruned\n", "pruned work");
+    run_git(&["merge", "feature/pruned"], &repo.path());
+
+    for args in [
+        &["remove", "feature/removed"][..],
+        &["merge", "feature/merged"],
+        &["prune", "--execute"],
+    ] {
+        server
+            .wt_core()
+            .args(args)
+            .current_dir(repo.path())
+            .assert()
+            .success();
+    }
+
+    assert_eq!(server.sessions(), ["feature-kept"]);
+}
+
+#[test]
+fn sessions_are_left_alone_without_wt_tmux() {
+    let Some(server) = TmuxServer::start() else {
+        return;
+    };
+    let repo = fixtures::TestRepo::new();
+    server.add(&repo.path(), &["feature/auth", "--tmux"]);
+
+    let output = server
+        .wt_core()
+        .args(["list", "--json"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.get_output().stdout).expect("valid json");
+    assert!(json["worktrees"][1].get("tmux_session").is_none());
+
+    server
+        .wt_core()
+        .args(["remove", "feature/auth"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    assert_eq!(server.sessions(), ["feature-auth"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn list_never_runs_tmux_without_wt_tmux() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let repo = fixtures::TestRepo::new();
+    let dir = TempDir::new().expect("tempdir");
+    let bin = dir.path().join("bin");
+    let sockets = dir.path().join("sockets");
+    let marker = dir.path().join("ran-tmux");
+    std::fs::create_dir_all(&bin).expect("mkdir bin");
+    std::fs::create_dir_all(sockets.join("tmux-1000")).expect("mkdir sockets");
+    let fake = bin.join("tmux");
+    std::fs::write(
+        &fake,
+        format!("#!/bin/sh\necho \"$@\" >> '{}'\n", marker.display()),
+    )
+    .expect("write fake tmux");
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+    let path = format!("{}:{}", bin.display(), std::env::var("PATH").expect("PATH"));
+
+    for args in [&["list"][..], &["list", "--json"]] {
+        Command::new(assert_cmd::cargo_bin!("wt-core"))
+            .args(args)
+            .current_dir(repo.path())
+            .env("PATH", &path)
+            .env("TMUX_TMPDIR", &sockets)
+            .env_remove("TMUX")
+            .assert()
+            .success();
+    }
+    assert!(!marker.exists(), "list ran tmux");
+}
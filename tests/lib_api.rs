@@ -0,0 +1,133 @@
+//! The library API used directly, without going through the binary.
+
+mod fixtures;
+
+use wt_core::domain::{BranchName, WorktreeStatsStatus};
+use wt_core::error::ExitCode;
+use wt_core::worktree::{self, AddOptions, DiagLevel, IntegrationStatus};
+
+#[test]
+fn add_go_and_remove_through_the_library() {
+    let repo = fixtures::TestRepo::new();
+    let root = worktree::repo_root(&repo.path()).expect("repo root");
+    let branch = BranchName::new("feature/lib");
+
+    let added = worktree::add(&root, &branch, &AddOptions::default()).expect("add");
+    assert!(added.worktree_path.exists());
+    assert_eq!(
+        added.worktree_path.parent(),
+        Some(root.worktrees_dir().as_path())
+    );
+
+    let go = worktree::go(&root, &branch).expect("go");
+    assert_eq!(go.worktree_path, added.worktree_path);
+
+    let listed = worktree::list_worktrees(&root).expect("list");
+    assert!(listed
+        .iter()
+        .any(|wt| wt.branch.as_deref() == Some("feature/lib")));
+
+    let removed = worktree::remove(&root, Some(&branch), false).expect("remove");
+    assert_eq!(removed.removed_path, added.worktree_path);
+    assert!(!added.worktree_path.exists());
+}
+
+#[test]
+fn merge_and_prune_through_the_library() {
+    let repo = fixtures::TestRepo::new();
+    let root = worktree::repo_root(&repo.path()).expect("repo root");
+    let merged = BranchName::new("feature/merged");
+    let wt = worktree::add(&root, &merged, &AddOptions::default()).expect("add");
+    fixtures::commit_file(&wt.worktree_path, "lib.txt", "lib\n", "add lib");
+
+    let result = worktree::merge(&root, Some(&merged), None, false, true).expect("merge");
+    assert_eq!(result.branch.as_str(), "feature/merged");
+    assert!(!result.cleaned_up);
+
+    let dry_run = worktree::prune_dry_run(&root, None, false).expect("prune dry run");
+    let entry = dry_run
+        .entries
+        .iter()
+        .find(|e| e.branch.as_deref() == Some("feature/merged"))
+        .expect("entry");
+    assert!(matches!(entry.status, IntegrationStatus::Integrated(_)));
+
+    let executed = worktree::prune_execute(&root, None, false, false).expect("prune");
+    assert_eq!(executed.pruned.len(), 1);
+}
+
+#[test]
+fn errors_carry_stable_exit_codes() {
+    let dir = tempfile::TempDir::new().expect("tempdir");
+
+    let err = worktree::repo_root(dir.path()).expect_err("not a repo");
+
+    assert_eq!(err.code, ExitCode::NotARepo);
+}
+
+#[test]
+fn doctor_through_the_library() {
+    let repo = fixtures::TestRepo::new();
+    let root = worktree::repo_root(&repo.path()).expect("repo root");
+
+    let diags = worktree::doctor(&root).expect("doctor");
+
+    assert!(diags.iter().all(|d| d.level == DiagLevel::Ok));
+}
+
+#[test]
+fn overview_lock_and_prune_selected_through_the_library() {
+    let repo = fixtures::TestRepo::new();
+    let root = worktree::repo_root(&repo.path()).expect("repo root");
+    let mut paths = Vec::new();
+    for name in ["feature/kept", "feature/pruned"] {
+        let branch = BranchName::new(name);
+        let wt = worktree::add(&root, &branch, &AddOptions::default()).expect("add");
+        fixtures::commit_file(&wt.worktree_path, "lib.txt", name, "add lib");
+        worktree::merge(&root, Some(&branch), None, false, true).expect("merge");
+        paths.push(wt.worktree_path);
+    }
+    let active = BranchName::new("feature/active");
+    let wt = worktree::add(&root, &active, &AddOptions::default()).expect("add");
+    fixtures::commit_file(&wt.worktree_path, "active.txt", "wip\n", "start");
+    std::fs::write(wt.worktree_path.join("active.txt"), "changed\n").expect("write");
+
+    worktree::lock(&root, &paths[0], None).expect("lock");
+    let overview = worktree::overview(&root).expect("overview");
+    let entry = |branch: &str| {
+        overview
+            .entries
+            .iter()
+            .find(|e| e.worktree.branch.as_deref() == Some(branch))
+            .expect("entry")
+    };
+    assert!(overview.entries[0].worktree.is_main);
+    assert!(overview.entries[0].integration.is_none());
+    assert!(entry("feature/kept").locked);
+    assert!(matches!(
+        entry("feature/kept").integration,
+        Some(IntegrationStatus::Integrated(_))
+    ));
+    let active_entry = entry("feature/active");
+    assert_eq!(active_entry.dirty, Some(true));
+    assert!(!active_entry.locked);
+    assert!(matches!(
+        active_entry.integration,
+        Some(IntegrationStatus::NotIntegrated)
+    ));
+    match &active_entry.stats {
+        WorktreeStatsStatus::Available(stats) => assert_eq!(stats.commits_ahead, 1),
+        other => panic!("expected stats, got {other:?}"),
+    }
+
+    let pruned =
+        worktree::prune_selected(&root, &[paths[1].clone(), wt.worktree_path.clone()], false)
+            .expect("prune selected");
+    assert_eq!(pruned.pruned.len(), 1);
+    assert!(!paths[1].exists());
+    assert!(paths[0].exists());
+    assert!(wt.worktree_path.exists());
+
+    worktree::unlock(&root, &paths[0]).expect("unlock");
+    assert!(!worktree::is_locked(&paths[0]));
+}